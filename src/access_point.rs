@@ -1,22 +1,53 @@
-use std::fs::File;
-use std::io::Read;
 use std::str;
-use std::str::FromStr;
+use std::time::Duration;
 
 use anyhow::{anyhow, Result};
-use ipnet::Ipv4Net;
+use clap::ValueEnum;
+use glib::prelude::ObjectExt;
+use ipnet::Ipv6Net;
 use nm::*;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use tracing::{debug, info, instrument, warn};
 
 use crate::{
+    capability::{check_feature, Feature},
     cli::AccessPointArgs,
     connection::*,
-    station::{create_sta_connection, StationOpts},
-    util::{deserialize_password, DEFAULT_IP4_ADDR},
+    export::{export_connection, get_common_opts, get_ssid_str},
+    station::StationOpts,
+    status::*,
+    util::{
+        apply_config_overrides, config_fields, confirm_action, deserialize_ip4_addrs,
+        deserialize_ip6_addr, deserialize_password, read_config, serialize_ip6_addr,
+        validate_cloned_mac, validate_ifname, validate_mtu, validate_ssid, DEFAULT_IP4_ADDR,
+    },
 };
 
-#[derive(Default, Deserialize, PartialEq, Clone, Debug)]
+#[derive(ValueEnum, Serialize, Deserialize, PartialEq, Copy, Clone, Debug)]
+pub enum WirelessBand {
+    /// 5GHz
+    A,
+    /// 2.4GHz
+    Bg,
+}
+
+#[derive(ValueEnum, Serialize, Deserialize, Default, PartialEq, Copy, Clone, Debug)]
+pub enum WirelessMode {
+    /// Access point
+    #[default]
+    Ap,
+    /// Ad-hoc (IBSS)
+    Adhoc,
+}
+
+/// Channel widths (in MHz) which may be requested for an access point
+pub const CHANNEL_WIDTHS: [u32; 4] = [20, 40, 80, 160];
+
+/// System regulatory domain country code, as set by "iw reg set"
+const REGULATORY_DOMAIN_PATH: &str = "/sys/module/cfg80211/parameters/ieee80211_regdom";
+
+#[serde_with::skip_serializing_none]
+#[derive(Default, Serialize, Deserialize, PartialEq, Clone, Debug)]
 pub struct AccessPointOpts {
     #[serde(rename = "wireless_interface")]
     #[serde(default)]
@@ -27,35 +58,238 @@ pub struct AccessPointOpts {
     #[serde(with = "serde_with::rust::string_empty_as_none")]
     pub ssid: Option<String>,
 
+    /// Connection name shown by NetworkManager. If not specified, default to the SSID.
+    /// Connections are matched by interface and SSID, never by name
+    #[serde(default)]
+    #[serde(with = "serde_with::rust::string_empty_as_none")]
+    pub connection_id: Option<String>,
+
     /// Must be 8 characters or longer
     #[serde(default)]
     #[serde(deserialize_with = "deserialize_password")]
     pub password: Option<String>,
 
+    /// Explicitly create an open (passwordless) access point.
+    /// Creation requires either this or a password
+    #[serde(default)]
+    pub open: bool,
+
+    /// Static IPv4 addresses, each including prefix length.
+    /// Accepts a single address or a list of addresses
+    #[serde(default, alias = "ip4_addr", skip_serializing_if = "Vec::is_empty")]
+    #[serde(deserialize_with = "deserialize_ip4_addrs")]
+    pub ip4_addrs: Vec<String>,
+
+    /// IPv4 method, e.g. "shared" to provide NAT and DHCP to downstream clients.
+    /// If not specified, static using IPv4 addresses or the default 192.0.2.1/24
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ip4_method: Option<Ip4Method>,
+
+    /// IPv4 gateway. Requires a static IPv4 address
     #[serde(default)]
     #[serde(with = "serde_with::rust::string_empty_as_none")]
-    pub ip4_addr: Option<String>,
+    pub gateway: Option<String>,
+
+    /// IPv4 DNS servers
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub dns: Vec<String>,
+
+    /// Static IPv4 routes, each "dest/prefix via gateway [metric]",
+    /// e.g. "198.51.100.0/24 via 192.0.2.254 100"
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub routes: Vec<String>,
+
+    /// Static IPv6 address, including prefix length. If not specified, default to auto
+    #[serde(default)]
+    #[serde(deserialize_with = "deserialize_ip6_addr")]
+    #[serde(serialize_with = "serialize_ip6_addr")]
+    pub ip6_addr: Option<Ipv6Net>,
+
+    /// If not specified, use NetworkManager default (single)
+    #[serde(default)]
+    pub multi_connect: Option<MultiConnect>,
+
+    /// If not specified, default to disabled
+    #[serde(default)]
+    pub autoconnect: Option<bool>,
+
+    /// If not specified, use NetworkManager default (global DNS configuration)
+    #[serde(default)]
+    pub dns_over_tls: Option<DnsOverTls>,
+
+    /// Seed for generated stable MAC and IPv6 privacy addresses. May contain
+    /// NetworkManager substitution tokens, e.g. "${CONNECTION}"
+    #[serde(default)]
+    pub stable_id: Option<String>,
+
+    /// Firewalld zone the connection is placed in, e.g. "public". If not
+    /// specified, use firewalld default zone
+    #[serde(default)]
+    pub zone: Option<String>,
+
+    /// MAC address used instead of device's permanent address. Either an
+    /// explicit MAC address or one of "random", "stable", or "permanent"
+    #[serde(default)]
+    #[serde(with = "serde_with::rust::string_empty_as_none")]
+    pub cloned_mac: Option<String>,
+
+    /// Maximum transmission unit in bytes, between 68 and 9000. When
+    /// unspecified, NetworkManager uses the device's default
+    #[serde(default)]
+    pub mtu: Option<u32>,
+
+    /// Wireless power saving. Disabling reduces latency at the cost of power. If not
+    /// specified, use NetworkManager default (global configuration)
+    #[serde(default)]
+    pub powersave: Option<bool>,
+
+    /// IPv4 route metric, lower is preferred, e.g. to prefer a bond over a
+    /// station when both are active. When unspecified or -1, NetworkManager chooses
+    #[serde(default)]
+    pub route_metric: Option<i64>,
+
+    /// Requires a password. If not specified, default to WPA-PSK
+    #[serde(default)]
+    pub key_mgmt: Option<KeyMgmt>,
+
+    /// Minimum password length, between 8 and 63. Only raises the WPA-PSK
+    /// minimum of 8 characters and does not apply to 64 character hex keys
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub psk_min_len: Option<usize>,
+
+    /// Ad-hoc networks must be open. If not specified, default to AP
+    #[serde(default)]
+    pub mode: Option<WirelessMode>,
+
+    /// If not specified, NetworkManager chooses band automatically
+    #[serde(default)]
+    pub band: Option<WirelessBand>,
+
+    /// Channel width in MHz. Widths of 80MHz and wider require the 5GHz ("A") band
+    #[serde(default)]
+    pub channel_width: Option<u32>,
+
+    /// Maximum number of associated clients. Validated, but NetworkManager
+    /// wireless settings have no equivalent, so otherwise ignored
+    #[serde(default)]
+    pub max_clients: Option<u32>,
+
+    /// Prevent associated clients from communicating with each other. Requires
+    /// NetworkManager 1.28 or newer, ignored otherwise
+    #[serde(default)]
+    pub client_isolation: Option<bool>,
+
+    /// Regulatory domain as a two-letter ISO 3166-1 country code, e.g. "US". Determines
+    /// legal channels and transmit power, but must be set system-wide, not per connection
+    #[serde(default)]
+    pub country: Option<String>,
+
+    /// Time to wait for connection to (de)activate. When unspecified, wait
+    /// indefinitely to activate, or a short time to deactivate before deleting
+    #[serde(skip)]
+    pub timeout: Option<Duration>,
+
+    /// Retries for transient connection activation failures
+    #[serde(skip)]
+    pub retry: RetryPolicy,
+
+    /// Format used when printing connection status
+    #[serde(skip)]
+    pub output: OutputFormat,
+
+    /// Validate and build connection without modifying NetworkManager state
+    #[serde(skip)]
+    pub dry_run: bool,
+
+    /// Return once activation starts rather than waiting for it to finish
+    #[serde(skip)]
+    pub no_wait: bool,
+
+    /// Abort rather than deactivate conflicting active connections
+    #[serde(skip)]
+    pub no_preempt: bool,
+
+    /// Skip confirmation before destructive operations
+    #[serde(skip)]
+    pub yes: bool,
+
+    /// Target connection with this UUID rather than one matching other options
+    #[serde(skip)]
+    pub uuid: Option<String>,
+
+    /// Reject static IPv4 addresses overlapping subnets of active connections
+    #[serde(skip)]
+    pub strict: bool,
+
+    /// Delete any existing matching connection rather than fail during creation
+    #[serde(skip)]
+    pub force: bool,
 }
 
 impl TryFrom<AccessPointArgs> for AccessPointOpts {
     type Error = anyhow::Error;
 
     fn try_from(args: AccessPointArgs) -> Result<Self, Self::Error> {
-        if let Some(cfg) = args.config {
-            let mut buf = vec![];
-            let mut cfg_file = File::open(cfg)?;
-            cfg_file.read_to_end(&mut buf)?;
-
-            let config = str::from_utf8(buf.as_slice())?;
-            return parse_access_point_opts(config);
-        }
-
-        Ok(AccessPointOpts {
-            wireless_ifname: args.wireless_ifname,
-            ssid: args.ssid,
-            ip4_addr: args.ip4_addr,
-            password: args.password,
-        })
+        let mut opts = match args.config {
+            Some(cfg) => {
+                let config = read_config(&cfg)?;
+                let config = apply_config_overrides(
+                    &config,
+                    &args.overrides,
+                    config_fields::<AccessPointOpts>(),
+                )?;
+                parse_access_point_opts(&config)?
+            }
+            None => AccessPointOpts {
+                wireless_ifname: args.wireless_ifname,
+                ssid: args.ssid,
+                connection_id: args.connection_id,
+                ip4_addrs: args
+                    .ip4_addr
+                    .into_iter()
+                    .chain(args.extra_ip4_addrs)
+                    .collect(),
+                ip4_method: args.ip4_method,
+                gateway: args.gateway,
+                dns: args.dns,
+                routes: args.routes,
+                ip6_addr: args.ip6_addr,
+                multi_connect: args.multi_connect,
+                autoconnect: args.autoconnect,
+                stable_id: args.stable_id,
+                zone: args.zone,
+                cloned_mac: args.cloned_mac,
+                mtu: args.mtu,
+                powersave: args.powersave,
+                route_metric: args.route_metric,
+                dns_over_tls: args.dns_over_tls,
+                password: args.password,
+                open: args.open,
+                key_mgmt: args.key_mgmt,
+                psk_min_len: args.psk_min_len,
+                mode: args.mode,
+                band: args.band,
+                channel_width: args.channel_width,
+                max_clients: args.max_clients,
+                client_isolation: args.client_isolation,
+                country: args.country,
+                ..Default::default()
+            },
+        };
+
+        // Command-line only options, applied whether or not config file used
+        opts.timeout = args.timeout.map(Duration::from_secs);
+        opts.retry = args.retry;
+        opts.output = args.output;
+        opts.dry_run = args.dry_run;
+        opts.no_wait = args.no_wait;
+        opts.no_preempt = args.no_preempt;
+        opts.yes = args.yes;
+        opts.uuid = args.uuid;
+        opts.strict = args.strict;
+        opts.force = args.force;
+
+        Ok(opts)
     }
 }
 
@@ -65,7 +299,14 @@ impl From<StationOpts> for AccessPointOpts {
             wireless_ifname: opts.wireless_ifname,
             ssid: opts.ssid,
             password: opts.password,
-            ip4_addr: opts.ip4_addr,
+            key_mgmt: opts.key_mgmt,
+            ip4_addrs: opts.ip4_addrs,
+            ip4_method: opts.ip4_method,
+            gateway: opts.gateway,
+            dns: opts.dns,
+            ip6_addr: opts.ip6_addr,
+            multi_connect: opts.multi_connect,
+            ..Default::default()
         }
     }
 }
@@ -80,7 +321,7 @@ fn parse_access_point_opts(config: &str) -> Result<AccessPointOpts> {
 // that share same backing interfaces etc. This implies that
 // we allow duplicate connections. It's just up to the user to manage them
 #[instrument(skip(client), err)]
-pub async fn create_access_point(client: &Client, opts: AccessPointOpts) -> Result<()> {
+pub async fn create_access_point(client: &impl NmClient, opts: AccessPointOpts) -> Result<()> {
     let wireless_ifname = match &opts.wireless_ifname {
         Some(ifname) => ifname,
         None => return Err(anyhow!("Required wireless interface not specified")),
@@ -91,45 +332,39 @@ pub async fn create_access_point(client: &Client, opts: AccessPointOpts) -> Resu
         None => return Err(anyhow!("Required SSID not specified")),
     };
 
+    validate_ifname(wireless_ifname)?;
+
+    validate_open_access_point(opts.password.as_deref(), opts.open)?;
+    validate_wireless_mode(opts.mode.unwrap_or_default(), opts.password.as_deref())?;
+
     // Create AP struct here so we can comprehensively search
     // for any matching existing connection, should it exist
     // Does not add connection to Network Manager, that happens later
-    //
-    // STA connection added for searching purposes. Does not add
-    // connection to Network Manager, it is purely local
     let ap_conn = create_access_point_connection(&opts)?;
-    let sta_conn = create_sta_connection(&opts.clone().into())?;
 
-    // Check for and deactivate any existing active station connections
-    // which share the same wireless interface.
-    match get_active_connection(client, DeviceType::Wifi, &sta_conn) {
-        Some(c) => {
-            debug!(
-                "Found active wireless connection with ifname \"{}\", deactivating",
-                wireless_ifname
-            );
-            client.deactivate_connection_future(&c).await?;
-        }
-        None => debug!(
-            "No matching active wireless connections for interface \"{}\"",
-            wireless_ifname
-        ),
+    // Duplicate access point connections are otherwise allowed,
+    // so only replace an existing matching connection when forced
+    let existing_conn = match opts.force {
+        true => get_connection(client, DeviceType::Wifi, &ap_conn),
+        false => None,
     };
+    if let Some(existing_conn) = &existing_conn {
+        // Keep UUID stable for anything referencing the replaced connection
+        preserve_connection_uuid(&ap_conn, existing_conn);
+    }
 
-    // Check for and deactivate any matching AP conn
-    match get_active_connection(client, DeviceType::Wifi, &ap_conn) {
-        Some(c) => {
-            debug!(
-                "Found active wireless connection with ifname \"{}\", deactivating",
-                wireless_ifname
-            );
-            client.deactivate_connection_future(&c).await?;
-        }
-        None => debug!(
-            "No matching active wireless connections for interface \"{}\"",
-            wireless_ifname
-        ),
-    };
+    // Deactivate any active station or access point connections on the interface
+    deactivate_connections_on_iface(
+        client,
+        wireless_ifname,
+        DeviceType::Wifi,
+        opts.no_preempt,
+        opts.dry_run,
+        opts.yes,
+    )
+    .await?;
+
+    check_ip4_conflicts(client, &opts.ip4_addrs, &[wireless_ifname], opts.strict)?;
 
     let wireless_dev = match client.device_by_iface(wireless_ifname.as_str()) {
         Some(device) => device,
@@ -141,15 +376,43 @@ pub async fn create_access_point(client: &Client, opts: AccessPointOpts) -> Resu
         }
     };
 
+    if opts.dry_run {
+        if existing_conn.is_some() {
+            print_dry_run(&format!(
+                "delete existing access point connection \"{}\"",
+                ssid
+            ));
+        }
+        print_dry_run(&format!(
+            "create and activate access point connection \"{}\" on interface \"{}\"",
+            ssid, wireless_ifname
+        ));
+        return Ok(());
+    }
+
+    // Only delete replaced connection once all checks pass, so
+    // a failed check leaves the existing connection intact
+    if existing_conn.is_some() {
+        info!("Replacing existing access point connection \"{}\"", ssid);
+        delete_access_point(client, opts.clone()).await?;
+    }
+
     info!("Creating access point connection \"{}\"", ssid);
-    let ap_conn = client.add_connection_future(&ap_conn, true).await?;
+    let ap_conn = client
+        .add_connection_future(&ap_conn, true)
+        .await
+        .map_err(map_nm_error)?;
 
     info!("Activating access point connection \"{}\"", ssid);
-    let ap_conn = client
-        .activate_connection_future(Some(&ap_conn), Some(&wireless_dev), None)
-        .await?;
+    let ap_conn =
+        activate_connection_with_retry(client, &ap_conn, &wireless_dev, opts.retry).await?;
 
-    let res = wait_for_connection_to_activate(&ap_conn).await;
+    if opts.no_wait {
+        print_activation_state(&ap_conn, ssid);
+        return Ok(());
+    }
+
+    let res = wait_for_connection_to_activate(&ap_conn, opts.timeout).await;
 
     if res.is_ok() {
         info!("Activated access point connection \"{}\"", ssid);
@@ -158,12 +421,7 @@ pub async fn create_access_point(client: &Client, opts: AccessPointOpts) -> Resu
 }
 
 #[instrument(skip(client), err)]
-pub async fn delete_access_point(client: &Client, opts: AccessPointOpts) -> Result<()> {
-    let wireless_ifname = match &opts.wireless_ifname {
-        Some(ifname) => ifname,
-        None => return Err(anyhow!("Required wireless interface not specified")),
-    };
-
+pub async fn delete_access_point(client: &impl NmClient, opts: AccessPointOpts) -> Result<()> {
     let ssid = match &opts.ssid {
         Some(ssid) => ssid,
         None => return Err(anyhow!("Required SSID not specified")),
@@ -172,7 +430,8 @@ pub async fn delete_access_point(client: &Client, opts: AccessPointOpts) -> Resu
     let ap_conn = create_access_point_connection(&opts)?;
 
     // Use created SimpleConnection to find matching connections from NetworkManager
-    let ap_remote_conn = match get_connection(client, DeviceType::Wifi, &ap_conn) {
+    let uuid = opts.uuid.as_deref();
+    let ap_remote_conn = match get_target_connection(client, DeviceType::Wifi, &ap_conn, uuid)? {
         Some(c) => c,
         None => {
             return Err(anyhow!(
@@ -182,15 +441,25 @@ pub async fn delete_access_point(client: &Client, opts: AccessPointOpts) -> Resu
         }
     };
 
+    if opts.dry_run {
+        print_dry_run(&format!("delete access point connection \"{}\"", ssid));
+        return Ok(());
+    }
+
+    confirm_action(
+        &format!("delete access point connection \"{}\"", ssid),
+        opts.yes,
+    )?;
+
     // Deactivate access_point connection
     // Automatically deactivates slave connections on success
-    info!(
-        "Deactivating access point connection \"{}\" with interface \"{}\"",
-        ssid, wireless_ifname
-    );
-    match get_active_connection(client, DeviceType::Wifi, &ap_conn) {
+    info!("Deactivating access point connection \"{}\"", ssid);
+    match get_target_active_connection(client, DeviceType::Wifi, &ap_conn, uuid) {
         Some(c) => {
             client.deactivate_connection_future(&c).await?;
+
+            // Give connection a chance to fully deactivate before deleting
+            wait_for_deactivate_before_delete(&c, opts.timeout).await?;
             info!("Access point connection deactivated");
         }
         None => {
@@ -202,10 +471,7 @@ pub async fn delete_access_point(client: &Client, opts: AccessPointOpts) -> Resu
     };
 
     // Delete access_point connection
-    info!(
-        "Deleting access point connection \"{}\" with interface \"{}\"",
-        ssid, wireless_ifname,
-    );
+    info!("Deleting access point connection \"{}\"", ssid);
     ap_remote_conn.delete_future().await?;
     info!("Access point connection deleted");
 
@@ -213,7 +479,7 @@ pub async fn delete_access_point(client: &Client, opts: AccessPointOpts) -> Resu
 }
 
 #[instrument(skip(client), err)]
-pub fn access_point_status(client: &Client, opts: AccessPointOpts) -> Result<()> {
+pub fn access_point_status(client: &impl NmClient, opts: AccessPointOpts) -> Result<StatusCode> {
     let ssid = match &opts.ssid {
         Some(ssid) => ssid,
         None => return Err(anyhow!("Required SSID not specified")),
@@ -226,18 +492,18 @@ pub fn access_point_status(client: &Client, opts: AccessPointOpts) -> Result<()>
 
     // Only possibly active, so assume deactivated until proven otherwise
     let mut conn_state: ActiveConnectionState = ActiveConnectionState::Deactivated;
-    let mut ip4_addr_strs: Vec<String> = vec![];
-    if let Some(c) = get_active_connection(client, DeviceType::Wifi, &ap_conn) {
+    let mut ip4_addrs: Vec<StatusAddress> = vec![];
+    let mut ip6_addrs: Vec<StatusAddress> = vec![];
+    let mut route_metric: Option<i64> = None;
+    let uuid = opts.uuid.as_deref();
+    if let Some(c) = get_target_active_connection(client, DeviceType::Wifi, &ap_conn, uuid) {
         conn_state = c.state();
 
         // Gather active IPv4 info
         if let Some(cfg) = c.ip4_config() {
             // Active IPv4 addresses (i.e. non-NetworkManager configured)
-            for ip4_addr in cfg.addresses() {
-                let addr = ip4_addr.address().unwrap(); // TODO
-                let addr_str = addr.as_str();
-                ip4_addr_strs.push(format!("{addr_str}\t(active)"));
-            }
+            ip4_addrs.append(&mut get_active_addrs(&cfg));
+            route_metric = get_default_route_metric(&cfg);
         } else {
             // Expected when bond is waiting to get IP information.
             // Possible when backing devices are used for other
@@ -247,11 +513,16 @@ pub fn access_point_status(client: &Client, opts: AccessPointOpts) -> Result<()>
                 ssid
             )
         }
+
+        // Gather active IPv6 info. May be missing when IPv6 is disabled
+        if let Some(cfg) = c.ip6_config() {
+            ip6_addrs.append(&mut get_active_addrs(&cfg));
+        }
     };
 
     // Try to get connection that matches what we want from NetworkManager
     // If it doesn't exist, no sense continuing
-    let bond_remote_conn = match get_connection(client, DeviceType::Wifi, &ap_conn) {
+    let bond_remote_conn = match get_target_connection(client, DeviceType::Wifi, &ap_conn, uuid)? {
         Some(c) => c,
         None => {
             return Err(anyhow!(
@@ -270,50 +541,184 @@ pub fn access_point_status(client: &Client, opts: AccessPointOpts) -> Result<()>
         }
     };
 
-    let ip4_method_gstr = match bond_ip4_settings.method() {
+    // Effective metric when active, otherwise any configured metric
+    let route_metric = route_metric.or(get_route_metric(bond_ip4_settings.route_metric()));
+
+    let ip4_method = match bond_ip4_settings.method() {
         Some(m) => m,
         None => return Err(anyhow!("Unable to get ip4 configuration method")),
     };
-    let ip4_method = ip4_method_gstr.as_str();
 
     // Static IPv4 addresses
-    for ix in 0..bond_ip4_settings.num_addresses() {
-        match bond_ip4_settings.address(ix as i32) {
-            // Why does this take a signed int lmao
-            Some(c) => match c.address() {
-                Some(addr) => {
-                    ip4_addr_strs.push(format!("{addr}\t(static)"));
-                }
-                None => warn!("Unable to get address string with index \"{}\"", ix),
-            },
-            None => warn!("Unable to get address with index \"{}\"", ix),
+    ip4_addrs.append(&mut get_static_addrs(&bond_ip4_settings));
+
+    // Gather static IPv6 info, if any. Connections created
+    // before IPv6 support was added may not have IPv6 settings
+    let ip6_method = match bond_conn.setting_ip6_config() {
+        Some(s) => {
+            ip6_addrs.append(&mut get_static_addrs(&s));
+            s.method()
         }
-    }
-
-    // Begin printing status info
-    println!("Name:\t\t{}", &ssid);
-    println!("Type:\t\taccess point");
-    println!("Active:\t\t{}", get_connection_state_str(conn_state));
+        None => None,
+    };
 
-    // IPv4 status info
-    println!("IPv4:");
-    println!("  Method:\t{ip4_method}");
+    let report = StatusReport {
+        name: ssid.to_string(),
+        kind: "access point".to_string(),
+        active: get_connection_state_str(conn_state).to_string(),
+        wireless: Some(WirelessStatus {
+            ssid: bond_conn
+                .setting_wireless()
+                .and_then(|s| s.ssid())
+                .map(|s| get_ssid_display_str(&s)),
+            ..Default::default()
+        }),
+        bond_mode: None,
+        miimon: None,
+        slaves: None,
+        primary: None,
+        xmit_hash_policy: None,
+        mtu: bond_conn.setting_wireless().and_then(|s| get_mtu(s.mtu())),
+        route_metric,
+        ipv4_method: ip4_method.to_string(),
+        ipv4_addresses: ip4_addrs,
+        ipv4_routes: get_static_routes(&bond_ip4_settings),
+        ipv6_method: ip6_method.map_or(String::new(), |m| m.to_string()),
+        ipv6_addresses: ip6_addrs,
+        dns_over_tls: get_dns_over_tls_status(&bond_conn),
+        zone: get_zone(&bond_conn),
+    };
+    report.print(opts.output)?;
+    Ok(StatusCode::from_state(conn_state))
+}
 
-    print!("  Addresses:");
-    if ip4_addr_strs.is_empty() {
-        // Print first addr on same line, but if no addrs, need newline
-        println!();
+// Bring existing access point connection up or down without recreating or deleting it
+#[instrument(skip(client), err)]
+pub async fn set_access_point_active(
+    client: &impl NmClient,
+    opts: AccessPointOpts,
+    active: bool,
+) -> Result<()> {
+    if opts.ssid.is_none() {
+        return Err(anyhow!("Required SSID not specified"));
     }
-    for (ix, addr) in ip4_addr_strs.iter().enumerate() {
-        if ix == 0 {
-            // Print first IP addr on same line as "Addresses"
-            println!("\t{addr}");
-            continue;
+
+    let ap_conn = create_access_point_connection(&opts)?;
+    let uuid = opts.uuid.as_deref();
+    match active {
+        true => {
+            activate_existing_connection(
+                client,
+                DeviceType::Wifi,
+                &ap_conn,
+                uuid,
+                opts.timeout,
+                opts.no_wait,
+                opts.dry_run,
+            )
+            .await
+        }
+        false => {
+            deactivate_existing_connection(
+                client,
+                DeviceType::Wifi,
+                &ap_conn,
+                uuid,
+                opts.timeout,
+                opts.no_wait,
+                opts.dry_run,
+            )
+            .await
         }
-        println!("\t\t{addr}");
     }
+}
 
-    Ok(())
+// Recover access point options from an existing access point connection, e.g. for export.
+// Secrets are not recovered, so password-protected access points have no password
+#[instrument(skip(client), err)]
+pub async fn export_access_point(
+    client: &impl NmClient,
+    opts: AccessPointOpts,
+    include_secrets: bool,
+) -> Result<()> {
+    let ssid = match &opts.ssid {
+        Some(ssid) => ssid,
+        None => return Err(anyhow!("Required SSID not specified")),
+    };
+
+    let ap_conn = create_access_point_connection(&opts)?;
+
+    let ap_remote_conn = match get_connection(client, DeviceType::Wifi, &ap_conn) {
+        Some(c) => c,
+        None => {
+            return Err(anyhow!(
+                "Required access point connection \"{}\" does not exist, quitting...",
+                &ssid
+            ));
+        }
+    };
+
+    export_connection(client, &ap_remote_conn, include_secrets).await
+}
+
+pub fn export_access_point_opts(conn: &Connection) -> Result<AccessPointOpts> {
+    let s_wireless = match conn.setting_wireless() {
+        Some(s) => s,
+        None => return Err(anyhow!("Unable to get wireless settings")),
+    };
+
+    let mode = match s_wireless.mode().as_deref() {
+        Some(SETTING_WIRELESS_MODE_ADHOC) => Some(WirelessMode::Adhoc),
+        _ => None,
+    };
+
+    let band = match s_wireless.band().as_deref() {
+        Some("a") => Some(WirelessBand::A),
+        Some("bg") => Some(WirelessBand::Bg),
+        _ => None,
+    };
+
+    let key_mgmt = conn
+        .setting_wireless_security()
+        .and_then(|s| s.key_mgmt())
+        .and_then(|k| get_key_mgmt(&k));
+
+    let common = get_common_opts(conn)?;
+    let ssid = get_ssid_str(&s_wireless)?;
+
+    // Access points default to a static address rather than DHCP,
+    // so an automatic method without addresses must be explicit
+    let ip4_method = match common.ip4_method {
+        None if common.ip4_addrs.is_empty() => Some(Ip4Method::Auto),
+        method => method,
+    };
+
+    Ok(AccessPointOpts {
+        wireless_ifname: conn.interface_name().map(|i| i.to_string()),
+        connection_id: common.id.filter(|id| *id != ssid),
+        ssid: Some(ssid),
+        cloned_mac: s_wireless.cloned_mac_address().map(|m| m.to_string()),
+        mtu: get_mtu(s_wireless.mtu()),
+        powersave: get_powersave(s_wireless.powersave()),
+        client_isolation: get_ap_isolation(&s_wireless),
+        route_metric: common.route_metric,
+        open: conn.setting_wireless_security().is_none(),
+        key_mgmt,
+        mode,
+        band,
+        ip4_addrs: common.ip4_addrs,
+        ip4_method,
+        gateway: common.gateway,
+        dns: common.dns,
+        routes: common.routes,
+        ip6_addr: common.ip6_addr,
+        multi_connect: common.multi_connect,
+        autoconnect: common.autoconnect,
+        dns_over_tls: common.dns_over_tls,
+        stable_id: common.stable_id,
+        zone: common.zone,
+        ..Default::default()
+    })
 }
 
 pub fn create_access_point_connection(opts: &AccessPointOpts) -> Result<SimpleConnection> {
@@ -325,67 +730,274 @@ pub fn create_access_point_connection(opts: &AccessPointOpts) -> Result<SimpleCo
 
     // General connection settings
     s_connection.set_type(Some(SETTING_WIRELESS_SETTING_NAME));
-    s_connection.set_autoconnect(false);
+    // Unlike other connection types, don't start broadcasting unless asked
+    s_connection.set_autoconnect(opts.autoconnect.unwrap_or(false));
+
+    if let Some(multi_connect) = opts.multi_connect {
+        s_connection.set_multi_connect(multi_connect as i32);
+    }
+
+    if let Some(stable_id) = &opts.stable_id {
+        validate_stable_id(stable_id)?;
+        s_connection.set_stable_id(Some(stable_id));
+    }
+
+    if let Some(zone) = &opts.zone {
+        validate_zone(zone)?;
+        s_connection.set_zone(Some(zone));
+    }
+
+    if let Some(dns_over_tls) = opts.dns_over_tls {
+        set_dns_over_tls(&s_connection, dns_over_tls);
+    }
 
     match &opts.ssid {
         Some(ssid) => {
-            s_connection.set_id(Some(ssid));
+            s_connection.set_id(Some(opts.connection_id.as_deref().unwrap_or(ssid)));
         }
         None => return Err(anyhow!("Required SSID not specified")),
     };
 
-    // TODO: Allow for this to be None. That way user doesn't need to
-    // specify interface for deletion/status as it's rly not required
-    // Allows for more interesting matching as well
-    match &opts.wireless_ifname {
-        Some(ifname) => s_connection.set_interface_name(Some(ifname)),
-        None => return Err(anyhow!("Required wireless interface not specified")),
-    };
+    // Interface is only required when creating. Otherwise leave unset so
+    // deletion and status match on SSID and mode alone
+    if let Some(ifname) = &opts.wireless_ifname {
+        s_connection.set_interface_name(Some(ifname));
+    }
 
     // Wifi settings
-    //s_wireless.set_band(Some("bg"));
+    let mode = opts.mode.unwrap_or_default();
+    validate_channel_width(opts.band, opts.channel_width)?;
+
+    if let Some(band) = opts.band {
+        s_wireless.set_band(Some(get_wireless_band_str(band)));
+    }
+
+    // NetworkManager does not expose channel width for access points,
+    // so only validate it and let the driver pick the widest it supports
+    if let Some(width) = opts.channel_width {
+        warn!(
+            "Channel width \"{}MHz\" not supported by NetworkManager wireless settings, ignoring",
+            width
+        );
+    }
+
+    // Like channel width, NetworkManager does not expose a client limit
+    if let Some(max_clients) = opts.max_clients {
+        validate_max_clients(max_clients)?;
+        warn!(
+            "Maximum clients \"{}\" not supported by NetworkManager wireless settings, ignoring",
+            max_clients
+        );
+    }
+
+    if let Some(isolate) = opts.client_isolation {
+        set_ap_isolation(&s_wireless, isolate);
+    }
+
+    // NetworkManager has no per-connection regulatory domain, so only
+    // validate country and warn when the system regulatory domain differs
+    if let Some(country) = &opts.country {
+        validate_country(country)?;
+        check_regulatory_domain(country);
+    }
+
     s_wireless.set_hidden(false);
-    s_wireless.set_mode(Some(SETTING_WIRELESS_MODE_AP));
+    s_wireless.set_mode(Some(get_wireless_mode_str(mode)));
+
+    if let Some(cloned_mac) = &opts.cloned_mac {
+        validate_cloned_mac(cloned_mac)?;
+        s_wireless.set_cloned_mac_address(Some(cloned_mac));
+    }
+
+    if let Some(mtu) = opts.mtu {
+        validate_mtu(mtu)?;
+        s_wireless.set_mtu(mtu);
+    }
+
+    if let Some(powersave) = opts.powersave {
+        s_wireless.set_powersave(get_powersave_value(powersave));
+    }
 
     match &opts.ssid {
         Some(ssid) => {
+            validate_ssid(ssid)?;
             s_wireless.set_ssid(Some(&(ssid.as_bytes().into())));
         }
         None => return Err(anyhow!("Required SSID not specified")),
     };
 
     // Wifi security settings
-    if let Some(password) = &opts.password {
-        let s_wireless_security = SettingWirelessSecurity::new();
-        s_wireless_security.set_key_mgmt(Some("wpa-psk"));
-        s_wireless_security.set_psk(Some(password));
+    validate_wireless_mode(mode, opts.password.as_deref())?;
+    if let Some(s_wireless_security) =
+        create_wireless_security_setting(opts.password.as_deref(), opts.key_mgmt, opts.psk_min_len)?
+    {
         connection.add_setting(s_wireless_security);
     }
 
-    // IPv4 settings
-    let ip4_net = match &opts.ip4_addr {
-        Some(addr) => Ipv4Net::from_str(addr)?,
-        None => Ipv4Net::from_str(DEFAULT_IP4_ADDR)?,
+    // IPv4 settings. Access points serve clients, so use the default
+    // address unless the method does not take static addresses
+    let ip4_addrs = match opts.ip4_method {
+        None | Some(Ip4Method::Manual) | Some(Ip4Method::Shared) if opts.ip4_addrs.is_empty() => {
+            vec![DEFAULT_IP4_ADDR.to_string()]
+        }
+        _ => opts.ip4_addrs.clone(),
     };
-    let ip4_addr = IPAddress::new(
-        libc::AF_INET,
-        ip4_net.addr().to_string().as_str(),
-        ip4_net.prefix_len() as u32,
+    apply_route_metric(&s_ip4, opts.route_metric)?;
+    apply_ip4_method(&s_ip4, opts.ip4_method, &ip4_addrs, Ip4Method::Manual)?;
+
+    add_ip4_routes(&s_ip4, &opts.routes)?;
+    apply_ip4_gateway_dns(
+        &s_ip4,
+        opts.gateway.as_deref(),
+        &opts.dns,
+        !ip4_addrs.is_empty(),
     )?;
 
-    s_ip4.add_address(&ip4_addr);
-    s_ip4.set_method(Some(SETTING_IP4_CONFIG_METHOD_MANUAL));
-
     connection.add_setting(s_connection);
     connection.add_setting(s_wireless);
     connection.add_setting(s_ip4);
+    connection.add_setting(create_ip6_setting(opts.ip6_addr.as_ref())?);
 
     Ok(connection)
 }
 
+// Ensure requested channel width is valid and usable in the requested band.
+// 80MHz and wider channels are only available in the 5GHz band, so require
+// the band be explicitly specified for those
+pub fn validate_channel_width(band: Option<WirelessBand>, width: Option<u32>) -> Result<()> {
+    let width = match width {
+        Some(w) => w,
+        None => return Ok(()),
+    };
+
+    if !CHANNEL_WIDTHS.contains(&width) {
+        return Err(anyhow!(
+            "Invalid channel width \"{}\", expected one of {:?}",
+            width,
+            CHANNEL_WIDTHS
+        ));
+    }
+
+    if width >= 80 && band != Some(WirelessBand::A) {
+        return Err(anyhow!(
+            "Channel width \"{}MHz\" requires the 5GHz (\"a\") band",
+            width
+        ));
+    }
+
+    Ok(())
+}
+
+pub fn validate_country(country: &str) -> Result<()> {
+    if country.len() != 2 || !country.chars().all(|c| c.is_ascii_alphabetic()) {
+        return Err(anyhow!(
+            "Invalid country \"{}\", expected two-letter ISO 3166-1 code, e.g. \"US\"",
+            country
+        ));
+    }
+
+    Ok(())
+}
+
+// System regulatory domain set by the kernel wireless subsystem, if any. Unset
+// or world regulatory domain ("00") restricts channels and transmit power
+fn check_regulatory_domain(country: &str) {
+    let regdom = std::fs::read_to_string(REGULATORY_DOMAIN_PATH)
+        .map(|r| r.trim().to_string())
+        .unwrap_or_default();
+
+    if regdom.eq_ignore_ascii_case(country) {
+        debug!("System regulatory domain matches country \"{}\"", country);
+        return;
+    }
+
+    warn!(
+        "Country \"{}\" not supported by NetworkManager wireless settings and system \
+         regulatory domain is \"{}\". Set it system-wide, e.g. \"iw reg set {}\"",
+        country,
+        regdom,
+        country.to_ascii_uppercase()
+    );
+}
+
+pub fn validate_max_clients(max_clients: u32) -> Result<()> {
+    if max_clients == 0 {
+        return Err(anyhow!("Maximum clients must be at least 1"));
+    }
+
+    Ok(())
+}
+
+// Set client isolation in wireless settings. Older libnm versions
+// lack the property entirely, so warn and ignore rather than fail
+fn set_ap_isolation(s_wireless: &SettingWireless, isolate: bool) {
+    if !check_feature(Feature::ApIsolation, &isolate.to_string()) {
+        return;
+    }
+
+    // Property is an NMTernary, so set by nick to avoid depending on
+    // version-gated bindings for the enum itself
+    let value = if isolate { "true" } else { "false" };
+    s_wireless.set_property_from_str("ap-isolation", value);
+}
+
+// Client isolation from wireless settings, read by nick as in set_ap_isolation().
+// None when left to NetworkManager default or unsupported by libnm
+fn get_ap_isolation(s_wireless: &SettingWireless) -> Option<bool> {
+    s_wireless.find_property("ap-isolation")?;
+
+    let value = s_wireless.property_value("ap-isolation");
+    match glib::EnumValue::from_value(&value).map(|(_, v)| v.nick()) {
+        Some("true") => Some(true),
+        Some("false") => Some(false),
+        _ => None,
+    }
+}
+
+// Avoid accidentally broadcasting an unsecured SSID. Open access
+// points must be requested explicitly rather than by omitting a password
+pub fn validate_open_access_point(password: Option<&str>, open: bool) -> Result<()> {
+    match (password, open) {
+        (Some(_), true) => Err(anyhow!(
+            "Password specified for open access point, specify one or the other"
+        )),
+        (None, false) => Err(anyhow!(
+            "Access point requires a password, or explicitly allow an open access point"
+        )),
+        _ => Ok(()),
+    }
+}
+
+// Ad-hoc (IBSS) networks historically only support WEP or no security, and
+// neither WPA-PSK nor SAE are supported here. Since WEP is insecure and
+// not supported by nutil, require ad-hoc networks be open
+pub fn validate_wireless_mode(mode: WirelessMode, password: Option<&str>) -> Result<()> {
+    match (mode, password) {
+        (WirelessMode::Adhoc, Some(_)) => Err(anyhow!(
+            "Password-protected networks not supported in ad-hoc mode, use an open network"
+        )),
+        _ => Ok(()),
+    }
+}
+
+fn get_wireless_mode_str(mode: WirelessMode) -> &'static str {
+    match mode {
+        WirelessMode::Ap => SETTING_WIRELESS_MODE_AP,
+        WirelessMode::Adhoc => SETTING_WIRELESS_MODE_ADHOC,
+    }
+}
+
+fn get_wireless_band_str(band: WirelessBand) -> &'static str {
+    match band {
+        WirelessBand::A => "a",
+        WirelessBand::Bg => "bg",
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::connection::mock::MockClient;
 
     // Expect empty interface which should be caught later on
     // when attempting to create connection
@@ -489,7 +1101,7 @@ mod test {
         ";
 
         let opts = parse_access_point_opts(cfg).unwrap();
-        assert!(opts.ip4_addr.is_none());
+        assert!(opts.ip4_addrs.is_empty());
     }
 
     #[test]
@@ -504,4 +1116,248 @@ mod test {
 
         parse_access_point_opts(cfg).unwrap();
     }
+
+    #[test]
+    fn country() {
+        assert!(validate_country("US").is_ok());
+        assert!(validate_country("de").is_ok());
+
+        assert!(validate_country("").is_err());
+        assert!(validate_country("USA").is_err());
+        assert!(validate_country("00").is_err());
+        assert!(validate_country("Ü").is_err());
+    }
+
+    #[test]
+    fn band_and_channel_width() {
+        let cfg = "
+            wireless_interface: \"test_interface\"
+            ssid: \"test_ssid\"
+            band: !A
+            channel_width: 80
+        ";
+
+        let opts = parse_access_point_opts(cfg).unwrap();
+        assert_eq!(opts.band, Some(WirelessBand::A));
+        assert_eq!(opts.channel_width, Some(80));
+        assert!(validate_channel_width(opts.band, opts.channel_width).is_ok());
+    }
+
+    #[test]
+    fn client_limits() {
+        let cfg = "
+            wireless_interface: \"test_interface\"
+            ssid: \"test_ssid\"
+            max_clients: 8
+            client_isolation: true
+        ";
+
+        let opts = parse_access_point_opts(cfg).unwrap();
+        assert_eq!(opts.max_clients, Some(8));
+        assert_eq!(opts.client_isolation, Some(true));
+        assert!(validate_max_clients(8).is_ok());
+        assert!(validate_max_clients(0).is_err());
+    }
+
+    #[test]
+    fn powersave() {
+        let cfg = "
+            wireless_interface: \"test_interface\"
+            ssid: \"test_ssid\"
+            powersave: true
+        ";
+
+        let opts = parse_access_point_opts(cfg).unwrap();
+        assert_eq!(opts.powersave, Some(true));
+    }
+
+    #[test]
+    #[should_panic]
+    fn unexpected_band() {
+        let cfg = "
+            wireless_interface: \"test_interface\"
+            ssid: \"test_ssid\"
+            band: !UnexpectedBand
+        ";
+
+        parse_access_point_opts(cfg).unwrap();
+    }
+
+    #[test]
+    fn valid_band_channel_widths() {
+        assert!(validate_channel_width(None, None).is_ok());
+        assert!(validate_channel_width(None, Some(20)).is_ok());
+        assert!(validate_channel_width(Some(WirelessBand::Bg), Some(20)).is_ok());
+        assert!(validate_channel_width(Some(WirelessBand::Bg), Some(40)).is_ok());
+        assert!(validate_channel_width(Some(WirelessBand::A), Some(40)).is_ok());
+        assert!(validate_channel_width(Some(WirelessBand::A), Some(80)).is_ok());
+        assert!(validate_channel_width(Some(WirelessBand::A), Some(160)).is_ok());
+    }
+
+    #[test]
+    fn invalid_band_channel_widths() {
+        // Not a valid channel width
+        assert!(validate_channel_width(Some(WirelessBand::A), Some(30)).is_err());
+
+        // Too wide for 2.4GHz band
+        assert!(validate_channel_width(Some(WirelessBand::Bg), Some(80)).is_err());
+        assert!(validate_channel_width(Some(WirelessBand::Bg), Some(160)).is_err());
+
+        // Too wide without explicitly requesting 5GHz band
+        assert!(validate_channel_width(None, Some(80)).is_err());
+    }
+
+    #[test]
+    fn sae_key_mgmt() {
+        let cfg = "
+            wireless_interface: \"test_interface\"
+            ssid: \"test_ssid\"
+            password: \"test_password\"
+            key_mgmt: !Sae
+        ";
+
+        let opts = parse_access_point_opts(cfg).unwrap();
+        assert_eq!(opts.key_mgmt, Some(KeyMgmt::Sae));
+    }
+
+    #[test]
+    #[should_panic]
+    fn unexpected_key_mgmt() {
+        let cfg = "
+            wireless_interface: \"test_interface\"
+            ssid: \"test_ssid\"
+            password: \"test_password\"
+            key_mgmt: !UnexpectedKeyMgmt
+        ";
+
+        parse_access_point_opts(cfg).unwrap();
+    }
+
+    #[test]
+    fn open_access_point() {
+        let cfg = "
+            wireless_interface: \"test_interface\"
+            ssid: \"test_ssid\"
+            open: true
+        ";
+
+        let opts = parse_access_point_opts(cfg).unwrap();
+        assert!(opts.open);
+        assert!(validate_open_access_point(opts.password.as_deref(), opts.open).is_ok());
+    }
+
+    #[test]
+    fn open_access_point_security() {
+        assert!(validate_open_access_point(Some("test_password"), false).is_ok());
+        assert!(validate_open_access_point(None, true).is_ok());
+
+        // Neither password nor explicitly open
+        assert!(validate_open_access_point(None, false).is_err());
+
+        // Both password and explicitly open
+        assert!(validate_open_access_point(Some("test_password"), true).is_err());
+    }
+
+    #[test]
+    fn adhoc_mode() {
+        let cfg = "
+            wireless_interface: \"test_interface\"
+            ssid: \"test_ssid\"
+            open: true
+            mode: !Adhoc
+        ";
+
+        let opts = parse_access_point_opts(cfg).unwrap();
+        assert_eq!(opts.mode, Some(WirelessMode::Adhoc));
+
+        let conn = create_access_point_connection(&opts).unwrap();
+        let s_wireless = conn.setting_wireless().unwrap();
+        assert_eq!(s_wireless.mode().unwrap(), SETTING_WIRELESS_MODE_ADHOC);
+        assert!(conn.setting_wireless_security().is_none());
+    }
+
+    #[test]
+    fn adhoc_mode_security() {
+        assert!(validate_wireless_mode(WirelessMode::Ap, Some("test_password")).is_ok());
+        assert!(validate_wireless_mode(WirelessMode::Adhoc, None).is_ok());
+
+        // WPA security not supported with ad-hoc
+        assert!(validate_wireless_mode(WirelessMode::Adhoc, Some("test_password")).is_err());
+
+        let opts = AccessPointOpts {
+            wireless_ifname: Some("test_interface".to_string()),
+            ssid: Some("test_ssid".to_string()),
+            password: Some("test_password".to_string()),
+            mode: Some(WirelessMode::Adhoc),
+            ..Default::default()
+        };
+        assert!(create_access_point_connection(&opts).is_err());
+    }
+
+    #[test]
+    fn autoconnect() {
+        let mut opts = AccessPointOpts {
+            wireless_ifname: Some("test_interface".to_string()),
+            ssid: Some("test_ssid".to_string()),
+            open: true,
+            ..Default::default()
+        };
+
+        // Access points default to autoconnect off
+        let conn = create_access_point_connection(&opts).unwrap();
+        assert!(!conn.setting_connection().unwrap().autoconnect());
+
+        opts.autoconnect = Some(true);
+        let conn = create_access_point_connection(&opts).unwrap();
+        assert!(conn.setting_connection().unwrap().autoconnect());
+    }
+
+    // Status and deletion only need SSID, so leave interface unset
+    // and let matching skip it
+    #[test]
+    fn ssid_only_status_lookup() {
+        let cfg = "
+            ssid: \"test_ssid\"
+            open: true
+        ";
+
+        let opts = parse_access_point_opts(cfg).unwrap();
+        let base_conn = create_access_point_connection(&opts).unwrap();
+        assert!(base_conn
+            .setting_connection()
+            .unwrap()
+            .interface_name()
+            .is_none());
+
+        let full_opts = AccessPointOpts {
+            wireless_ifname: Some("test_interface".to_string()),
+            ..opts.clone()
+        };
+        let cmp_conn = create_access_point_connection(&full_opts)
+            .unwrap()
+            .upcast::<Connection>();
+        assert!(matching_wifi_connection(&base_conn, &cmp_conn));
+
+        // Different SSID still does not match
+        let other_opts = AccessPointOpts {
+            ssid: Some("other_ssid".to_string()),
+            ..full_opts
+        };
+        let cmp_conn = create_access_point_connection(&other_opts)
+            .unwrap()
+            .upcast::<Connection>();
+        assert!(!matching_wifi_connection(&base_conn, &cmp_conn));
+    }
+
+    #[test]
+    fn status_missing_access_point() {
+        let client = MockClient::default();
+        let opts = AccessPointOpts {
+            ssid: Some("test_ssid".to_string()),
+            open: true,
+            ..Default::default()
+        };
+
+        assert!(access_point_status(&client, opts).is_err());
+    }
 }
@@ -1,20 +1,44 @@
 use std::collections::HashSet;
-use std::fs::File;
-use std::io::Read;
 use std::str;
-use std::str::FromStr;
+use std::time::Duration;
 
-use anyhow::{anyhow, Result};
+use anyhow::{anyhow, Context, Result};
 use clap::ValueEnum;
-use ipnet::Ipv4Net;
+use ipnet::Ipv6Net;
 use nm::*;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use tracing::{debug, error, info, instrument, warn};
 
 use crate::cli::BondArgs;
 use crate::connection::*;
-
-#[derive(Default, ValueEnum, Deserialize, PartialEq, Copy, Clone, Debug)]
+use crate::export::{export_connection, get_common_opts};
+use crate::status::*;
+use crate::util::{
+    apply_config_overrides, config_fields, confirm_action, deserialize_ip4_addrs,
+    deserialize_ip6_addr, is_valid_mac_addr, read_config, serialize_ip6_addr, validate_cloned_mac,
+    validate_ifname, validate_mtu,
+};
+
+/// Largest LACP user port key accepted by the kernel (10 bits)
+const MAX_AD_USER_PORT_KEY: u16 = 1023;
+
+/// Transmit hash policies accepted by the kernel for slave selection
+const XMIT_HASH_POLICIES: [&str; 6] = [
+    "layer2",
+    "layer2+3",
+    "layer3+4",
+    "encap2+3",
+    "encap3+4",
+    "vlan+srcmac",
+];
+
+/// LACPDU rates accepted by the kernel, every 30 seconds or every second
+const LACP_RATES: [&str; 2] = ["slow", "fast"];
+
+/// MII link monitoring interval (ms) used when none specified
+pub const DEFAULT_MIIMON: u32 = 100;
+
+#[derive(Default, ValueEnum, Serialize, Deserialize, PartialEq, Copy, Clone, Debug)]
 pub enum BondMode {
     RoundRobin = 0,
     #[default]
@@ -26,7 +50,18 @@ pub enum BondMode {
     AdaptiveLoadBalancing = 6,
 }
 
-#[derive(Default, Deserialize, PartialEq, Clone, Debug)]
+/// Naming scheme for bond slave wired connection ids
+#[derive(Default, ValueEnum, Serialize, Deserialize, PartialEq, Copy, Clone, Debug)]
+pub enum SlaveIdScheme {
+    /// Slave interface name, e.g. "eth0"
+    #[default]
+    Slave,
+    /// Bond and slave interface names, e.g. "bond0-eth0". Unique per bond
+    BondSlave,
+}
+
+#[serde_with::skip_serializing_none]
+#[derive(Default, Serialize, Deserialize, PartialEq, Clone, Debug)]
 pub struct BondOpts {
     /// Required for all commands, so no default if unspecified
     #[serde(rename = "bond_interface")]
@@ -34,45 +69,480 @@ pub struct BondOpts {
     #[serde(with = "serde_with::rust::string_empty_as_none")]
     bond_ifname: Option<String>,
 
+    /// Connection name shown by NetworkManager. If not specified, default to the bond interface name.
+    /// Connections are matched by interface, never by name
+    #[serde(default)]
+    #[serde(with = "serde_with::rust::string_empty_as_none")]
+    connection_id: Option<String>,
+
+    /// Bond mode of operation. When unspecified, default to ActiveBackup
+    /// during creation and keep the existing mode when modifying
+    #[serde(default)]
+    bond_mode: Option<BondMode>,
+
+    /// MII link monitoring interval in milliseconds. Defaults to 100 when unspecified
+    #[serde(default)]
+    miimon: Option<u32>,
+
+    /// Milliseconds to wait before enabling a slave after link up. Multiple of miimon
+    #[serde(default)]
+    updelay: Option<u32>,
+
+    /// Milliseconds to wait before disabling a slave after link down. Multiple of miimon
+    #[serde(default)]
+    downdelay: Option<u32>,
+
+    /// Deliver frames received on inactive slaves rather than dropping them.
+    /// Only applicable in ActiveBackup mode
+    #[serde(default)]
+    all_slaves_active: Option<bool>,
+
+    /// Preferred slave interface, active whenever available. Must be one
+    /// of the slave interfaces. Only applicable in ActiveBackup mode
+    #[serde(default)]
+    #[serde(with = "serde_with::rust::string_empty_as_none")]
+    primary: Option<String>,
+
+    /// Slave selection hash policy, e.g. "layer3+4". Only applicable
+    /// in XOR and 802.3ad modes
+    #[serde(default)]
+    #[serde(with = "serde_with::rust::string_empty_as_none")]
+    xmit_hash_policy: Option<String>,
+
+    /// LACP actor system priority (1-65535). Only applicable in 802.3ad mode
+    #[serde(default)]
+    ad_actor_sys_prio: Option<u16>,
+
+    /// LACP actor system MAC address. Only applicable in 802.3ad mode
+    #[serde(default)]
+    #[serde(with = "serde_with::rust::string_empty_as_none")]
+    ad_actor_system: Option<String>,
+
+    /// LACP user port key (0-1023). Only applicable in 802.3ad mode
+    #[serde(default)]
+    ad_user_port_key: Option<u16>,
+
+    /// Rate partner is asked to send LACPDUs, "slow" or "fast".
+    /// Only applicable in 802.3ad mode
+    #[serde(default)]
+    #[serde(with = "serde_with::rust::string_empty_as_none")]
+    lacp_rate: Option<String>,
+
+    /// Naming scheme for slave connection ids. If not specified, use slave interface name
     #[serde(default)]
-    bond_mode: BondMode,
+    slave_id_scheme: Option<SlaveIdScheme>,
 
     #[serde(default, rename = "slave_interfaces")]
+    #[serde(skip_serializing_if = "HashSet::is_empty")]
     slave_ifnames: HashSet<String>,
 
+    /// Static IPv4 addresses, each including prefix length.
+    /// Accepts a single address or a list of addresses
+    #[serde(default, alias = "ip4_addr", skip_serializing_if = "Vec::is_empty")]
+    #[serde(deserialize_with = "deserialize_ip4_addrs")]
+    pub ip4_addrs: Vec<String>,
+
+    /// IPv4 method, e.g. "shared" to provide NAT and DHCP to downstream clients.
+    /// If not specified, static with IPv4 addresses, otherwise DHCP
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ip4_method: Option<Ip4Method>,
+
+    /// IPv4 gateway. Requires a static IPv4 address
+    #[serde(default)]
+    #[serde(with = "serde_with::rust::string_empty_as_none")]
+    pub gateway: Option<String>,
+
+    /// IPv4 DNS servers
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub dns: Vec<String>,
+
+    /// Static IPv4 routes, each "dest/prefix via gateway [metric]",
+    /// e.g. "198.51.100.0/24 via 192.0.2.254 100"
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub routes: Vec<String>,
+
+    /// Seconds to wait for a DHCP lease before failing activation. Only
+    /// used without a static IPv4 address. Zero uses NetworkManager's default
+    #[serde(default)]
+    pub dhcp_timeout: Option<i32>,
+
+    /// DHCP client identifier sent when requesting a lease, e.g. "mac" or
+    /// "duid". Only used with automatic IPv4 configuration
+    #[serde(default)]
+    #[serde(with = "serde_with::rust::string_empty_as_none")]
+    pub dhcp_client_id: Option<String>,
+
+    /// Hostname sent when requesting a lease. Only used with automatic IPv4 configuration
+    #[serde(default)]
+    #[serde(with = "serde_with::rust::string_empty_as_none")]
+    pub dhcp_hostname: Option<String>,
+
+    /// Whether the connection may activate even if IPv4 configuration fails, e.g.
+    /// when no DHCP server responds. If not specified, use NetworkManager default (true)
+    #[serde(default)]
+    pub ip4_may_fail: Option<bool>,
+
+    /// Static IPv6 address, including prefix length. If not specified, default to auto
+    #[serde(default)]
+    #[serde(deserialize_with = "deserialize_ip6_addr")]
+    #[serde(serialize_with = "serialize_ip6_addr")]
+    pub ip6_addr: Option<Ipv6Net>,
+
+    /// If not specified, use NetworkManager default (single)
+    #[serde(default)]
+    pub multi_connect: Option<MultiConnect>,
+
+    /// If not specified, default to enabled
+    #[serde(default)]
+    pub autoconnect: Option<bool>,
+
+    /// If not specified, use NetworkManager default (global DNS configuration)
+    #[serde(default)]
+    pub dns_over_tls: Option<DnsOverTls>,
+
+    /// Seed for generated stable MAC and IPv6 privacy addresses. May contain
+    /// NetworkManager substitution tokens, e.g. "${CONNECTION}"
+    #[serde(default)]
+    pub stable_id: Option<String>,
+
+    /// Firewalld zone the connection is placed in, e.g. "public". If not
+    /// specified, use firewalld default zone
+    #[serde(default)]
+    pub zone: Option<String>,
+
+    /// MAC address used by slave devices. Either an explicit MAC address
+    /// or one of "random", "stable", or "permanent"
     #[serde(default)]
     #[serde(with = "serde_with::rust::string_empty_as_none")]
-    pub ip4_addr: Option<String>,
+    pub cloned_mac: Option<String>,
+
+    /// Maximum transmission unit used by slave devices in bytes, between 68
+    /// and 9000, e.g. 9000 for jumbo frames. When unspecified, use device default
+    #[serde(default)]
+    pub mtu: Option<u32>,
+
+    /// IPv4 route metric, lower is preferred, e.g. to prefer a bond over a
+    /// station when both are active. When unspecified or -1, NetworkManager chooses
+    #[serde(default)]
+    pub route_metric: Option<i64>,
+
+    /// Time to wait for connection to (de)activate. When unspecified, wait
+    /// indefinitely to activate, or a short time to deactivate before deleting
+    #[serde(skip)]
+    pub timeout: Option<Duration>,
+
+    /// Retries for transient connection activation failures
+    #[serde(skip)]
+    pub retry: RetryPolicy,
+
+    /// Format used when printing connection status
+    #[serde(skip)]
+    pub output: OutputFormat,
+
+    /// Validate and build connection without modifying NetworkManager state
+    #[serde(skip)]
+    pub dry_run: bool,
+
+    /// Return once activation starts rather than waiting for it to finish
+    #[serde(skip)]
+    pub no_wait: bool,
+
+    /// Abort rather than deactivate conflicting active connections
+    #[serde(skip)]
+    pub no_preempt: bool,
+
+    /// Skip confirmation before destructive operations
+    #[serde(skip)]
+    pub yes: bool,
+
+    /// Target connection with this UUID rather than one matching other options
+    #[serde(skip)]
+    pub uuid: Option<String>,
+
+    /// Delete any existing matching connection rather than fail during creation
+    #[serde(skip)]
+    pub force: bool,
+
+    /// List all bonds rather than the status of a single bond
+    #[serde(skip)]
+    pub all: bool,
+
+    /// Convert slave connections to standalone wired connections rather
+    /// than deleting them. Only used when deleting a bond
+    #[serde(skip)]
+    pub keep_slaves: bool,
+
+    /// Bond interface slave currently belongs to. Only used when moving a slave
+    #[serde(skip)]
+    pub from_bond_ifname: Option<String>,
+
+    /// Bond interface to move slave to. Only used when moving a slave
+    #[serde(skip)]
+    pub to_bond_ifname: Option<String>,
+
+    /// Reject valid but ineffective option combinations and static IPv4
+    /// addresses overlapping subnets of active connections rather than warning
+    #[serde(skip)]
+    pub strict: bool,
+}
+
+/// One-line summary of a bond connection, used when listing all bonds
+#[derive(Serialize, PartialEq, Clone, Debug)]
+pub struct BondSummary {
+    pub name: String,
+
+    /// Active connection state, e.g. "activated"
+    pub active: String,
+
+    pub num_slaves: usize,
 }
 
 impl TryFrom<BondArgs> for BondOpts {
     type Error = anyhow::Error;
 
     fn try_from(args: BondArgs) -> Result<Self, Self::Error> {
-        if let Some(cfg) = args.config {
-            let mut buf = vec![];
-            let mut cfg_file = File::open(cfg)?;
-            cfg_file.read_to_end(&mut buf)?;
+        let mut opts = match args.config {
+            Some(cfg) => {
+                let config = read_config(&cfg)?;
+                let config =
+                    apply_config_overrides(&config, &args.overrides, config_fields::<BondOpts>())?;
+                parse_bond_opts(&config)?
+            }
+            None => BondOpts {
+                bond_ifname: args.ifname,
+                connection_id: args.connection_id,
+                bond_mode: args.bond_mode,
+                miimon: args.miimon,
+                updelay: args.updelay,
+                downdelay: args.downdelay,
+                all_slaves_active: args.all_slaves_active,
+                primary: args.primary,
+                xmit_hash_policy: args.xmit_hash_policy,
+                ad_actor_sys_prio: args.ad_actor_sys_prio,
+                ad_actor_system: args.ad_actor_system,
+                ad_user_port_key: args.ad_user_port_key,
+                lacp_rate: args.lacp_rate,
+                slave_id_scheme: args.slave_id_scheme,
+                slave_ifnames: HashSet::from_iter(args.slave_ifnames),
+                ip4_addrs: args.ip4_addrs,
+                ip4_method: args.ip4_method,
+                gateway: args.gateway,
+                dns: args.dns,
+                routes: args.routes,
+                dhcp_timeout: args.dhcp_timeout,
+                dhcp_client_id: args.dhcp_client_id,
+                dhcp_hostname: args.dhcp_hostname,
+                ip4_may_fail: args.ip4_may_fail,
+                ip6_addr: args.ip6_addr,
+                multi_connect: args.multi_connect,
+                autoconnect: args.autoconnect,
+                stable_id: args.stable_id,
+                zone: args.zone,
+                cloned_mac: args.cloned_mac,
+                mtu: args.mtu,
+                route_metric: args.route_metric,
+                dns_over_tls: args.dns_over_tls,
+                ..Default::default()
+            },
+        };
+
+        // Command-line only options, applied whether or not config file used
+        opts.timeout = args.timeout.map(Duration::from_secs);
+        opts.retry = args.retry;
+        opts.output = args.output;
+        opts.dry_run = args.dry_run;
+        opts.no_wait = args.no_wait;
+        opts.no_preempt = args.no_preempt;
+        opts.yes = args.yes;
+        opts.uuid = args.uuid;
+        opts.force = args.force;
+        opts.all = args.all;
+        opts.keep_slaves = args.keep_slaves;
+        opts.from_bond_ifname = args.from_bond_ifname;
+        opts.to_bond_ifname = args.to_bond_ifname;
+        opts.strict = args.strict;
+
+        Ok(opts)
+    }
+}
 
-            let config = str::from_utf8(buf.as_slice())?;
-            return parse_bond_opts(config);
+impl BondOpts {
+    // Bond mode used when building the connection, defaulting when unspecified
+    fn bond_mode(&self) -> BondMode {
+        self.bond_mode.unwrap_or_default()
+    }
+
+    // Validate bond options which depend on one another, e.g. options only
+    // applicable in certain bond modes. Valid but ineffective option
+    // combinations are warned about rather than rejected, unless strict
+    pub fn validate(&self) -> Result<()> {
+        if self.all_slaves_active.is_some() && !all_slaves_active_applicable(self.bond_mode()) {
+            self.warn_or_reject(format!(
+                "Option \"all_slaves_active\" only applicable in \"{}\" bond mode, ignoring",
+                get_bond_mode_str(BondMode::ActiveBackup)
+            ))?;
         }
 
-        let bond_mode = match args.bond_mode {
-            Some(mode) => mode,
-            None => {
-                let mode: BondMode = Default::default();
-                info!("Bond mode not specified, defaulting to \"{}\"", get_bond_mode_str(mode));
-                mode
+        if !self.link_monitoring_enabled() {
+            self.warn_or_reject(
+                "Bond link monitoring disabled (\"miimon\" is 0), \
+                 slave link failures will not be detected"
+                    .to_string(),
+            )?;
+        }
+
+        if let Some(cloned_mac) = &self.cloned_mac {
+            validate_cloned_mac(cloned_mac)?;
+        }
+
+        if let Some(mtu) = self.mtu {
+            validate_mtu(mtu)?;
+        }
+
+        validate_ip4_method(self.ip4_method, !self.ip4_addrs.is_empty())?;
+        validate_ip4_dhcp_timeout(self.dhcp_timeout, !self.ip4_addrs.is_empty())?;
+        validate_ip4_may_fail(self.ip4_may_fail, self.dhcp_timeout)?;
+
+        self.validate_link_delays()?;
+
+        if let Some(primary) = &self.primary {
+            if !self.slave_ifnames.contains(primary) {
+                return Err(anyhow!(
+                    "Primary interface \"{}\" is not one of the bond slave interfaces",
+                    primary
+                ));
+            }
+
+            if self.bond_mode() != BondMode::ActiveBackup {
+                self.warn_or_reject(format!(
+                    "Option \"primary\" only applicable in \"{}\" bond mode, ignoring",
+                    get_bond_mode_str(BondMode::ActiveBackup)
+                ))?;
             }
+        }
+
+        self.validate_xmit_hash_policy()?;
+        self.validate_lacp()
+    }
+
+    fn validate_xmit_hash_policy(&self) -> Result<()> {
+        let policy = match &self.xmit_hash_policy {
+            Some(p) => p,
+            None => return Ok(()),
         };
 
-        Ok(BondOpts {
-            bond_ifname: args.ifname,
-            bond_mode,
-            slave_ifnames: HashSet::from_iter(args.slave_ifnames.into_iter()),
-            ip4_addr: args.ip4_addr,
-        })
+        if !XMIT_HASH_POLICIES.contains(&policy.as_str()) {
+            return Err(anyhow!(
+                "Option \"xmit_hash_policy\" must be one of {:?}, got \"{}\"",
+                XMIT_HASH_POLICIES,
+                policy
+            ));
+        }
+
+        if !xmit_hash_policy_applicable(self.bond_mode()) {
+            self.warn_or_reject(format!(
+                "Option \"xmit_hash_policy\" only applicable in \"{}\" and \"{}\" bond modes, ignoring",
+                get_bond_mode_str(BondMode::XOR),
+                get_bond_mode_str(BondMode::DynamicLinkAggregation)
+            ))?;
+        }
+
+        Ok(())
+    }
+
+    // Bond only detects slave link failures when MII (or ARP) monitoring is enabled.
+    // ARP monitoring is not yet configurable, so only MII monitoring is considered
+    fn link_monitoring_enabled(&self) -> bool {
+        self.miimon.unwrap_or(DEFAULT_MIIMON) != 0
+    }
+
+    // Kernel rounds link up/down delays down to a multiple of the MII monitoring
+    // interval, so warn when the requested delay would not be used as given
+    fn validate_link_delays(&self) -> Result<()> {
+        let miimon = self.miimon.unwrap_or(DEFAULT_MIIMON);
+        if miimon == 0 {
+            return Ok(());
+        }
+
+        for (name, delay) in [("updelay", self.updelay), ("downdelay", self.downdelay)] {
+            if let Some(delay) = delay {
+                if delay % miimon != 0 {
+                    self.warn_or_reject(format!(
+                        "Option \"{}\" value \"{}\" is not a multiple of miimon \"{}\", \
+                         will be rounded down to \"{}\"",
+                        name,
+                        delay,
+                        miimon,
+                        delay - delay % miimon
+                    ))?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn warn_or_reject(&self, msg: String) -> Result<()> {
+        if self.strict {
+            return Err(anyhow!("{}", msg));
+        }
+
+        warn!("{}", msg);
+        Ok(())
+    }
+
+    // LACP options only make sense in 802.3ad mode. Unlike other mode-specific
+    // options, reject these outside of it, as user likely intended 802.3ad mode
+    fn validate_lacp(&self) -> Result<()> {
+        let lacp_opts_set = self.ad_actor_sys_prio.is_some()
+            || self.ad_actor_system.is_some()
+            || self.ad_user_port_key.is_some()
+            || self.lacp_rate.is_some();
+
+        if lacp_opts_set && self.bond_mode() != BondMode::DynamicLinkAggregation {
+            return Err(anyhow!(
+                "LACP options only applicable in \"{}\" bond mode, not \"{}\"",
+                get_bond_mode_str(BondMode::DynamicLinkAggregation),
+                get_bond_mode_str(self.bond_mode())
+            ));
+        }
+
+        if self.ad_actor_sys_prio == Some(0) {
+            return Err(anyhow!(
+                "Option \"ad_actor_sys_prio\" must be between 1 and 65535"
+            ));
+        }
+
+        if let Some(port_key) = self.ad_user_port_key {
+            if port_key > MAX_AD_USER_PORT_KEY {
+                return Err(anyhow!(
+                    "Option \"ad_user_port_key\" must be between 0 and {}, got \"{}\"",
+                    MAX_AD_USER_PORT_KEY,
+                    port_key
+                ));
+            }
+        }
+
+        if let Some(lacp_rate) = &self.lacp_rate {
+            if !LACP_RATES.contains(&lacp_rate.as_str()) {
+                return Err(anyhow!(
+                    "Option \"lacp_rate\" must be one of {:?}, got \"{}\"",
+                    LACP_RATES,
+                    lacp_rate
+                ));
+            }
+        }
+
+        if let Some(actor_system) = &self.ad_actor_system {
+            if !is_valid_mac_addr(actor_system) {
+                return Err(anyhow!(
+                    "Option \"ad_actor_system\" must be a MAC address, got \"{}\"",
+                    actor_system
+                ));
+            }
+        }
+
+        Ok(())
     }
 }
 
@@ -82,80 +552,271 @@ fn parse_bond_opts(config: &str) -> Result<BondOpts> {
 }
 
 #[instrument(skip(client), err)]
-pub async fn create_bond(client: &Client, opts: BondOpts) -> Result<()> {
+pub async fn create_bond(client: &impl NmClient, opts: BondOpts) -> Result<()> {
     let bond_ifname = match &opts.bond_ifname {
         Some(ifname) => ifname,
         None => return Err(anyhow!("Required bond interface not specified")),
     };
 
-    // Only need to check if no or empty slave ifnames specified.
+    validate_ifname(bond_ifname)?;
+
+    if opts.bond_mode.is_none() {
+        info!(
+            "Bond mode not specified, defaulting to \"{}\"",
+            get_bond_mode_str(opts.bond_mode())
+        );
+    }
+
+    // Only need to check if no or invalid slave ifnames specified.
     // Duplicates taken care of by HashSet, and existence of interface
     // check by NetworkManager itself (which we handle the error of).
     if opts.slave_ifnames.is_empty() {
         return Err(anyhow!(
             "One or more slave interfaces required to create a bond connection"
         ));
-    } else if opts.slave_ifnames.iter().any(|c| c.is_empty()) {
-        return Err(anyhow!("Empty string is not a valid slave interface name"));
     }
+    for slave_ifname in opts.slave_ifnames.iter() {
+        validate_ifname(slave_ifname)?;
+    }
+
+    opts.validate()?;
 
     // Create bond structs here so we can comprehensively search
     // for any matching existing connection, should it exist
     // Does not add connection to Network Manager, that happens later
     let bond_conn = create_bond_connection(&opts)?;
 
-    // Make sure a bond connection with same name does not already exist
-    // If bond connection using same devices does not exist, good to continue
-    if get_connection(client, DeviceType::Bond, &bond_conn).is_some() {
-        return Err(anyhow!("Bond connection already exists, quitting..."));
+    // Make sure a bond connection with same name does not already exist, unless
+    // replacing it. If bond connection using same devices does not exist, good to continue
+    let existing_conn = get_connection(client, DeviceType::Bond, &bond_conn);
+    if let Some(existing_conn) = &existing_conn {
+        if !opts.force {
+            return Err(anyhow!("Bond connection already exists, quitting..."));
+        }
+
+        // Keep UUID stable for anything referencing the replaced connection
+        preserve_connection_uuid(&bond_conn, existing_conn);
     }
 
+    // Existing slaves of a replaced bond, which may differ from the new slaves
+    let replaced_slave_ifnames = match &existing_conn {
+        Some(_) => get_slave_ifnames(client, bond_ifname),
+        None => vec![],
+    };
+
+    // Don't steal interfaces already enslaved to another bond
+    check_slaves_unclaimed(client, &opts.slave_ifnames, bond_ifname)?;
+
     // Deactivate matching active ethernet connections. Otherwise, newly-created bond
     // connection will stay in "Activating" state until backing slave connections are
     // active (which the existing non-slave ethernet connections preempt from doing so).
+    // Slaves of a bond being replaced are deleted along with it, so aren't conflicts
+    let replaced_bond_ifname = existing_conn.as_ref().map(|_| bond_ifname.as_str());
+    deactivate_conflicting_wired_connections(
+        client,
+        &opts.slave_ifnames,
+        replaced_bond_ifname,
+        opts.dry_run,
+        opts.no_preempt,
+        opts.yes,
+    )
+    .await?;
+
+    // Bond and slaves about to be replaced don't conflict with the new bond
+    let replaced_ifnames: Vec<&str> = std::iter::once(bond_ifname.as_str())
+        .chain(opts.slave_ifnames.iter().map(|s| s.as_str()))
+        .chain(replaced_slave_ifnames.iter().map(|s| s.as_str()))
+        .collect();
+    check_ip4_conflicts(client, &opts.ip4_addrs, &replaced_ifnames, opts.strict)?;
+
+    // Check that backing devices for provided wired interfaces exist
+    let wired_devs = get_slave_devices(client, &opts.slave_ifnames)?;
+
+    // Bond connection doesn't exist and backing ethernet devices exist,
+    // so create new bond connection (using newly-created wired connections
+    // which are backed by existing wired devices)
+    if opts.dry_run {
+        if existing_conn.is_some() {
+            print_dry_run(&format!(
+                "delete existing bond connection \"{}\" and slave connections {:?}",
+                bond_ifname, replaced_slave_ifnames
+            ));
+        }
+        print_dry_run(&format!(
+            "create and activate bond connection \"{}\" with slave interfaces {:?}",
+            bond_ifname, opts.slave_ifnames
+        ));
+        return Ok(());
+    }
+
+    // Only delete replaced connection once all checks pass, so
+    // a failed check leaves the existing connection intact
+    if existing_conn.is_some() {
+        info!("Replacing existing bond connection \"{}\"", bond_ifname);
+        delete_bond(client, replaced_bond_opts(&opts, replaced_slave_ifnames)).await?;
+    }
+
+    info!("Creating bond connection \"{}\"", bond_ifname);
+    client
+        .add_connection_future(&bond_conn, true)
+        .await
+        .map_err(map_nm_error)?;
+
+    info!("Activating bond connection \"{}\"", bond_ifname);
+    let mut slave_active_conns: Vec<(&str, ActiveConnection)> = vec![];
+    for (wired_dev, slave_ifname) in wired_devs.iter().zip(opts.slave_ifnames.iter()) {
+        let slave_active_conn =
+            add_slave_connection(client, &opts, bond_ifname, slave_ifname, wired_dev).await?;
+        slave_active_conns.push((slave_ifname, slave_active_conn));
+    }
+
+    let bond_conn = match get_active_connection(client, DeviceType::Bond, &bond_conn) {
+        Some(c) => c,
+        None => return Err(anyhow!("Bond connection \"{}\" not active", &bond_ifname)),
+    };
+
+    if opts.no_wait {
+        print_activation_state(&bond_conn, bond_ifname);
+        return Ok(());
+    }
+
+    // Bond stays activating until its slaves are up, so wait on slaves first
+    // to report which slave, if any, failed rather than only the bond
+    wait_for_slaves_to_activate(&slave_active_conns, opts.timeout).await?;
+
+    let res = wait_for_connection_to_activate(&bond_conn, opts.timeout).await;
+
+    if res.is_ok() {
+        info!("Activated bond connection \"{}\"", &bond_ifname);
+    }
+    res
+}
+
+// Wait for each slave wired connection to activate, erroring with the first slave which fails
+async fn wait_for_slaves_to_activate(
+    slave_active_conns: &[(&str, ActiveConnection)],
+    timeout: Option<Duration>,
+) -> Result<()> {
+    for (slave_ifname, slave_active_conn) in slave_active_conns.iter() {
+        debug!(
+            "Waiting for slave wired connection \"{}\" to activate",
+            slave_ifname
+        );
+        wait_for_connection_to_activate(slave_active_conn, timeout)
+            .await
+            .with_context(|| {
+                format!(
+                    "Slave wired connection \"{}\" failed to activate",
+                    slave_ifname
+                )
+            })?;
+    }
+
+    Ok(())
+}
+
+// Ensure no wired connection, active or not, for any of the given slave interfaces
+// is a slave of a bond other than the given bond
+fn check_slaves_unclaimed(
+    client: &impl NmClient,
+    slave_ifnames: &HashSet<String>,
+    bond_ifname: &str,
+) -> Result<()> {
+    for conn in client.connections().into_iter() {
+        let conn = conn.upcast::<Connection>();
+
+        if conn.setting_wired().is_none() {
+            continue;
+        }
+
+        let slave_ifname = match conn.interface_name() {
+            Some(ifname) if slave_ifnames.contains(ifname.as_str()) => ifname,
+            _ => continue,
+        };
+
+        let slave_master = conn.setting_connection().and_then(|s| s.master());
+        check_slave_unclaimed(&slave_ifname, slave_master.as_deref(), bond_ifname)?;
+    }
+
+    Ok(())
+}
+
+fn check_slave_unclaimed(
+    slave_ifname: &str,
+    slave_master: Option<&str>,
+    bond_ifname: &str,
+) -> Result<()> {
+    match slave_master {
+        Some(master) if master != bond_ifname => Err(anyhow!(
+            "Interface \"{}\" is already a slave of bond \"{}\", not using as slave of bond \"{}\"",
+            slave_ifname,
+            master,
+            bond_ifname
+        )),
+        _ => Ok(()),
+    }
+}
+
+// Deactivate any active standalone wired connections using the given slave interfaces,
+// freeing devices for use as bond slaves. Errors if any interface is already a bond slave,
+// or if any interface has an active standalone connection and preemption is disabled.
+// Active slaves of the bond being replaced, if any, are left for its deletion
+async fn deactivate_conflicting_wired_connections(
+    client: &impl NmClient,
+    slave_ifnames: &HashSet<String>,
+    replaced_bond_ifname: Option<&str>,
+    dry_run: bool,
+    no_preempt: bool,
+    yes: bool,
+) -> Result<()> {
     info!(
         "Deactivating any existing wired connections which use same interfaces as bond \
          slave wired connection ifnames: \"{:?}\"",
-        &opts.slave_ifnames
+        slave_ifnames
     );
 
-    for slave_ifname in opts.slave_ifnames.iter() {
-        // Find and deactivate any existing standalone wired connection with same ifname
-        let existing_wired_conn = create_wired_connection(slave_ifname, None)?;
-        match get_active_connection(client, DeviceType::Ethernet, &existing_wired_conn) {
-            Some(c) => {
-                debug!(
-                    "Found active standalone wired connection with slave ifname \"{}\", deactivating",
-                    slave_ifname
-                );
-                client.deactivate_connection_future(&c).await?;
+    for slave_ifname in slave_ifnames.iter() {
+        // Slaves of another bond are never preempted
+        let existing_wired_conn_slave = create_wired_connection(slave_ifname, Some(""))?;
+        if let Some(c) =
+            get_active_connection(client, DeviceType::Ethernet, &existing_wired_conn_slave)
+        {
+            let slave_master = c
+                .connection()
+                .and_then(|c| c.setting_connection())
+                .and_then(|s| s.master());
+            if replaced_bond_ifname.is_some() && slave_master.as_deref() == replaced_bond_ifname {
                 continue;
             }
-            None => debug!(
-                "No matching active standalone wired connection for interface \"{}\"",
-                slave_ifname
-            ),
-        };
 
-        // Find and deactivate any existing slave wired connection with same ifname
-        let existing_wired_conn_slave = create_wired_connection(slave_ifname, Some(""))?;
-        match get_active_connection(client, DeviceType::Ethernet, &existing_wired_conn_slave) {
-            Some(_) => {
-                return Err(anyhow!(
-                    "Found existing slave wired connection with ifname \"{}\" matching desired slave ifname",
-                    slave_ifname
-                ))
-            }
-            None => debug!(
-                "No matching active slave wired connection for interface \"{}\"",
+            return Err(anyhow!(
+                "Found existing slave wired connection with ifname \"{}\" matching desired slave ifname",
                 slave_ifname
-            ),
-        };
+            ));
+        }
+
+        // Deactivate any existing standalone wired connection on the interface
+        deactivate_connections_on_iface(
+            client,
+            slave_ifname,
+            DeviceType::Ethernet,
+            no_preempt,
+            dry_run,
+            yes,
+        )
+        .await?;
     }
 
-    // Check that backing devices for provided wired interfaces exist
+    Ok(())
+}
+
+// Backing devices for slave interfaces, in slave interface iteration order
+fn get_slave_devices(
+    client: &impl NmClient,
+    slave_ifnames: &HashSet<String>,
+) -> Result<Vec<Device>> {
     let mut wired_devs: Vec<Device> = vec![];
-    for slave_ifname in opts.slave_ifnames.iter() {
+    for slave_ifname in slave_ifnames.iter() {
         let wired_dev = match client.device_by_iface(slave_ifname) {
             Some(device) => device,
             None => {
@@ -168,48 +829,54 @@ pub async fn create_bond(client: &Client, opts: BondOpts) -> Result<()> {
         wired_devs.push(wired_dev);
     }
 
-    // Bond connection doesn't exist and backing ethernet devices exist,
-    // so create new bond connection (using newly-created wired connections
-    // which are backed by existing wired devices)
-    info!("Creating bond connection \"{}\"", bond_ifname);
-    client.add_connection_future(&bond_conn, true).await?;
-
-    info!("Activating bond connection \"{}\"", bond_ifname);
-    for (wired_dev, slave_ifname) in wired_devs.iter().zip(opts.slave_ifnames.iter()) {
-        let wired_conn = create_wired_connection(slave_ifname, Some(bond_ifname))?;
-
-        // Created and configured connection, send it off to NetworkManager
-        let wired_conn = client.add_connection_future(&wired_conn, true).await?;
-
-        // Connections are created, connect backing devices to enable the connections.
-        // If everything is normal, adding the connections should activate them as
-        // we have already downed any other connections that were using the backing devices.
-        //
-        // On off chance that devices are deactivating using the `ip link set down`
-        // command, for example, this will reactivate the devices.
-        //
-        // Non-Network Manager device deactivation thru software will result in NetworkManager
-        // not realizing that the devices or connections are inactive. Simply re-activating
-        // the connection will reset this, assuming no other software gets in the way.
-        client
-            .activate_connection_future(Some(&wired_conn), Some(wired_dev), None)
-            .await?;
-    }
+    Ok(wired_devs)
+}
 
-    let bond_conn = match get_active_connection(client, DeviceType::Bond, &bond_conn) {
-        Some(c) => c,
-        None => return Err(anyhow!("Bond connection \"{}\" not active", &bond_ifname)),
-    };
-    let res = wait_for_connection_to_activate(&bond_conn).await;
+// Create and activate wired slave connection for bond on given backing device
+async fn add_slave_connection(
+    client: &impl NmClient,
+    opts: &BondOpts,
+    bond_ifname: &str,
+    slave_ifname: &str,
+    wired_dev: &Device,
+) -> Result<ActiveConnection> {
+    let slave_id_scheme = opts.slave_id_scheme.unwrap_or_default();
+    let slave_conn_id = get_slave_conn_id(slave_id_scheme, bond_ifname, slave_ifname);
+    let wired_conn =
+        create_wired_connection_with_id(slave_ifname, Some(bond_ifname), &slave_conn_id)?;
+    add_wired_setting(&wired_conn, opts.cloned_mac.as_deref(), opts.mtu);
+
+    // Created and configured connection, send it off to NetworkManager
+    let wired_conn = client
+        .add_connection_future(&wired_conn, true)
+        .await
+        .map_err(map_nm_error)?;
+
+    // Connections are created, connect backing devices to enable the connections.
+    // If everything is normal, adding the connections should activate them as
+    // we have already downed any other connections that were using the backing devices.
+    //
+    // On off chance that devices are deactivating using the `ip link set down`
+    // command, for example, this will reactivate the devices.
+    //
+    // Non-Network Manager device deactivation thru software will result in NetworkManager
+    // not realizing that the devices or connections are inactive. Simply re-activating
+    // the connection will reset this, assuming no other software gets in the way.
+    activate_connection_with_retry(client, &wired_conn, wired_dev, opts.retry).await
+}
 
-    if res.is_ok() {
-        info!("Activated bond connection \"{}\"", &bond_ifname);
+// Options to delete a bond being replaced along with all of its existing
+// slave connections, not just those also slaves of the replacement bond
+fn replaced_bond_opts(opts: &BondOpts, existing_slave_ifnames: Vec<String>) -> BondOpts {
+    BondOpts {
+        slave_ifnames: HashSet::from_iter(existing_slave_ifnames),
+        keep_slaves: false,
+        ..opts.clone()
     }
-    res
 }
 
 #[instrument(skip(client), err)]
-pub async fn delete_bond(client: &Client, opts: BondOpts) -> Result<()> {
+pub async fn delete_bond(client: &impl NmClient, opts: BondOpts) -> Result<()> {
     let bond_ifname = match &opts.bond_ifname {
         Some(ifname) => ifname,
         None => return Err(anyhow!("Required bond interface not specified")),
@@ -223,7 +890,9 @@ pub async fn delete_bond(client: &Client, opts: BondOpts) -> Result<()> {
     let bond_conn = create_bond_connection(&opts)?;
 
     // Use created SimpleConnection to find matching connections from NetworkManager
-    let bond_remote_conn = match get_connection(client, DeviceType::Bond, &bond_conn) {
+    let uuid = opts.uuid.as_deref();
+    let target_conn = get_target_connection(client, DeviceType::Bond, &bond_conn, uuid)?;
+    let bond_remote_conn = match target_conn {
         Some(c) => c,
         None => {
             return Err(anyhow!(
@@ -233,12 +902,31 @@ pub async fn delete_bond(client: &Client, opts: BondOpts) -> Result<()> {
         }
     };
 
+    if opts.dry_run {
+        print_dry_run(&format!(
+            "delete bond connection \"{}\" and slave connections {:?}",
+            bond_ifname, opts.slave_ifnames
+        ));
+        return Ok(());
+    }
+
+    confirm_action(
+        &format!(
+            "delete bond connection \"{}\" and slave connections {:?}",
+            bond_ifname, opts.slave_ifnames
+        ),
+        opts.yes,
+    )?;
+
     // Deactivate bond connection
     // Automatically deactivates slave connections on success
     info!("Deactivating bond connection with interface \"{}\" (and associated slave wired connections)", bond_ifname);
-    match get_active_connection(client, DeviceType::Bond, &bond_conn) {
+    match get_target_active_connection(client, DeviceType::Bond, &bond_conn, uuid) {
         Some(c) => {
             client.deactivate_connection_future(&c).await?;
+
+            // Give connection a chance to fully deactivate before deleting
+            wait_for_deactivate_before_delete(&c, opts.timeout).await?;
             info!("Bond connection and associated interfaces deactivated");
         }
         None => {
@@ -249,6 +937,14 @@ pub async fn delete_bond(client: &Client, opts: BondOpts) -> Result<()> {
         }
     };
 
+    // Find slave connections to keep before bond is gone
+    let kept_slave_conns = match opts.keep_slaves {
+        true => {
+            get_slave_connections(client, bond_ifname, DeviceType::Ethernet).unwrap_or_default()
+        }
+        false => vec![],
+    };
+
     // Delete bond connection
     info!(
         "Deleting bond connection with interface \"{}\"",
@@ -257,22 +953,21 @@ pub async fn delete_bond(client: &Client, opts: BondOpts) -> Result<()> {
     bond_remote_conn.delete_future().await?;
     info!("Bond connection deleted");
 
-    let slave_conns = get_slave_connections(client, bond_ifname, DeviceType::Ethernet);
-
-    let mut slave_ifnames: Vec<String> = vec![];
-    if let Some(slave_conns) = slave_conns {
-        for (ix, conn) in slave_conns.iter().enumerate() {
-            match conn.setting_connection() {
-                Some(setting) => {
-                    if let Some(slave_ifname) = setting.interface_name() {
-                        slave_ifnames.push(slave_ifname.as_str().to_string());
-                    }
-                }
-                None => warn!("Unable to get address string with index \"{}\"", ix),
-            }
-        }
+    // Optionally keep slave wired connections usable as standalone wired connections
+    for slave_conn in kept_slave_conns.iter() {
+        info!(
+            "Converting slave wired connection \"{}\" to standalone wired connection",
+            slave_conn.id().unwrap_or_default()
+        );
+        clear_slave_master(slave_conn)?;
+        slave_conn
+            .commit_changes_future(true)
+            .await
+            .map_err(map_nm_error)?;
     }
 
+    let slave_ifnames = get_slave_ifnames(client, bond_ifname);
+
     // Optionally delete wired slave connections if associated with bond connection to be deleted
     for slave_ifname in opts.slave_ifnames.iter() {
         let wired_conn = create_wired_connection(slave_ifname, Some(bond_ifname))?;
@@ -300,35 +995,34 @@ pub async fn delete_bond(client: &Client, opts: BondOpts) -> Result<()> {
 }
 
 #[instrument(skip(client), err)]
-pub fn bond_status(client: &Client, opts: BondOpts) -> Result<()> {
+pub fn bond_status(client: &impl NmClient, opts: BondOpts) -> Result<StatusCode> {
     let bond_ifname = match &opts.bond_ifname {
         Some(ifname) => ifname,
         None => return Err(anyhow!("Required bond interface not specified")),
     };
 
-    if opts.slave_ifnames.iter().any(|c| c.is_empty()) {
-        return Err(anyhow!("Empty string is not a valid slave interface name"));
-    }
-
-    // Create bond struct here so we can comprehensively search
-    // for any matching existing connection, should it exist
-    // Does not add connection to Network Manager, that happens later
-    let bond_conn = create_bond_connection(&opts)?;
+    // Bonds are matched by interface alone and everything reported is read back
+    // from the existing connection, so ignore any other user-specified options
+    let lookup_opts = BondOpts {
+        bond_ifname: Some(bond_ifname.clone()),
+        ..Default::default()
+    };
+    let bond_conn = create_bond_connection(&lookup_opts)?;
 
     // Only possibly active, so assume deactivated until proven otherwise
     let mut conn_state: ActiveConnectionState = ActiveConnectionState::Deactivated;
-    let mut ip4_addr_strs: Vec<String> = vec![];
-    if let Some(c) = get_active_connection(client, DeviceType::Bond, &bond_conn) {
+    let mut ip4_addrs: Vec<StatusAddress> = vec![];
+    let mut ip6_addrs: Vec<StatusAddress> = vec![];
+    let mut route_metric: Option<i64> = None;
+    let uuid = opts.uuid.as_deref();
+    if let Some(c) = get_target_active_connection(client, DeviceType::Bond, &bond_conn, uuid) {
         conn_state = c.state();
 
         // Gather active IPv4 info
         if let Some(cfg) = c.ip4_config() {
             // Active IPv4 addresses (i.e. non-NetworkManager configured)
-            for ip4_addr in cfg.addresses() {
-                let addr = ip4_addr.address().unwrap(); // TODO
-                let addr_str = addr.as_str();
-                ip4_addr_strs.push(format!("{addr_str}\t(active)"));
-            }
+            ip4_addrs.append(&mut get_active_addrs(&cfg));
+            route_metric = get_default_route_metric(&cfg);
         } else {
             // Expected when bond is waiting to get IP information.
             // Possible when backing devices are used for other
@@ -338,11 +1032,17 @@ pub fn bond_status(client: &Client, opts: BondOpts) -> Result<()> {
                 bond_ifname
             )
         }
+
+        // Gather active IPv6 info. May be missing when IPv6 is disabled
+        if let Some(cfg) = c.ip6_config() {
+            ip6_addrs.append(&mut get_active_addrs(&cfg));
+        }
     };
 
     // Try to get connection that matches what we want from NetworkManager
     // If it doesn't exist, no sense continuing
-    let bond_remote_conn = match get_connection(client, DeviceType::Bond, &bond_conn) {
+    let target_conn = get_target_connection(client, DeviceType::Bond, &bond_conn, uuid)?;
+    let bond_remote_conn = match target_conn {
         Some(c) => c,
         None => {
             return Err(anyhow!(
@@ -361,42 +1061,295 @@ pub fn bond_status(client: &Client, opts: BondOpts) -> Result<()> {
         }
     };
 
-    let ip4_method_gstr = match bond_ip4_settings.method() {
+    // Effective metric when active, otherwise any configured metric
+    let route_metric = route_metric.or(get_route_metric(bond_ip4_settings.route_metric()));
+
+    let ip4_method = match bond_ip4_settings.method() {
         Some(m) => m,
         None => return Err(anyhow!("Unable to get ip4 configuration method")),
     };
-    let ip4_method = ip4_method_gstr.as_str();
 
     // Static IPv4 addresses
-    for ix in 0..bond_ip4_settings.num_addresses() {
-        match bond_ip4_settings.address(ix as i32) {
-            // Why does this take a signed int lmao
-            Some(c) => match c.address() {
-                Some(addr) => {
-                    ip4_addr_strs.push(format!("{addr}\t(static)"));
-                }
-                None => warn!("Unable to get address string with index \"{}\"", ix),
-            },
-            None => warn!("Unable to get address with index \"{}\"", ix),
+    ip4_addrs.append(&mut get_static_addrs(&bond_ip4_settings));
+
+    // Gather static IPv6 info, if any. Connections created
+    // before IPv6 support was added may not have IPv6 settings
+    let ip6_method = match bond_conn.setting_ip6_config() {
+        Some(s) => {
+            ip6_addrs.append(&mut get_static_addrs(&s));
+            s.method()
+        }
+        None => None,
+    };
+
+    // Backing connections/devices. Only one slave carries traffic in active-backup mode
+    let active_backup = bond_conn
+        .setting_bond()
+        .and_then(|s| s.option_by_name(SETTING_BOND_OPTION_MODE))
+        .is_some_and(|m| m == get_bond_mode_str(BondMode::ActiveBackup));
+    let active_slave = match active_backup {
+        true => Some(get_active_slave(bond_ifname)),
+        false => None,
+    };
+
+    let slaves = get_slave_ifnames(client, bond_ifname)
+        .iter()
+        .map(|ifname| {
+            let state = client.device_by_iface(ifname).map(|d| d.state());
+            get_slave_status(ifname, state, active_slave.as_ref())
+        })
+        .collect();
+
+    // Operational parameters as configured on the matched connection
+    let bond_mode = get_bond_option(&bond_conn, SETTING_BOND_OPTION_MODE);
+    let miimon =
+        get_bond_option(&bond_conn, SETTING_BOND_OPTION_MIIMON).and_then(|m| m.parse::<u32>().ok());
+    let primary = get_bond_option(&bond_conn, SETTING_BOND_OPTION_PRIMARY);
+    let xmit_hash_policy = get_bond_option(&bond_conn, SETTING_BOND_OPTION_XMIT_HASH_POLICY);
+
+    let report = StatusReport {
+        name: bond_ifname.to_string(),
+        kind: "bond".to_string(),
+        active: get_connection_state_str(conn_state).to_string(),
+        wireless: None,
+        bond_mode,
+        miimon,
+        slaves: Some(slaves),
+        primary,
+        xmit_hash_policy,
+        mtu: get_slave_mtu(client, bond_ifname),
+        route_metric,
+        ipv4_method: ip4_method.to_string(),
+        ipv4_addresses: ip4_addrs,
+        ipv4_routes: get_static_routes(&bond_ip4_settings),
+        ipv6_method: ip6_method.map_or(String::new(), |m| m.to_string()),
+        ipv6_addresses: ip6_addrs,
+        dns_over_tls: get_dns_over_tls_status(&bond_conn),
+        zone: get_zone(&bond_conn),
+    };
+    report.print(opts.output)?;
+    Ok(StatusCode::from_state(conn_state))
+}
+
+// Bond option configured on a connection, if any
+fn get_bond_option(conn: &Connection, name: &str) -> Option<String> {
+    conn.setting_bond()
+        .and_then(|s| s.option_by_name(name))
+        .map(|o| o.to_string())
+}
+
+// Bring existing bond connection up or down without recreating or deleting it.
+// Slave connections follow their bond
+#[instrument(skip(client), err)]
+pub async fn set_bond_active(client: &impl NmClient, opts: BondOpts, active: bool) -> Result<()> {
+    if opts.bond_ifname.is_none() {
+        return Err(anyhow!("Required bond interface not specified"));
+    }
+
+    let bond_conn = create_bond_connection(&opts)?;
+    let uuid = opts.uuid.as_deref();
+    match active {
+        true => {
+            activate_existing_connection(
+                client,
+                DeviceType::Bond,
+                &bond_conn,
+                uuid,
+                opts.timeout,
+                opts.no_wait,
+                opts.dry_run,
+            )
+            .await
+        }
+        false => {
+            deactivate_existing_connection(
+                client,
+                DeviceType::Bond,
+                &bond_conn,
+                uuid,
+                opts.timeout,
+                opts.no_wait,
+                opts.dry_run,
+            )
+            .await
         }
     }
+}
+
+// Recover bond options from an existing bond connection, e.g. for export
+// Bonds have no secrets, so always exported in full
+#[instrument(skip(client), err)]
+pub async fn export_bond(client: &impl NmClient, opts: BondOpts) -> Result<()> {
+    let bond_ifname = match &opts.bond_ifname {
+        Some(ifname) => ifname,
+        None => return Err(anyhow!("Required bond interface not specified")),
+    };
+
+    let bond_conn = create_bond_connection(&opts)?;
+
+    let bond_remote_conn = match get_connection(client, DeviceType::Bond, &bond_conn) {
+        Some(c) => c,
+        None => {
+            return Err(anyhow!(
+                "Required bond connection \"{}\" does not exist, quitting...",
+                &bond_ifname
+            ));
+        }
+    };
+
+    export_connection(client, &bond_remote_conn, false).await
+}
+
+pub fn export_bond_opts(client: &impl NmClient, conn: &Connection) -> Result<BondOpts> {
+    let bond_ifname = match conn.interface_name() {
+        Some(ifname) => ifname.to_string(),
+        None => return Err(anyhow!("Unable to get interface name for bond connection")),
+    };
+
+    let s_bond = match conn.setting_bond() {
+        Some(s) => s,
+        None => return Err(anyhow!("Unable to get bond settings")),
+    };
+    let get_option = |name: &str| s_bond.option_by_name(name).map(|v| v.to_string());
+    let parse_option = |name: &str| -> Result<Option<u32>> {
+        match get_option(name) {
+            Some(v) => {
+                Ok(Some(v.parse().map_err(|_| {
+                    anyhow!("Invalid bond {} option \"{}\"", name, v)
+                })?))
+            }
+            None => Ok(None),
+        }
+    };
+
+    let bond_mode = get_configured_bond_mode(&s_bond)?;
+
+    let lacp_u16 = |name: &str| -> Result<Option<u16>> {
+        match parse_option(name)? {
+            Some(v) => Ok(Some(u16::try_from(v)?)),
+            None => Ok(None),
+        }
+    };
+
+    let common = get_common_opts(conn)?;
+    Ok(BondOpts {
+        bond_mode: Some(bond_mode),
+        miimon: parse_option(SETTING_BOND_OPTION_MIIMON)?,
+        updelay: parse_option(SETTING_BOND_OPTION_UPDELAY)?,
+        downdelay: parse_option(SETTING_BOND_OPTION_DOWNDELAY)?,
+        all_slaves_active: get_option(SETTING_BOND_OPTION_ALL_SLAVES_ACTIVE).map(|v| v == "1"),
+        primary: get_option(SETTING_BOND_OPTION_PRIMARY),
+        xmit_hash_policy: get_option(SETTING_BOND_OPTION_XMIT_HASH_POLICY),
+        ad_actor_sys_prio: lacp_u16(SETTING_BOND_OPTION_AD_ACTOR_SYS_PRIO)?,
+        ad_actor_system: get_option(SETTING_BOND_OPTION_AD_ACTOR_SYSTEM),
+        ad_user_port_key: lacp_u16(SETTING_BOND_OPTION_AD_USER_PORT_KEY)?,
+        lacp_rate: get_option(SETTING_BOND_OPTION_LACP_RATE),
+        slave_ifnames: HashSet::from_iter(get_slave_ifnames(client, &bond_ifname)),
+        mtu: get_slave_mtu(client, &bond_ifname),
+        cloned_mac: get_slave_cloned_mac(client, &bond_ifname),
+        slave_id_scheme: get_slave_id_scheme(client, &bond_ifname),
+        route_metric: common.route_metric,
+        ip4_may_fail: common.ip4_may_fail,
+        dhcp_timeout: common.dhcp_timeout,
+        dhcp_client_id: common.dhcp_client_id,
+        dhcp_hostname: common.dhcp_hostname,
+        connection_id: common.id.filter(|id| *id != bond_ifname),
+        bond_ifname: Some(bond_ifname),
+        ip4_addrs: common.ip4_addrs,
+        ip4_method: common.ip4_method,
+        gateway: common.gateway,
+        dns: common.dns,
+        routes: common.routes,
+        ip6_addr: common.ip6_addr,
+        multi_connect: common.multi_connect,
+        autoconnect: common.autoconnect,
+        dns_over_tls: common.dns_over_tls,
+        stable_id: common.stable_id,
+        zone: common.zone,
+        ..Default::default()
+    })
+}
+
+// Print one-line summary of every bond connection known to NetworkManager
+pub fn list_bonds(client: &impl NmClient, opts: BondOpts) -> Result<()> {
+    let mut summaries: Vec<BondSummary> = vec![];
+
+    for remote_conn in client.connections().into_iter() {
+        let conn = remote_conn.upcast::<Connection>();
+
+        let is_bond = conn
+            .setting_connection()
+            .and_then(|s| s.type_())
+            .is_some_and(|t| t.as_str() == SETTING_BOND_SETTING_NAME);
+        if !is_bond {
+            continue;
+        }
 
-    let slave_conns = get_slave_connections(client, bond_ifname, DeviceType::Ethernet);
+        let bond_ifname = match conn.interface_name() {
+            Some(ifname) => ifname.to_string(),
+            None => {
+                warn!("Unable to get interface name for bond connection {}", conn);
+                continue;
+            }
+        };
 
-    // Begin printing status info
-    println!("Name:\t\t{}", &bond_ifname);
-    println!("Type:\t\tbond");
-    println!("Active:\t\t{}", get_connection_state_str(conn_state));
+        // Only possibly active, so assume deactivated until proven otherwise
+        let bond_conn = create_bond_connection(&BondOpts {
+            bond_ifname: Some(bond_ifname.clone()),
+            ..Default::default()
+        })?;
+        let conn_state = match get_active_connection(client, DeviceType::Bond, &bond_conn) {
+            Some(c) => c.state(),
+            None => ActiveConnectionState::Deactivated,
+        };
+
+        summaries.push(BondSummary {
+            num_slaves: get_slave_ifnames(client, &bond_ifname).len(),
+            name: bond_ifname,
+            active: get_connection_state_str(conn_state).to_string(),
+        });
+    }
+    summaries.sort_by_key(|s| s.name.clone());
 
-    // Backing connections/devices
-    print!("Slave devices:");
-    if let Some(slave_conns) = slave_conns {
-        if slave_conns.is_empty() {
-            // Print first addr on same line, but if no addrs, need newline
-            println!();
+    match opts.output {
+        OutputFormat::Text => {
+            for summary in summaries.iter() {
+                println!("{}", get_bond_summary_str(summary));
+            }
         }
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&summaries)?),
+    }
+
+    Ok(())
+}
+
+fn get_slave_conn_id(scheme: SlaveIdScheme, bond_ifname: &str, slave_ifname: &str) -> String {
+    match scheme {
+        SlaveIdScheme::Slave => slave_ifname.to_string(),
+        SlaveIdScheme::BondSlave => format!("{}-{}", bond_ifname, slave_ifname),
+    }
+}
+
+fn get_bond_summary_str(summary: &BondSummary) -> String {
+    format!(
+        "{}\t{}\t{} slave(s)",
+        summary.name, summary.active, summary.num_slaves
+    )
+}
+
+/// Device types which may be enslaved to a bond
+const SLAVE_DEVICE_TYPES: [DeviceType; 2] = [DeviceType::Ethernet, DeviceType::Wifi];
+
+// Gather interface names of all slave connections for a bond, wired or otherwise
+fn get_slave_ifnames(client: &impl NmClient, bond_ifname: &str) -> Vec<String> {
+    let mut slave_ifnames: Vec<String> = vec![];
+
+    for slave_device_type in SLAVE_DEVICE_TYPES {
+        let slave_conns = match get_slave_connections(client, bond_ifname, slave_device_type) {
+            Some(c) => c,
+            None => continue,
+        };
 
-        let mut slave_ifnames: Vec<String> = vec![];
         for (ix, conn) in slave_conns.iter().enumerate() {
             match conn.setting_connection() {
                 Some(setting) => {
@@ -404,155 +1357,826 @@ pub fn bond_status(client: &Client, opts: BondOpts) -> Result<()> {
                         slave_ifnames.push(slave_ifname.as_str().to_string());
                     }
                 }
-                None => warn!("Unable to get address string with index \"{}\"", ix),
+                None => warn!(
+                    "Unable to get settings for slave connection with index \"{}\"",
+                    ix
+                ),
             }
         }
+    }
 
-        for (ix, ifname) in slave_ifnames.iter().enumerate() {
-            if ix == 0 {
-                // Print first ifname on same line as "Slave devices"
-                println!("\t{ifname}");
-                continue;
-            }
-            println!("\t\t{ifname}");
+    slave_ifnames
+}
+
+// Existing bond connection and all its slave connections, i.e. every
+// connection deleted when the bond is replaced with "--force"
+pub fn get_replaced_bond_connections(
+    client: &impl NmClient,
+    opts: &BondOpts,
+) -> Result<Vec<RemoteConnection>> {
+    let bond_conn = create_bond_connection(opts)?;
+    let mut conns: Vec<RemoteConnection> = get_connection(client, DeviceType::Bond, &bond_conn)
+        .into_iter()
+        .collect();
+
+    if let Some(bond_ifname) = &opts.bond_ifname {
+        for slave_device_type in SLAVE_DEVICE_TYPES {
+            conns.extend(
+                get_slave_connections(client, bond_ifname, slave_device_type).unwrap_or_default(),
+            );
         }
     }
 
-    // IPv4 status info
-    println!("IPv4:");
-    println!("  Method:\t{ip4_method}");
+    Ok(conns)
+}
+
+// MTU configured on bond's wired slave connections, if any. Slaves
+// created together share an MTU, so the first configured one is used
+fn get_slave_mtu(client: &impl NmClient, bond_ifname: &str) -> Option<u32> {
+    get_slave_connections(client, bond_ifname, DeviceType::Ethernet)?
+        .iter()
+        .filter_map(|c| c.setting_wired())
+        .find_map(|s| get_mtu(s.mtu()))
+}
+
+// Cloned MAC address of bond slave connections, all created with the same one
+fn get_slave_cloned_mac(client: &impl NmClient, bond_ifname: &str) -> Option<String> {
+    get_slave_connections(client, bond_ifname, DeviceType::Ethernet)?
+        .iter()
+        .filter_map(|c| c.setting_wired())
+        .find_map(|s| s.cloned_mac_address().map(|m| m.to_string()))
+}
+
+// Slave connection id scheme, inferred from existing slave connection ids.
+// None when slaves use the default scheme
+fn get_slave_id_scheme(client: &impl NmClient, bond_ifname: &str) -> Option<SlaveIdScheme> {
+    let bond_slave = get_slave_connections(client, bond_ifname, DeviceType::Ethernet)?
+        .iter()
+        .any(|c| match (c.id(), c.interface_name()) {
+            (Some(id), Some(slave_ifname)) => {
+                id == get_slave_conn_id(SlaveIdScheme::BondSlave, bond_ifname, &slave_ifname)
+            }
+            _ => false,
+        });
+
+    bond_slave.then_some(SlaveIdScheme::BondSlave)
+}
+
+// Add slave wired connections to an existing bond, without tearing down the bond
+// or its other slaves. Conflicting standalone wired connections are deactivated
+#[instrument(skip(client), err)]
+pub async fn add_bond_slaves(client: &impl NmClient, opts: BondOpts) -> Result<()> {
+    let bond_ifname = match &opts.bond_ifname {
+        Some(ifname) => ifname,
+        None => return Err(anyhow!("Required bond interface not specified")),
+    };
+
+    validate_slave_change(&opts.slave_ifnames)?;
+    check_bond_exists(client, bond_ifname)?;
 
-    print!("  Addresses:");
-    if ip4_addr_strs.is_empty() {
-        // Print first addr on same line, but if no addrs, need newline
-        println!();
+    let slave_ifnames = get_slave_ifnames(client, bond_ifname);
+    if let Some(slave_ifname) = opts
+        .slave_ifnames
+        .iter()
+        .find(|s| slave_ifnames.contains(*s))
+    {
+        return Err(anyhow!(
+            "Interface \"{}\" is already a slave of bond \"{}\"",
+            slave_ifname,
+            bond_ifname
+        ));
     }
-    for (ix, addr) in ip4_addr_strs.iter().enumerate() {
-        if ix == 0 {
-            // Print first IP addr on same line as "Addresses"
-            println!("\t{addr}");
-            continue;
-        }
-        println!("\t\t{addr}");
+
+    check_slaves_unclaimed(client, &opts.slave_ifnames, bond_ifname)?;
+
+    deactivate_conflicting_wired_connections(
+        client,
+        &opts.slave_ifnames,
+        None,
+        opts.dry_run,
+        opts.no_preempt,
+        opts.yes,
+    )
+    .await?;
+    let wired_devs = get_slave_devices(client, &opts.slave_ifnames)?;
+
+    if opts.dry_run {
+        print_dry_run(&format!(
+            "create and activate slave interfaces {:?} of bond \"{}\"",
+            opts.slave_ifnames, bond_ifname
+        ));
+        return Ok(());
+    }
+
+    for (wired_dev, slave_ifname) in wired_devs.iter().zip(opts.slave_ifnames.iter()) {
+        info!(
+            "Adding slave wired connection \"{}\" to bond \"{}\"",
+            slave_ifname, bond_ifname
+        );
+        add_slave_connection(client, &opts, bond_ifname, slave_ifname, wired_dev).await?;
     }
 
     Ok(())
 }
 
-pub fn create_bond_connection(opts: &BondOpts) -> Result<SimpleConnection> {
-    let connection = SimpleConnection::new();
+// Deactivate and delete slave wired connections of an existing bond,
+// without tearing down the bond or its other slaves
+#[instrument(skip(client), err)]
+pub async fn remove_bond_slaves(client: &impl NmClient, opts: BondOpts) -> Result<()> {
+    let bond_ifname = match &opts.bond_ifname {
+        Some(ifname) => ifname,
+        None => return Err(anyhow!("Required bond interface not specified")),
+    };
 
-    let s_connection = SettingConnection::new();
-    let s_bond = SettingBond::new();
-    let s_ip4 = SettingIP4Config::new();
+    validate_slave_change(&opts.slave_ifnames)?;
+    check_bond_exists(client, bond_ifname)?;
 
-    // General connection settings
-    s_connection.set_type(Some(SETTING_BOND_SETTING_NAME));
+    // Find all slave connections before changing anything, so nothing
+    // is removed when any specified interface is not a slave of the bond
+    let mut slave_conns: Vec<(&String, RemoteConnection)> = vec![];
+    for slave_ifname in opts.slave_ifnames.iter() {
+        let wired_conn = create_wired_connection(slave_ifname, Some(bond_ifname))?;
+        match get_connection(client, DeviceType::Ethernet, &wired_conn) {
+            Some(c) => slave_conns.push((slave_ifname, c)),
+            None => {
+                return Err(anyhow!(
+                    "Slave wired connection \"{}\" of bond \"{}\" does not exist",
+                    slave_ifname,
+                    bond_ifname
+                ))
+            }
+        }
+    }
 
-    match &opts.bond_ifname {
-        Some(ifname) => {
-            s_connection.set_id(Some(ifname));
-            s_connection.set_interface_name(Some(ifname));
+    if opts.dry_run {
+        print_dry_run(&format!(
+            "deactivate and delete slave interfaces {:?} of bond \"{}\"",
+            opts.slave_ifnames, bond_ifname
+        ));
+        return Ok(());
+    }
+
+    for (slave_ifname, slave_remote_conn) in slave_conns.iter() {
+        let wired_conn = create_wired_connection(slave_ifname, Some(bond_ifname))?;
+        if let Some(c) = get_active_connection(client, DeviceType::Ethernet, &wired_conn) {
+            info!(
+                "Deactivating slave wired connection \"{}\" of bond \"{}\"",
+                slave_ifname, bond_ifname
+            );
+            client.deactivate_connection_future(&c).await?;
+
+            // Give connection a chance to fully deactivate before deleting
+            wait_for_deactivate_before_delete(&c, opts.timeout).await?;
         }
-        None => return Err(anyhow!("Required bond interface not specified")),
+
+        info!(
+            "Deleting slave wired connection \"{}\" of bond \"{}\"",
+            slave_ifname, bond_ifname
+        );
+        slave_remote_conn.delete_future().await?;
     }
 
-    // Bond-specific settings
-    let bond_mode = get_bond_mode_str(opts.bond_mode);
-    if !s_bond.add_option(SETTING_BOND_OPTION_MODE, bond_mode) {
-        error!("Unable to set bond mode option to \"{}\"", bond_mode);
+    Ok(())
+}
+
+// Adding or removing slaves requires one or more valid slave interface names
+fn validate_slave_change(slave_ifnames: &HashSet<String>) -> Result<()> {
+    if slave_ifnames.is_empty() {
         return Err(anyhow!(
-            "Unable to set bond mode option to \"{}\"",
-            bond_mode
+            "One or more slave interfaces (\"--slaves\") required"
         ));
     }
-    if !s_bond.add_option(SETTING_BOND_OPTION_MIIMON, "100") {
-        error!("Unable to set bond MIIMON option to \"{}\"", "100");
-        return Err(anyhow!("Unable to set bond MIIMON option to \"{}\"", "100"));
+
+    for slave_ifname in slave_ifnames.iter() {
+        validate_ifname(slave_ifname)?;
     }
 
-    // IPv4 settings
-    match &opts.ip4_addr {
-        Some(addr) => {
-            let ip4_net = Ipv4Net::from_str(addr)?;
-
-            let ip4_addr = IPAddress::new(
-                libc::AF_INET,
-                ip4_net.addr().to_string().as_str(),
-                ip4_net.prefix_len() as u32,
-            )?;
+    Ok(())
+}
 
-            s_ip4.add_address(&ip4_addr);
-            s_ip4.set_method(Some(SETTING_IP4_CONFIG_METHOD_MANUAL));
-        }
-        None => {
-            s_ip4.set_method(Some(SETTING_IP4_CONFIG_METHOD_AUTO));
-        }
+// Change bond options of an existing bond in place, rather than deleting and recreating
+// it. Existing bond mode is kept unless a new mode is specified. Active bonds are
+// reactivated so changes take effect
+#[instrument(skip(client), err)]
+pub async fn modify_bond(client: &impl NmClient, mut opts: BondOpts) -> Result<()> {
+    let bond_ifname = match &opts.bond_ifname {
+        Some(ifname) => ifname.clone(),
+        None => return Err(anyhow!("Required bond interface not specified")),
+    };
+
+    // Primary must be an existing slave, unless slaves explicitly specified
+    if opts.slave_ifnames.is_empty() {
+        opts.slave_ifnames = HashSet::from_iter(get_slave_ifnames(client, &bond_ifname));
     }
 
-    connection.add_setting(s_connection);
-    connection.add_setting(s_bond);
-    connection.add_setting(s_ip4);
+    // Bond matching only considers interface name, so new options don't prevent a match
+    let bond_conn = create_bond_connection(&opts)?;
+    let bond_remote_conn = match get_connection(client, DeviceType::Bond, &bond_conn) {
+        Some(c) => c,
+        None => {
+            return Err(anyhow!(
+                "Required bond connection \"{}\" does not exist, quitting...",
+                &bond_ifname
+            ));
+        }
+    };
 
-    Ok(connection)
-}
+    let s_bond = match bond_remote_conn.setting_bond() {
+        Some(s) => s,
+        None => return Err(anyhow!("Unable to get bond settings")),
+    };
 
-fn get_bond_mode_str(mode: BondMode) -> &'static str {
-    match mode {
-        BondMode::RoundRobin => todo!(),
-        BondMode::ActiveBackup => "active-backup",
-        BondMode::XOR => todo!(),
-        BondMode::Broadcast => todo!(),
-        BondMode::DynamicLinkAggregation => todo!(),
-        BondMode::TransmitLoadBalancing => todo!(),
-        BondMode::AdaptiveLoadBalancing => todo!(),
+    // Keep existing mode unless explicitly changed, so options like
+    // primary are validated against the mode the bond will actually use
+    if opts.bond_mode.is_none() {
+        opts.bond_mode = Some(get_configured_bond_mode(&s_bond)?);
     }
-}
+    opts.validate()?;
 
-#[cfg(test)]
-mod test {
-    use super::*;
+    apply_bond_changes(&s_bond, &opts)?;
 
-    #[test]
-    fn no_bond_ifname() {
-        let cfg = "
-            bond_mode: !ActiveBackup
-            slave_interfaces:
-                - enp2s0
-        ";
+    if opts.dry_run {
+        print_dry_run(&format!(
+            "modify bond connection \"{}\" options",
+            bond_ifname
+        ));
+        return Ok(());
+    }
 
-        let opts = parse_bond_opts(cfg).unwrap();
-        assert!(opts.bond_ifname.is_none());
+    info!("Modifying bond connection \"{}\"", bond_ifname);
+    bond_remote_conn
+        .commit_changes_future(true)
+        .await
+        .map_err(map_nm_error)?;
+
+    if get_active_connection(client, DeviceType::Bond, &bond_conn).is_none() {
+        info!(
+            "Bond connection \"{}\" not active, changes apply on next activation",
+            bond_ifname
+        );
+        return Ok(());
     }
 
-    #[test]
-    fn empty_bond_ifname() {
-        let cfg = "
-            bond_interface: \"\"
-            bond_mode: !ActiveBackup
-            slave_interfaces:
-                - enp2s0
-        ";
+    info!("Reactivating bond connection \"{}\"", bond_ifname);
+    let bond_active_conn = client
+        .activate_connection_future(Some(&bond_remote_conn), None::<&Device>, None)
+        .await
+        .map_err(map_nm_error)?;
 
-        let opts = parse_bond_opts(cfg).unwrap();
-        assert!(opts.bond_ifname.is_none());
+    if opts.no_wait {
+        print_activation_state(&bond_active_conn, &bond_ifname);
+        return Ok(());
     }
+    wait_for_connection_to_activate(&bond_active_conn, opts.timeout).await?;
 
-    // Expect to default to BondMode default
-    #[test]
-    fn no_bond_mode() {
-        let cfg = "
-            bond_interface: bond0
-            slave_interfaces:
-                - enp2s0
-        ";
-        let default_mode: BondMode = Default::default();
+    info!("Modified bond connection \"{}\"", bond_ifname);
+    Ok(())
+}
 
-        let opts = parse_bond_opts(cfg).unwrap();
-        assert_eq!(opts.bond_mode, default_mode);
+// Apply any specified bond options to existing bond settings. Options only
+// applicable in the previous bond mode are removed
+fn apply_bond_changes(s_bond: &SettingBond, opts: &BondOpts) -> Result<()> {
+    let bond_mode = opts.bond_mode();
+    set_bond_option(
+        s_bond,
+        SETTING_BOND_OPTION_MODE,
+        get_bond_mode_str(bond_mode),
+    )?;
+
+    let options = [
+        (
+            SETTING_BOND_OPTION_MIIMON,
+            opts.miimon.map(|m| m.to_string()),
+        ),
+        (
+            SETTING_BOND_OPTION_UPDELAY,
+            opts.updelay.map(|d| d.to_string()),
+        ),
+        (
+            SETTING_BOND_OPTION_DOWNDELAY,
+            opts.downdelay.map(|d| d.to_string()),
+        ),
+    ];
+    for (name, value) in options.iter() {
+        if let Some(value) = value {
+            set_bond_option(s_bond, name, value)?;
+        }
     }
 
-    #[test]
+    if !all_slaves_active_applicable(bond_mode) {
+        s_bond.remove_option(SETTING_BOND_OPTION_ALL_SLAVES_ACTIVE);
+    } else if let Some(all_slaves_active) = opts.all_slaves_active {
+        let all_slaves_active = if all_slaves_active { "1" } else { "0" };
+        set_bond_option(
+            s_bond,
+            SETTING_BOND_OPTION_ALL_SLAVES_ACTIVE,
+            all_slaves_active,
+        )?;
+    }
+
+    if bond_mode != BondMode::ActiveBackup {
+        s_bond.remove_option(SETTING_BOND_OPTION_PRIMARY);
+    } else if let Some(primary) = &opts.primary {
+        set_bond_option(s_bond, SETTING_BOND_OPTION_PRIMARY, primary)?;
+    }
+
+    if !xmit_hash_policy_applicable(bond_mode) {
+        s_bond.remove_option(SETTING_BOND_OPTION_XMIT_HASH_POLICY);
+    } else if let Some(policy) = &opts.xmit_hash_policy {
+        set_bond_option(s_bond, SETTING_BOND_OPTION_XMIT_HASH_POLICY, policy)?;
+    }
+
+    let lacp_opts = [
+        (
+            SETTING_BOND_OPTION_AD_ACTOR_SYS_PRIO,
+            opts.ad_actor_sys_prio.map(|p| p.to_string()),
+        ),
+        (
+            SETTING_BOND_OPTION_AD_ACTOR_SYSTEM,
+            opts.ad_actor_system.clone(),
+        ),
+        (
+            SETTING_BOND_OPTION_AD_USER_PORT_KEY,
+            opts.ad_user_port_key.map(|k| k.to_string()),
+        ),
+        (SETTING_BOND_OPTION_LACP_RATE, opts.lacp_rate.clone()),
+    ];
+    for (name, value) in lacp_opts.iter() {
+        if bond_mode != BondMode::DynamicLinkAggregation {
+            s_bond.remove_option(name);
+        } else if let Some(value) = value {
+            set_bond_option(s_bond, name, value)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn set_bond_option(s_bond: &SettingBond, name: &str, value: &str) -> Result<()> {
+    if !s_bond.add_option(name, value) {
+        error!("Unable to set bond {} option to \"{}\"", name, value);
+        return Err(anyhow!(
+            "Unable to set bond {} option to \"{}\"",
+            name,
+            value
+        ));
+    }
+
+    Ok(())
+}
+
+// Active slave as reported by the kernel bonding driver. NetworkManager does not
+// expose it. None when no slave is active or the bond device does not exist
+fn get_active_slave(bond_ifname: &str) -> Option<String> {
+    let path = format!("/sys/class/net/{bond_ifname}/bonding/active_slave");
+
+    std::fs::read_to_string(path)
+        .ok()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+// Active slave is only reported for active-backup bonds, which is when outer option is Some
+fn get_slave_status(
+    slave_ifname: &str,
+    state: Option<DeviceState>,
+    active_slave: Option<&Option<String>>,
+) -> SlaveStatus {
+    SlaveStatus {
+        interface: slave_ifname.to_string(),
+        state: state.map_or("missing", get_device_state_str).to_string(),
+        active: active_slave.map(|a| a.as_deref() == Some(slave_ifname)),
+    }
+}
+
+// Bond matching only considers interface name, so bond mode here is irrelevant
+fn check_bond_exists(client: &impl NmClient, bond_ifname: &str) -> Result<()> {
+    let bond_conn = create_bond_connection(&BondOpts {
+        bond_ifname: Some(bond_ifname.to_string()),
+        ..Default::default()
+    })?;
+
+    if get_connection(client, DeviceType::Bond, &bond_conn).is_none() {
+        return Err(anyhow!(
+            "Bond connection \"{}\" does not exist",
+            bond_ifname
+        ));
+    }
+
+    Ok(())
+}
+
+// Move slave wired connection from one bond to another by editing the existing
+// slave connection in place, rather than deleting and recreating it. This preserves
+// any slave-specific settings. Bond interface name in options is the slave ifname
+#[instrument(skip(client), err)]
+pub async fn move_bond_slave(client: &impl NmClient, opts: BondOpts) -> Result<()> {
+    let slave_ifname = match &opts.bond_ifname {
+        Some(ifname) => ifname,
+        None => return Err(anyhow!("Required slave interface not specified")),
+    };
+
+    let from_bond_ifname = match &opts.from_bond_ifname {
+        Some(ifname) if !ifname.is_empty() => ifname,
+        _ => return Err(anyhow!("Required source bond interface not specified")),
+    };
+
+    let to_bond_ifname = match &opts.to_bond_ifname {
+        Some(ifname) if !ifname.is_empty() => ifname,
+        _ => return Err(anyhow!("Required destination bond interface not specified")),
+    };
+
+    // Both bonds must exist
+    for bond_ifname in [from_bond_ifname, to_bond_ifname] {
+        check_bond_exists(client, bond_ifname)?;
+    }
+
+    // Empty master matches slave wired connection with any master
+    let any_slave_conn = create_wired_connection(slave_ifname, Some(""))?;
+    let slave_remote_conn = match get_connection(client, DeviceType::Ethernet, &any_slave_conn) {
+        Some(c) => c,
+        None => {
+            return Err(anyhow!(
+                "Slave wired connection \"{}\" does not exist",
+                slave_ifname
+            ))
+        }
+    };
+
+    let slave_master = slave_remote_conn
+        .setting_connection()
+        .and_then(|s| s.master());
+    check_move_slave(
+        slave_ifname,
+        slave_master.as_ref().map(|m| m.as_str()),
+        from_bond_ifname,
+        to_bond_ifname,
+    )?;
+
+    // Deactivate slave so it detaches from source bond. Reactivated
+    // once moved, attaching it to the destination bond
+    let slave_conn = create_wired_connection(slave_ifname, Some(from_bond_ifname))?;
+    let was_active = match get_active_connection(client, DeviceType::Ethernet, &slave_conn) {
+        Some(c) => {
+            info!(
+                "Deactivating slave wired connection \"{}\" of bond \"{}\"",
+                slave_ifname, from_bond_ifname
+            );
+            client.deactivate_connection_future(&c).await?;
+            wait_for_state(&c, ActiveConnectionState::Deactivated, opts.timeout).await?;
+            true
+        }
+        None => false,
+    };
+
+    info!(
+        "Moving slave wired connection \"{}\" from bond \"{}\" to bond \"{}\"",
+        slave_ifname, from_bond_ifname, to_bond_ifname
+    );
+    set_slave_master(&slave_remote_conn, to_bond_ifname)?;
+    slave_remote_conn
+        .commit_changes_future(true)
+        .await
+        .map_err(map_nm_error)?;
+
+    if was_active {
+        info!(
+            "Reactivating slave wired connection \"{}\" on bond \"{}\"",
+            slave_ifname, to_bond_ifname
+        );
+        let slave_active_conn = client
+            .activate_connection_future(Some(&slave_remote_conn), None::<&Device>, None)
+            .await
+            .map_err(map_nm_error)?;
+        wait_for_connection_to_activate(&slave_active_conn, opts.timeout).await?;
+    }
+
+    info!("Moved slave wired connection \"{}\"", slave_ifname);
+    Ok(())
+}
+
+// Ensure slave with given current master may be moved between specified bonds
+fn check_move_slave(
+    slave_ifname: &str,
+    slave_master: Option<&str>,
+    from_bond_ifname: &str,
+    to_bond_ifname: &str,
+) -> Result<()> {
+    if from_bond_ifname == to_bond_ifname {
+        return Err(anyhow!(
+            "Source and destination bond \"{}\" are the same",
+            from_bond_ifname
+        ));
+    }
+
+    match slave_master {
+        Some(master) if master == from_bond_ifname => Ok(()),
+        Some(master) => Err(anyhow!(
+            "Slave \"{}\" belongs to bond \"{}\", not \"{}\"",
+            slave_ifname,
+            master,
+            from_bond_ifname
+        )),
+        None => Err(anyhow!(
+            "Wired connection \"{}\" is not a bond slave",
+            slave_ifname
+        )),
+    }
+}
+
+// Point slave connection at new bond master. Changes are local
+// until committed to NetworkManager
+fn set_slave_master(conn: &impl ConnectionExt, bond_ifname: &str) -> Result<()> {
+    let s_connection = match conn.setting_connection() {
+        Some(s) => s,
+        None => return Err(anyhow!("Unable to get connection settings")),
+    };
+
+    s_connection.set_master(Some(bond_ifname));
+    s_connection.set_slave_type(Some(SETTING_BOND_SETTING_NAME));
+
+    Ok(())
+}
+
+// Detach slave connection from its bond master, leaving a standalone
+// connection. Changes are local until committed to NetworkManager
+fn clear_slave_master(conn: &impl ConnectionExt) -> Result<()> {
+    let s_connection = match conn.setting_connection() {
+        Some(s) => s,
+        None => return Err(anyhow!("Unable to get connection settings")),
+    };
+
+    s_connection.set_master(None);
+    s_connection.set_slave_type(None);
+
+    Ok(())
+}
+
+pub fn create_bond_connection(opts: &BondOpts) -> Result<SimpleConnection> {
+    let connection = SimpleConnection::new();
+
+    let s_connection = SettingConnection::new();
+    let s_bond = SettingBond::new();
+    let s_ip4 = SettingIP4Config::new();
+
+    // General connection settings
+    s_connection.set_type(Some(SETTING_BOND_SETTING_NAME));
+    s_connection.set_autoconnect(opts.autoconnect.unwrap_or(true));
+
+    if let Some(multi_connect) = opts.multi_connect {
+        s_connection.set_multi_connect(multi_connect as i32);
+    }
+
+    if let Some(stable_id) = &opts.stable_id {
+        validate_stable_id(stable_id)?;
+        s_connection.set_stable_id(Some(stable_id));
+    }
+
+    if let Some(zone) = &opts.zone {
+        validate_zone(zone)?;
+        s_connection.set_zone(Some(zone));
+    }
+
+    if let Some(dns_over_tls) = opts.dns_over_tls {
+        set_dns_over_tls(&s_connection, dns_over_tls);
+    }
+
+    match &opts.bond_ifname {
+        Some(ifname) => {
+            s_connection.set_id(Some(opts.connection_id.as_deref().unwrap_or(ifname)));
+            s_connection.set_interface_name(Some(ifname));
+        }
+        None => return Err(anyhow!("Required bond interface not specified")),
+    }
+
+    // Bond-specific settings
+    let bond_mode = get_bond_mode_str(opts.bond_mode());
+    if !s_bond.add_option(SETTING_BOND_OPTION_MODE, bond_mode) {
+        error!("Unable to set bond mode option to \"{}\"", bond_mode);
+        return Err(anyhow!(
+            "Unable to set bond mode option to \"{}\"",
+            bond_mode
+        ));
+    }
+    let miimon = opts.miimon.unwrap_or(DEFAULT_MIIMON).to_string();
+    if !s_bond.add_option(SETTING_BOND_OPTION_MIIMON, &miimon) {
+        error!("Unable to set bond MIIMON option to \"{}\"", miimon);
+        return Err(anyhow!(
+            "Unable to set bond MIIMON option to \"{}\"",
+            miimon
+        ));
+    }
+
+    let link_delays = [
+        (SETTING_BOND_OPTION_UPDELAY, opts.updelay),
+        (SETTING_BOND_OPTION_DOWNDELAY, opts.downdelay),
+    ];
+    for (name, delay) in link_delays.iter() {
+        if let Some(delay) = delay {
+            let delay = delay.to_string();
+            if !s_bond.add_option(name, &delay) {
+                error!("Unable to set bond {} option to \"{}\"", name, delay);
+                return Err(anyhow!(
+                    "Unable to set bond {} option to \"{}\"",
+                    name,
+                    delay
+                ));
+            }
+        }
+    }
+
+    if let Some(all_slaves_active) = opts.all_slaves_active {
+        if all_slaves_active_applicable(opts.bond_mode()) {
+            let all_slaves_active = if all_slaves_active { "1" } else { "0" };
+
+            if !s_bond.add_option(SETTING_BOND_OPTION_ALL_SLAVES_ACTIVE, all_slaves_active) {
+                error!(
+                    "Unable to set bond all_slaves_active option to \"{}\"",
+                    all_slaves_active
+                );
+                return Err(anyhow!(
+                    "Unable to set bond all_slaves_active option to \"{}\"",
+                    all_slaves_active
+                ));
+            }
+        }
+    }
+
+    if let Some(primary) = &opts.primary {
+        if opts.bond_mode() == BondMode::ActiveBackup
+            && !s_bond.add_option(SETTING_BOND_OPTION_PRIMARY, primary)
+        {
+            error!("Unable to set bond primary option to \"{}\"", primary);
+            return Err(anyhow!(
+                "Unable to set bond primary option to \"{}\"",
+                primary
+            ));
+        }
+    }
+
+    if let Some(policy) = &opts.xmit_hash_policy {
+        if xmit_hash_policy_applicable(opts.bond_mode())
+            && !s_bond.add_option(SETTING_BOND_OPTION_XMIT_HASH_POLICY, policy)
+        {
+            error!(
+                "Unable to set bond xmit_hash_policy option to \"{}\"",
+                policy
+            );
+            return Err(anyhow!(
+                "Unable to set bond xmit_hash_policy option to \"{}\"",
+                policy
+            ));
+        }
+    }
+
+    // LACP options. Rejected outside 802.3ad mode during validation
+    if opts.bond_mode() == BondMode::DynamicLinkAggregation {
+        let lacp_opts = [
+            (
+                SETTING_BOND_OPTION_AD_ACTOR_SYS_PRIO,
+                opts.ad_actor_sys_prio.map(|p| p.to_string()),
+            ),
+            (
+                SETTING_BOND_OPTION_AD_ACTOR_SYSTEM,
+                opts.ad_actor_system.clone(),
+            ),
+            (
+                SETTING_BOND_OPTION_AD_USER_PORT_KEY,
+                opts.ad_user_port_key.map(|k| k.to_string()),
+            ),
+            (SETTING_BOND_OPTION_LACP_RATE, opts.lacp_rate.clone()),
+        ];
+
+        for (name, value) in lacp_opts.iter() {
+            if let Some(value) = value {
+                if !s_bond.add_option(name, value) {
+                    error!("Unable to set bond {} option to \"{}\"", name, value);
+                    return Err(anyhow!(
+                        "Unable to set bond {} option to \"{}\"",
+                        name,
+                        value
+                    ));
+                }
+            }
+        }
+    }
+
+    // IPv4 settings
+    apply_route_metric(&s_ip4, opts.route_metric)?;
+    apply_ip4_method(&s_ip4, opts.ip4_method, &opts.ip4_addrs, Ip4Method::Auto)?;
+
+    add_ip4_routes(&s_ip4, &opts.routes)?;
+    apply_ip4_gateway_dns(
+        &s_ip4,
+        opts.gateway.as_deref(),
+        &opts.dns,
+        !opts.ip4_addrs.is_empty(),
+    )?;
+    apply_ip4_dhcp_timeout(&s_ip4, opts.dhcp_timeout, !opts.ip4_addrs.is_empty())?;
+    apply_ip4_dhcp_identity(
+        &s_ip4,
+        opts.dhcp_client_id.as_deref(),
+        opts.dhcp_hostname.as_deref(),
+    );
+
+    apply_ip4_may_fail(&s_ip4, opts.ip4_may_fail, opts.dhcp_timeout)?;
+
+    connection.add_setting(s_connection);
+    connection.add_setting(s_bond);
+    connection.add_setting(s_ip4);
+    connection.add_setting(create_ip6_setting(opts.ip6_addr.as_ref())?);
+
+    Ok(connection)
+}
+
+// Only ActiveBackup mode has inactive slaves whose received frames would be dropped
+fn all_slaves_active_applicable(mode: BondMode) -> bool {
+    mode == BondMode::ActiveBackup
+}
+
+// Only modes which hash traffic across slaves use a transmit hash policy
+fn xmit_hash_policy_applicable(mode: BondMode) -> bool {
+    matches!(mode, BondMode::XOR | BondMode::DynamicLinkAggregation)
+}
+
+// Mode configured in existing bond settings. Mode may be either
+// name or number, e.g. "active-backup" or "1"
+fn get_configured_bond_mode(s_bond: &SettingBond) -> Result<BondMode> {
+    let mode = match s_bond.option_by_name(SETTING_BOND_OPTION_MODE) {
+        Some(mode) => mode,
+        None => return Ok(BondMode::default()),
+    };
+
+    BondMode::value_variants()
+        .iter()
+        .find(|m| get_bond_mode_str(**m) == mode || (**m as i32).to_string() == mode)
+        .copied()
+        .ok_or_else(|| anyhow!("Unknown bond mode \"{}\"", mode))
+}
+
+fn get_bond_mode_str(mode: BondMode) -> &'static str {
+    match mode {
+        BondMode::RoundRobin => "balance-rr",
+        BondMode::ActiveBackup => "active-backup",
+        BondMode::XOR => "balance-xor",
+        BondMode::Broadcast => "broadcast",
+        BondMode::DynamicLinkAggregation => "802.3ad",
+        BondMode::TransmitLoadBalancing => "balance-tlb",
+        BondMode::AdaptiveLoadBalancing => "balance-alb",
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use clap::Parser;
+
+    use super::*;
+    use crate::cli::{App, Command};
+    use crate::connection::mock::MockClient;
+
+    #[test]
+    fn no_bond_ifname() {
+        let cfg = "
+            bond_mode: !ActiveBackup
+            slave_interfaces:
+                - enp2s0
+        ";
+
+        let opts = parse_bond_opts(cfg).unwrap();
+        assert!(opts.bond_ifname.is_none());
+    }
+
+    #[test]
+    fn empty_bond_ifname() {
+        let cfg = "
+            bond_interface: \"\"
+            bond_mode: !ActiveBackup
+            slave_interfaces:
+                - enp2s0
+        ";
+
+        let opts = parse_bond_opts(cfg).unwrap();
+        assert!(opts.bond_ifname.is_none());
+    }
+
+    // Expect to default to BondMode default
+    #[test]
+    fn no_bond_mode() {
+        let cfg = "
+            bond_interface: bond0
+            slave_interfaces:
+                - enp2s0
+        ";
+        let default_mode: BondMode = Default::default();
+
+        let opts = parse_bond_opts(cfg).unwrap();
+        assert!(opts.bond_mode.is_none());
+        assert_eq!(opts.bond_mode(), default_mode);
+    }
+
+    #[test]
     #[should_panic]
     fn empty_bond_mode() {
         let cfg = "
@@ -605,4 +2229,983 @@ mod test {
         let opts = parse_bond_opts(cfg).unwrap();
         assert!(opts.slave_ifnames.is_empty());
     }
+
+    #[test]
+    fn bond_mode_option() {
+        let modes = [
+            (BondMode::RoundRobin, "balance-rr"),
+            (BondMode::ActiveBackup, "active-backup"),
+            (BondMode::XOR, "balance-xor"),
+            (BondMode::Broadcast, "broadcast"),
+            (BondMode::DynamicLinkAggregation, "802.3ad"),
+            (BondMode::TransmitLoadBalancing, "balance-tlb"),
+            (BondMode::AdaptiveLoadBalancing, "balance-alb"),
+        ];
+
+        for (mode, mode_str) in modes {
+            let opts = BondOpts {
+                bond_ifname: Some("bond0".to_string()),
+                bond_mode: Some(mode),
+                ..Default::default()
+            };
+
+            let conn = create_bond_connection(&opts).unwrap();
+            let s_bond = conn.setting_bond().unwrap();
+            assert_eq!(
+                s_bond.option_by_name(SETTING_BOND_OPTION_MODE).unwrap(),
+                mode_str
+            );
+        }
+    }
+
+    #[test]
+    fn miimon() {
+        let cfg = "
+            bond_interface: bond0
+            bond_mode: !ActiveBackup
+            miimon: 250
+            slave_interfaces:
+                - enp2s0
+        ";
+
+        let opts = parse_bond_opts(cfg).unwrap();
+        assert_eq!(opts.miimon, Some(250));
+
+        let conn = create_bond_connection(&opts).unwrap();
+        let s_bond = conn.setting_bond().unwrap();
+        assert_eq!(
+            s_bond.option_by_name(SETTING_BOND_OPTION_MIIMON).unwrap(),
+            "250"
+        );
+    }
+
+    #[test]
+    fn modify_bond_options() {
+        let cfg = "
+            bond_interface: bond0
+            bond_mode: !ActiveBackup
+            primary: enp2s0
+            slave_interfaces:
+                - enp2s0
+        ";
+
+        let opts = parse_bond_opts(cfg).unwrap();
+        let conn = create_bond_connection(&opts).unwrap();
+        let s_bond = conn.setting_bond().unwrap();
+
+        // Only change MII monitoring interval, primary kept
+        let mut opts = opts.clone();
+        opts.miimon = Some(250);
+        opts.primary = None;
+        apply_bond_changes(&s_bond, &opts).unwrap();
+        assert_eq!(
+            s_bond.option_by_name(SETTING_BOND_OPTION_MIIMON).unwrap(),
+            "250"
+        );
+        assert_eq!(
+            s_bond.option_by_name(SETTING_BOND_OPTION_PRIMARY).unwrap(),
+            "enp2s0"
+        );
+
+        // Changing mode removes primary, only valid in active-backup mode
+        opts.bond_mode = Some(BondMode::RoundRobin);
+        apply_bond_changes(&s_bond, &opts).unwrap();
+        assert_eq!(
+            s_bond.option_by_name(SETTING_BOND_OPTION_MODE).unwrap(),
+            get_bond_mode_str(BondMode::RoundRobin)
+        );
+        assert!(s_bond.option_by_name(SETTING_BOND_OPTION_PRIMARY).is_none());
+    }
+
+    #[test]
+    fn modify_bond_mode_removes_options() {
+        let cfg = "
+            bond_interface: bond0
+            bond_mode: !DynamicLinkAggregation
+            xmit_hash_policy: layer3+4
+            lacp_rate: fast
+            ad_actor_sys_prio: 100
+            ad_actor_system: \"02:00:00:00:00:01\"
+            ad_user_port_key: 10
+            slave_interfaces:
+                - enp2s0
+        ";
+
+        let opts = parse_bond_opts(cfg).unwrap();
+        let conn = create_bond_connection(&opts).unwrap();
+        let s_bond = conn.setting_bond().unwrap();
+        assert!(s_bond
+            .option_by_name(SETTING_BOND_OPTION_LACP_RATE)
+            .is_some());
+
+        // Options only applicable in 802.3ad mode removed, new mode options applied
+        let mut opts = opts.clone();
+        opts.bond_mode = Some(BondMode::ActiveBackup);
+        opts.xmit_hash_policy = None;
+        opts.lacp_rate = None;
+        opts.ad_actor_sys_prio = None;
+        opts.ad_actor_system = None;
+        opts.ad_user_port_key = None;
+        opts.primary = Some("enp2s0".to_string());
+        opts.all_slaves_active = Some(true);
+        apply_bond_changes(&s_bond, &opts).unwrap();
+
+        assert_eq!(
+            s_bond.option_by_name(SETTING_BOND_OPTION_MODE).unwrap(),
+            get_bond_mode_str(BondMode::ActiveBackup)
+        );
+        for name in [
+            SETTING_BOND_OPTION_XMIT_HASH_POLICY,
+            SETTING_BOND_OPTION_LACP_RATE,
+            SETTING_BOND_OPTION_AD_ACTOR_SYS_PRIO,
+            SETTING_BOND_OPTION_AD_ACTOR_SYSTEM,
+            SETTING_BOND_OPTION_AD_USER_PORT_KEY,
+        ] {
+            assert!(
+                s_bond.option_by_name(name).is_none(),
+                "{} not removed",
+                name
+            );
+        }
+        assert_eq!(
+            s_bond.option_by_name(SETTING_BOND_OPTION_PRIMARY).unwrap(),
+            "enp2s0"
+        );
+        assert_eq!(
+            s_bond
+                .option_by_name(SETTING_BOND_OPTION_ALL_SLAVES_ACTIVE)
+                .unwrap(),
+            "1"
+        );
+    }
+
+    #[test]
+    fn replace_bond_slaves() {
+        let cfg = "
+            bond_interface: bond0
+            slave_interfaces:
+                - enp3s0
+                - enp4s0
+        ";
+
+        // Existing bond slaves deleted, regardless of replacement bond slaves
+        let opts = parse_bond_opts(cfg).unwrap();
+        let existing = vec!["enp2s0".to_string(), "enp3s0".to_string()];
+        let delete_opts = replaced_bond_opts(&opts, existing.clone());
+        assert_eq!(delete_opts.slave_ifnames, HashSet::from_iter(existing));
+        assert_eq!(delete_opts.bond_ifname, opts.bond_ifname);
+        assert!(!delete_opts.keep_slaves);
+    }
+
+    #[test]
+    fn modify_keeps_bond_mode() {
+        let cfg = "
+            bond_interface: bond0
+            bond_mode: !DynamicLinkAggregation
+            slave_interfaces:
+                - enp2s0
+        ";
+
+        let opts = parse_bond_opts(cfg).unwrap();
+        let conn = create_bond_connection(&opts).unwrap();
+        let s_bond = conn.setting_bond().unwrap();
+
+        // As on command line without "--mode", only MII monitoring interval changes
+        let args = App::parse_from(["nutil", "bond", "modify", "bond0", "--miimon", "250"]);
+        let c_args = match args.command.unwrap() {
+            Command::Bond { c_args, .. } => c_args,
+            _ => panic!("Expected bond command"),
+        };
+        let mut opts = BondOpts::try_from(c_args).unwrap();
+        assert!(opts.bond_mode.is_none());
+
+        // Existing mode resolved as in modify_bond()
+        opts.bond_mode = Some(get_configured_bond_mode(&s_bond).unwrap());
+        apply_bond_changes(&s_bond, &opts).unwrap();
+        assert_eq!(
+            s_bond.option_by_name(SETTING_BOND_OPTION_MODE).unwrap(),
+            get_bond_mode_str(BondMode::DynamicLinkAggregation)
+        );
+        assert_eq!(
+            s_bond.option_by_name(SETTING_BOND_OPTION_MIIMON).unwrap(),
+            "250"
+        );
+    }
+
+    #[test]
+    fn ip6_addr() {
+        let cfg = "
+            bond_interface: bond0
+            ip6_addr: \"2001:db8::1/64\"
+        ";
+
+        let opts = parse_bond_opts(cfg).unwrap();
+        assert_eq!(
+            opts.ip6_addr,
+            Some(Ipv6Net::from_str("2001:db8::1/64").unwrap())
+        );
+    }
+
+    #[test]
+    fn empty_ip6_addr() {
+        let cfg = "
+            bond_interface: bond0
+            ip6_addr: \"\"
+        ";
+
+        let opts = parse_bond_opts(cfg).unwrap();
+        assert!(opts.ip6_addr.is_none());
+    }
+
+    #[test]
+    #[should_panic]
+    fn invalid_ip6_addr() {
+        let cfg = "
+            bond_interface: bond0
+            ip6_addr: \"192.168.0.10/24\"
+        ";
+
+        parse_bond_opts(cfg).unwrap();
+    }
+
+    #[test]
+    fn all_slaves_active() {
+        let cfg = "
+            bond_interface: bond0
+            bond_mode: !ActiveBackup
+            all_slaves_active: true
+            slave_interfaces:
+                - enp2s0
+        ";
+
+        let opts = parse_bond_opts(cfg).unwrap();
+        assert_eq!(opts.all_slaves_active, Some(true));
+        assert!(opts.validate().is_ok());
+    }
+
+    #[test]
+    fn all_slaves_active_mode() {
+        assert!(all_slaves_active_applicable(BondMode::ActiveBackup));
+
+        assert!(!all_slaves_active_applicable(BondMode::RoundRobin));
+        assert!(!all_slaves_active_applicable(BondMode::XOR));
+        assert!(!all_slaves_active_applicable(BondMode::Broadcast));
+        assert!(!all_slaves_active_applicable(
+            BondMode::DynamicLinkAggregation
+        ));
+        assert!(!all_slaves_active_applicable(
+            BondMode::TransmitLoadBalancing
+        ));
+        assert!(!all_slaves_active_applicable(
+            BondMode::AdaptiveLoadBalancing
+        ));
+    }
+
+    #[test]
+    fn move_slave_preconditions() {
+        assert!(check_move_slave("enp2s0", Some("bond0"), "bond0", "bond1").is_ok());
+
+        // Same source and destination
+        assert!(check_move_slave("enp2s0", Some("bond0"), "bond0", "bond0").is_err());
+
+        // Slave belongs to another bond
+        assert!(check_move_slave("enp2s0", Some("bond2"), "bond0", "bond1").is_err());
+
+        // Not a slave at all
+        assert!(check_move_slave("enp2s0", None, "bond0", "bond1").is_err());
+    }
+
+    #[test]
+    fn slave_unclaimed() {
+        // Standalone wired connection, free to use
+        assert!(check_slave_unclaimed("enp2s0", None, "bond0").is_ok());
+
+        // Already slave of same bond, e.g. when replacing bond
+        assert!(check_slave_unclaimed("enp2s0", Some("bond0"), "bond0").is_ok());
+
+        // Slave of different bond, error names conflicting bond
+        let err = check_slave_unclaimed("enp2s0", Some("bond1"), "bond0").unwrap_err();
+        assert!(err.to_string().contains("\"bond1\""));
+    }
+
+    #[test]
+    fn slave_status() {
+        // Not active-backup, no active slave reported
+        let status = get_slave_status("eth0", Some(DeviceState::Activated), None);
+        assert_eq!(status.state, "activated");
+        assert_eq!(status.active, None);
+
+        // Active-backup, only active slave marked active
+        let active_slave = Some("eth0".to_string());
+        let status = get_slave_status("eth0", Some(DeviceState::Activated), Some(&active_slave));
+        assert_eq!(status.active, Some(true));
+
+        let status = get_slave_status("eth1", Some(DeviceState::Disconnected), Some(&active_slave));
+        assert_eq!(status.state, "disconnected");
+        assert_eq!(status.active, Some(false));
+
+        // Backing device missing entirely
+        let status = get_slave_status("eth2", None, Some(&None));
+        assert_eq!(status.state, "missing");
+        assert_eq!(status.active, Some(false));
+    }
+
+    #[test]
+    fn move_slave_master() {
+        let conn = create_wired_connection("enp2s0", Some("bond0")).unwrap();
+
+        set_slave_master(&conn, "bond1").unwrap();
+
+        let s_connection = conn.setting_connection().unwrap();
+        assert_eq!(s_connection.master().unwrap(), "bond1");
+        assert_eq!(
+            s_connection.slave_type().unwrap(),
+            SETTING_BOND_SETTING_NAME
+        );
+
+        // Other settings left untouched
+        assert_eq!(s_connection.interface_name().unwrap(), "enp2s0");
+    }
+
+    #[test]
+    fn keep_slave_standalone() {
+        let conn = create_wired_connection("enp2s0", Some("bond0")).unwrap();
+
+        clear_slave_master(&conn).unwrap();
+
+        let s_connection = conn.setting_connection().unwrap();
+        assert!(s_connection.master().is_none());
+        assert!(s_connection.slave_type().is_none());
+
+        // Still usable as a wired connection on the same interface
+        let standalone_conn = create_wired_connection("enp2s0", None).unwrap();
+        assert!(matching_wired_connection(
+            &standalone_conn,
+            conn.upcast_ref::<Connection>()
+        ));
+    }
+
+    #[test]
+    fn stable_id() {
+        let cfg = "
+            bond_interface: bond0
+            bond_mode: !ActiveBackup
+            stable_id: \"${CONNECTION}/${DEVICE}\"
+            slave_interfaces:
+                - enp2s0
+        ";
+
+        let opts = parse_bond_opts(cfg).unwrap();
+        assert_eq!(opts.stable_id.as_deref(), Some("${CONNECTION}/${DEVICE}"));
+
+        let conn = create_bond_connection(&opts).unwrap();
+        let s_conn = conn.setting_connection().unwrap();
+        assert_eq!(s_conn.stable_id().unwrap(), "${CONNECTION}/${DEVICE}");
+    }
+
+    #[test]
+    fn zone() {
+        let cfg = "
+            bond_interface: bond0
+            zone: public
+            slave_interfaces:
+                - enp2s0
+        ";
+
+        let opts = parse_bond_opts(cfg).unwrap();
+        assert_eq!(opts.zone.as_deref(), Some("public"));
+
+        let conn = create_bond_connection(&opts).unwrap();
+        assert_eq!(get_zone(&conn).as_deref(), Some("public"));
+    }
+
+    #[test]
+    fn empty_stable_id() {
+        let cfg = "
+            bond_interface: bond0
+            stable_id: \"\"
+            slave_interfaces:
+                - enp2s0
+        ";
+
+        let opts = parse_bond_opts(cfg).unwrap();
+        assert!(create_bond_connection(&opts).is_err());
+    }
+
+    #[test]
+    fn bond_summary() {
+        let summary = BondSummary {
+            name: "bond0".to_string(),
+            active: "activated".to_string(),
+            num_slaves: 2,
+        };
+
+        assert_eq!(
+            get_bond_summary_str(&summary),
+            "bond0\tactivated\t2 slave(s)"
+        );
+
+        let json = serde_json::to_value(&summary).unwrap();
+        assert_eq!(json["name"], "bond0");
+        assert_eq!(json["active"], "activated");
+        assert_eq!(json["num_slaves"], 2);
+    }
+
+    #[test]
+    fn dns_over_tls() {
+        let cfg = "
+            bond_interface: bond0
+            dns_over_tls: !Opportunistic
+            slave_interfaces:
+                - enp2s0
+        ";
+
+        let opts = parse_bond_opts(cfg).unwrap();
+        assert_eq!(opts.dns_over_tls, Some(DnsOverTls::Opportunistic));
+    }
+
+    #[test]
+    #[should_panic]
+    fn unexpected_dns_over_tls() {
+        let cfg = "
+            bond_interface: bond0
+            dns_over_tls: !Sometimes
+            slave_interfaces:
+                - enp2s0
+        ";
+
+        parse_bond_opts(cfg).unwrap();
+    }
+
+    #[test]
+    fn lacp_options() {
+        let cfg = "
+            bond_interface: bond0
+            bond_mode: !DynamicLinkAggregation
+            ad_actor_sys_prio: 100
+            ad_actor_system: \"00:11:22:aa:bb:cc\"
+            ad_user_port_key: 1023
+            slave_interfaces:
+                - enp2s0
+        ";
+
+        let opts = parse_bond_opts(cfg).unwrap();
+        assert_eq!(opts.ad_actor_sys_prio, Some(100));
+        assert_eq!(opts.ad_actor_system.as_deref(), Some("00:11:22:aa:bb:cc"));
+        assert_eq!(opts.ad_user_port_key, Some(1023));
+        assert!(opts.validate().is_ok());
+    }
+
+    #[test]
+    fn lacp_options_wrong_mode() {
+        let cfg = "
+            bond_interface: bond0
+            bond_mode: !ActiveBackup
+            ad_actor_sys_prio: 100
+            slave_interfaces:
+                - enp2s0
+        ";
+
+        let opts = parse_bond_opts(cfg).unwrap();
+        assert!(opts.validate().is_err());
+    }
+
+    #[test]
+    fn invalid_lacp_options() {
+        let opts = BondOpts {
+            bond_mode: Some(BondMode::DynamicLinkAggregation),
+            ..Default::default()
+        };
+
+        // Invalid MAC address format
+        let bad_mac = BondOpts {
+            ad_actor_system: Some("00:11:22:aa:bb".to_string()),
+            ..opts.clone()
+        };
+        assert!(bad_mac.validate().is_err());
+
+        // Out of range
+        let bad_prio = BondOpts {
+            ad_actor_sys_prio: Some(0),
+            ..opts.clone()
+        };
+        assert!(bad_prio.validate().is_err());
+
+        let bad_port_key = BondOpts {
+            ad_user_port_key: Some(1024),
+            ..opts.clone()
+        };
+        assert!(bad_port_key.validate().is_err());
+
+        let bad_lacp_rate = BondOpts {
+            lacp_rate: Some("medium".to_string()),
+            ..opts.clone()
+        };
+        assert!(bad_lacp_rate.validate().is_err());
+    }
+
+    #[test]
+    fn lacp_rate() {
+        let cfg = "
+            bond_interface: bond0
+            bond_mode: !DynamicLinkAggregation
+            lacp_rate: fast
+            slave_interfaces:
+                - enp2s0
+        ";
+
+        let mut opts = parse_bond_opts(cfg).unwrap();
+        assert_eq!(opts.lacp_rate.as_deref(), Some("fast"));
+        assert!(opts.validate().is_ok());
+
+        // Only applicable in 802.3ad mode
+        opts.bond_mode = Some(BondMode::XOR);
+        assert!(opts.validate().is_err());
+    }
+
+    #[test]
+    #[should_panic]
+    fn lacp_sys_prio_overflow() {
+        let cfg = "
+            bond_interface: bond0
+            bond_mode: !DynamicLinkAggregation
+            ad_actor_sys_prio: 65536
+        ";
+
+        parse_bond_opts(cfg).unwrap();
+    }
+
+    #[test]
+    fn multiple_ip4_addrs() {
+        let cfg = "
+            bond_interface: bond0
+            ip4_addrs:
+                - 192.0.2.1/24
+                - 198.51.100.1/24
+            slave_interfaces:
+                - enp2s0
+        ";
+
+        let opts = parse_bond_opts(cfg).unwrap();
+        assert_eq!(opts.ip4_addrs, vec!["192.0.2.1/24", "198.51.100.1/24"]);
+
+        let conn = create_bond_connection(&opts).unwrap();
+        let s_ip4 = conn.setting_ip4_config().unwrap();
+        assert_eq!(s_ip4.num_addresses(), 2);
+    }
+
+    // Single address under original "ip4_addr" key still accepted
+    #[test]
+    fn single_ip4_addr() {
+        let cfg = "
+            bond_interface: bond0
+            ip4_addr: 192.0.2.1/24
+            slave_interfaces:
+                - enp2s0
+        ";
+
+        let opts = parse_bond_opts(cfg).unwrap();
+        assert_eq!(opts.ip4_addrs, vec!["192.0.2.1/24"]);
+    }
+
+    #[test]
+    fn ip4_method() {
+        // Shared method alongside static address, e.g. gateway for downstream devices
+        let cfg = "
+            bond_interface: bond0
+            ip4_addrs: 192.0.2.1/24
+            ip4_method: !Shared
+            slave_interfaces:
+                - enp2s0
+        ";
+
+        let opts = parse_bond_opts(cfg).unwrap();
+        assert_eq!(opts.ip4_method, Some(Ip4Method::Shared));
+        assert!(opts.validate().is_ok());
+
+        // Manual method without static address
+        let cfg = "
+            bond_interface: bond0
+            ip4_method: !Manual
+            slave_interfaces:
+                - enp2s0
+        ";
+
+        let opts = parse_bond_opts(cfg).unwrap();
+        assert!(opts.validate().is_err());
+    }
+
+    #[test]
+    fn config_override() {
+        let cfg = "
+            bond_interface: \"bond0\"
+            bond_mode: !ActiveBackup
+            slave_interfaces: [\"eth0\"]
+        ";
+
+        let overrides = vec![
+            "bond_mode=!RoundRobin".to_string(),
+            "miimon=200".to_string(),
+        ];
+        let config = apply_config_overrides(cfg, &overrides, config_fields::<BondOpts>()).unwrap();
+        let opts = parse_bond_opts(&config).unwrap();
+
+        assert_eq!(opts.bond_ifname, Some("bond0".to_string()));
+        assert_eq!(opts.bond_mode, Some(BondMode::RoundRobin));
+        assert_eq!(opts.miimon, Some(200));
+    }
+
+    #[test]
+    #[should_panic]
+    fn unknown_config_override() {
+        let cfg = "
+            bond_interface: \"bond0\"
+        ";
+
+        let overrides = vec!["unknown_field=1".to_string()];
+        let config = apply_config_overrides(cfg, &overrides, config_fields::<BondOpts>()).unwrap();
+        parse_bond_opts(&config).unwrap();
+    }
+
+    // Unknown fields in config files are ignored, e.g. keys for other tools,
+    // unlike unknown overrides which are likely typos
+    #[test]
+    fn unknown_config_field() {
+        let cfg = "
+            bond_interface: \"bond0\"
+            unknown_field: 1
+        ";
+
+        let opts = parse_bond_opts(cfg).unwrap();
+        assert_eq!(opts.bond_ifname, Some("bond0".to_string()));
+
+        let fields = config_fields::<BondOpts>();
+        assert!(fields.contains(&"bond_interface"));
+        assert!(fields.contains(&"ip4_addr"));
+        assert!(!fields.contains(&"bond_ifname"));
+        assert!(!fields.contains(&"dry_run"));
+    }
+
+    #[test]
+    fn primary() {
+        let cfg = "
+            bond_interface: \"bond0\"
+            bond_mode: !ActiveBackup
+            slave_interfaces: [\"eth0\", \"eth1\"]
+            primary: \"eth1\"
+        ";
+
+        let opts = parse_bond_opts(cfg).unwrap();
+        assert_eq!(opts.primary, Some("eth1".to_string()));
+        assert!(opts.validate().is_ok());
+    }
+
+    #[test]
+    fn primary_not_slave() {
+        let cfg = "
+            bond_interface: \"bond0\"
+            bond_mode: !ActiveBackup
+            slave_interfaces: [\"eth0\", \"eth1\"]
+            primary: \"eth2\"
+        ";
+
+        let opts = parse_bond_opts(cfg).unwrap();
+        assert!(opts.validate().is_err());
+    }
+
+    #[test]
+    fn xmit_hash_policy() {
+        let cfg = "
+            bond_interface: \"bond0\"
+            bond_mode: !DynamicLinkAggregation
+            slave_interfaces: [\"eth0\", \"eth1\"]
+            xmit_hash_policy: \"layer3+4\"
+        ";
+
+        let mut opts = parse_bond_opts(cfg).unwrap();
+        assert_eq!(opts.xmit_hash_policy, Some("layer3+4".to_string()));
+        assert!(opts.validate().is_ok());
+
+        opts.bond_mode = Some(BondMode::XOR);
+        assert!(opts.validate().is_ok());
+
+        // Only warned about in other modes by default, rejected when strict
+        opts.bond_mode = Some(BondMode::ActiveBackup);
+        assert!(opts.validate().is_ok());
+        opts.strict = true;
+        assert!(opts.validate().is_err());
+
+        // Unknown policies always rejected
+        opts.bond_mode = Some(BondMode::XOR);
+        opts.strict = false;
+        opts.xmit_hash_policy = Some("layer4".to_string());
+        assert!(opts.validate().is_err());
+    }
+
+    #[test]
+    fn no_link_monitoring() {
+        let cfg = "
+            bond_interface: \"bond0\"
+            slave_interfaces: [\"eth0\"]
+            miimon: 0
+        ";
+
+        let mut opts = parse_bond_opts(cfg).unwrap();
+        assert!(!opts.link_monitoring_enabled());
+
+        // Only warned about by default, rejected when strict
+        assert!(opts.validate().is_ok());
+        opts.strict = true;
+        assert!(opts.validate().is_err());
+
+        // Default MII monitoring interval used when unspecified
+        opts.miimon = None;
+        assert!(opts.link_monitoring_enabled());
+        assert!(opts.validate().is_ok());
+    }
+
+    #[test]
+    fn link_delays() {
+        let cfg = "
+            bond_interface: \"bond0\"
+            slave_interfaces: [\"eth0\"]
+            miimon: 100
+            updelay: 200
+            downdelay: 150
+        ";
+
+        let mut opts = parse_bond_opts(cfg).unwrap();
+        assert_eq!(opts.updelay, Some(200));
+        assert_eq!(opts.downdelay, Some(150));
+
+        // Downdelay not a multiple of miimon, only warned about unless strict
+        assert!(opts.validate().is_ok());
+        opts.strict = true;
+        assert!(opts.validate().is_err());
+
+        opts.downdelay = Some(300);
+        assert!(opts.validate().is_ok());
+    }
+
+    #[test]
+    fn autoconnect() {
+        let cfg = "
+            bond_interface: \"bond0\"
+            slave_interfaces: [\"eth0\"]
+            autoconnect: false
+        ";
+
+        let mut opts = parse_bond_opts(cfg).unwrap();
+        assert_eq!(opts.autoconnect, Some(false));
+
+        let conn = create_bond_connection(&opts).unwrap();
+        assert!(!conn.setting_connection().unwrap().autoconnect());
+
+        // Bonds default to autoconnect on
+        opts.autoconnect = None;
+        let conn = create_bond_connection(&opts).unwrap();
+        assert!(conn.setting_connection().unwrap().autoconnect());
+    }
+
+    #[test]
+    fn slave_conn_ids() {
+        assert_eq!(
+            get_slave_conn_id(SlaveIdScheme::Slave, "bond0", "eth0"),
+            "eth0"
+        );
+        assert_eq!(
+            get_slave_conn_id(SlaveIdScheme::BondSlave, "bond0", "eth0"),
+            "bond0-eth0"
+        );
+
+        // Current scheme used when unspecified
+        let cfg = "
+            bond_interface: \"bond0\"
+            slave_interfaces: [\"eth0\"]
+        ";
+        let opts = parse_bond_opts(cfg).unwrap();
+        assert_eq!(
+            opts.slave_id_scheme.unwrap_or_default(),
+            SlaveIdScheme::Slave
+        );
+
+        let cfg = "
+            bond_interface: \"bond0\"
+            slave_interfaces: [\"eth0\"]
+            slave_id_scheme: !BondSlave
+        ";
+        let opts = parse_bond_opts(cfg).unwrap();
+        assert_eq!(opts.slave_id_scheme, Some(SlaveIdScheme::BondSlave));
+    }
+
+    #[test]
+    fn cloned_mac() {
+        let cfg = "
+            bond_interface: \"bond0\"
+            slave_interfaces: [\"eth0\"]
+            cloned_mac: \"random\"
+        ";
+
+        let mut opts = parse_bond_opts(cfg).unwrap();
+        assert_eq!(opts.cloned_mac, Some("random".to_string()));
+        assert!(opts.validate().is_ok());
+
+        opts.cloned_mac = Some("not_a_mac".to_string());
+        assert!(opts.validate().is_err());
+    }
+
+    #[test]
+    fn mtu() {
+        let cfg = "
+            bond_interface: \"bond0\"
+            slave_interfaces: [\"eth0\", \"eth1\"]
+            mtu: 9000
+        ";
+
+        let mut opts = parse_bond_opts(cfg).unwrap();
+        assert_eq!(opts.mtu, Some(9000));
+        assert!(opts.validate().is_ok());
+
+        opts.mtu = Some(9001);
+        assert!(opts.validate().is_err());
+        opts.mtu = Some(67);
+        assert!(opts.validate().is_err());
+    }
+
+    #[test]
+    fn dhcp_timeout() {
+        let cfg = "
+            bond_interface: \"bond0\"
+            slave_interfaces: [\"eth0\"]
+            dhcp_timeout: 30
+        ";
+
+        let mut opts = parse_bond_opts(cfg).unwrap();
+        assert_eq!(opts.dhcp_timeout, Some(30));
+        assert!(opts.validate().is_ok());
+
+        let conn = create_bond_connection(&opts).unwrap();
+        let s_ip4 = conn.setting_ip4_config().unwrap();
+        assert_eq!(s_ip4.dhcp_timeout(), 30);
+        assert!(!s_ip4.may_fail());
+
+        let exported = export_bond_opts(&MockClient::default(), conn.upcast_ref()).unwrap();
+        assert_eq!(exported.dhcp_timeout, Some(30));
+
+        // Default timeout leaves may-fail as is
+        opts.dhcp_timeout = Some(0);
+        let conn = create_bond_connection(&opts).unwrap();
+        assert!(conn.setting_ip4_config().unwrap().may_fail());
+
+        opts.dhcp_timeout = Some(-1);
+        assert!(opts.validate().is_err());
+
+        // No DHCP with static address
+        opts.dhcp_timeout = Some(30);
+        opts.ip4_addrs = vec!["192.0.2.1/24".to_string()];
+        assert!(opts.validate().is_err());
+    }
+
+    #[test]
+    fn connection_id() {
+        let cfg = "
+            bond_interface: \"bond0\"
+            connection_id: \"uplink\"
+            slave_interfaces: [\"eth0\"]
+        ";
+
+        let mut opts = parse_bond_opts(cfg).unwrap();
+        assert_eq!(opts.connection_id.as_deref(), Some("uplink"));
+
+        let conn = create_bond_connection(&opts).unwrap();
+        assert_eq!(conn.id().as_deref(), Some("uplink"));
+        assert_eq!(conn.interface_name().as_deref(), Some("bond0"));
+
+        // Defaults to interface name
+        opts.connection_id = None;
+        let conn = create_bond_connection(&opts).unwrap();
+        assert_eq!(conn.id().as_deref(), Some("bond0"));
+    }
+
+    #[test]
+    fn ip4_may_fail() {
+        let cfg = "
+            bond_interface: \"bond0\"
+            slave_interfaces: [\"eth0\"]
+            ip4_may_fail: false
+        ";
+
+        let mut opts = parse_bond_opts(cfg).unwrap();
+        assert_eq!(opts.ip4_may_fail, Some(false));
+
+        let conn = create_bond_connection(&opts).unwrap();
+        assert!(!conn.setting_ip4_config().unwrap().may_fail());
+
+        // Defaults to NetworkManager behavior
+        opts.ip4_may_fail = None;
+        let conn = create_bond_connection(&opts).unwrap();
+        assert!(conn.setting_ip4_config().unwrap().may_fail());
+    }
+
+    #[test]
+    fn dhcp_identity() {
+        let cfg = "
+            bond_interface: \"bond0\"
+            slave_interfaces: [\"eth0\"]
+            dhcp_client_id: \"mac\"
+            dhcp_hostname: \"host0\"
+        ";
+
+        let mut opts = parse_bond_opts(cfg).unwrap();
+        assert_eq!(opts.dhcp_client_id.as_deref(), Some("mac"));
+        assert_eq!(opts.dhcp_hostname.as_deref(), Some("host0"));
+
+        let conn = create_bond_connection(&opts).unwrap();
+        let s_ip4 = conn.setting_ip4_config().unwrap();
+        assert_eq!(s_ip4.dhcp_client_id().as_deref(), Some("mac"));
+        assert_eq!(s_ip4.dhcp_hostname().as_deref(), Some("host0"));
+        assert!(s_ip4.dhcp_send_hostname());
+
+        // Ignored with static address
+        opts.ip4_addrs = vec!["192.0.2.1/24".to_string()];
+        let conn = create_bond_connection(&opts).unwrap();
+        let s_ip4 = conn.setting_ip4_config().unwrap();
+        assert!(s_ip4.dhcp_client_id().is_none());
+        assert!(s_ip4.dhcp_hostname().is_none());
+    }
+
+    #[test]
+    fn slave_change() {
+        assert!(validate_slave_change(&HashSet::from(["eth2".to_string()])).is_ok());
+
+        assert!(validate_slave_change(&HashSet::new()).is_err());
+        assert!(validate_slave_change(&HashSet::from(["eth/2".to_string()])).is_err());
+    }
+
+    #[test]
+    fn status_missing_bond() {
+        let client = MockClient::default();
+        let opts = BondOpts {
+            bond_ifname: Some("bond0".to_string()),
+            ..Default::default()
+        };
+
+        assert!(bond_status(&client, opts).is_err());
+    }
+
+    // Missing slave devices must be caught before anything is added to NetworkManager
+    #[test]
+    fn create_bond_missing_slaves() {
+        let client = MockClient::default();
+        let opts = BondOpts {
+            bond_ifname: Some("bond0".to_string()),
+            slave_ifnames: HashSet::from(["eth0".to_string(), "eth1".to_string()]),
+            yes: true,
+            ..Default::default()
+        };
+
+        let res = glib::MainContext::default().block_on(create_bond(&client, opts));
+        assert!(res.is_err());
+        assert!(client.calls.borrow().is_empty());
+    }
 }
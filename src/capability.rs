@@ -0,0 +1,203 @@
+use std::sync::Mutex;
+
+use anyhow::{anyhow, Result};
+use nm::utils_version;
+use serde::Serialize;
+use tracing::warn;
+
+use crate::connection::NmClient;
+use crate::status::OutputFormat;
+
+/// Features already warned about as unsupported, so each is only warned about once,
+/// e.g. when applying a directory of configs which all request the same feature
+static WARNED: Mutex<Vec<Feature>> = Mutex::new(Vec::new());
+
+/// Settings which only exist in newer libnm versions
+#[derive(PartialEq, Copy, Clone, Debug)]
+pub enum Feature {
+    /// Connection "dns-over-tls" property
+    DnsOverTls,
+    /// Wireless "ap-isolation" property
+    ApIsolation,
+    /// Wireless security "sae" key management (WPA3-Personal)
+    Sae,
+}
+
+impl Feature {
+    /// First libnm version supporting the feature, encoded the same as `nm::utils_version()`
+    pub fn min_version(self) -> u32 {
+        match self {
+            Feature::DnsOverTls => encode_version(1, 34, 0),
+            Feature::ApIsolation => encode_version(1, 28, 0),
+            Feature::Sae => encode_version(1, 16, 0),
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            Feature::DnsOverTls => "DNS-over-TLS",
+            Feature::ApIsolation => "Client isolation",
+            Feature::Sae => "SAE key management",
+        }
+    }
+}
+
+/// Linked libnm and running NetworkManager daemon versions
+#[derive(Serialize, Debug)]
+pub struct VersionReport {
+    pub libnm: String,
+
+    /// Only reported when NetworkManager is running
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub daemon: Option<String>,
+}
+
+impl VersionReport {
+    pub fn new(client: &impl NmClient) -> Self {
+        VersionReport {
+            libnm: format_version(utils_version()),
+            daemon: client.version(),
+        }
+    }
+
+    pub fn print(&self, output: OutputFormat) -> Result<()> {
+        match output {
+            OutputFormat::Text => {
+                println!("libnm:\t\t{}", self.libnm);
+                println!(
+                    "NetworkManager:\t{}",
+                    self.daemon.as_deref().unwrap_or("unknown")
+                );
+            }
+            OutputFormat::Json => println!("{}", serde_json::to_string_pretty(self)?),
+        }
+
+        Ok(())
+    }
+}
+
+pub fn encode_version(major: u32, minor: u32, micro: u32) -> u32 {
+    (major << 16) | (minor << 8) | micro
+}
+
+// Inverse of encode_version(), e.g. "1.34.0"
+pub fn format_version(version: u32) -> String {
+    format!(
+        "{}.{}.{}",
+        version >> 16,
+        (version >> 8) & 0xff,
+        version & 0xff
+    )
+}
+
+pub fn feature_supported(feature: Feature, version: u32) -> bool {
+    version >= feature.min_version()
+}
+
+// Whether the linked libnm supports a requested feature. When unsupported, warn
+// (once per feature) that the given value is ignored rather than letting setting
+// the property fail with a less obvious error
+pub fn check_feature(feature: Feature, value: &str) -> bool {
+    let version = utils_version();
+    if feature_supported(feature, version) {
+        return true;
+    }
+
+    let mut warned = WARNED.lock().unwrap_or_else(|e| e.into_inner());
+    if !warned.contains(&feature) {
+        warned.push(feature);
+        warn!(
+            "{} requires NetworkManager {} or newer, but libnm is {}, ignoring \"{}\"",
+            feature.description(),
+            format_version(feature.min_version()),
+            format_version(version),
+            value
+        );
+    }
+
+    false
+}
+
+// Like check_feature(), but for features which cannot be ignored without
+// changing what the connection means, e.g. silently downgrading security
+pub fn require_feature(feature: Feature) -> Result<()> {
+    let version = utils_version();
+    if feature_supported(feature, version) {
+        return Ok(());
+    }
+
+    Err(anyhow!(
+        "{} requires NetworkManager {} or newer, but libnm is {}",
+        feature.description(),
+        format_version(feature.min_version()),
+        format_version(version)
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn version_encoding() {
+        assert_eq!(encode_version(1, 34, 0), (1 << 16) | (34 << 8));
+        assert_eq!(format_version(encode_version(1, 42, 4)), "1.42.4");
+        assert_eq!(format_version(2 << 16), "2.0.0");
+    }
+
+    #[test]
+    fn dns_over_tls_version() {
+        // 1.34.0 and later
+        assert!(feature_supported(
+            Feature::DnsOverTls,
+            encode_version(1, 34, 0)
+        ));
+        assert!(feature_supported(
+            Feature::DnsOverTls,
+            encode_version(1, 42, 4)
+        ));
+        assert!(feature_supported(
+            Feature::DnsOverTls,
+            encode_version(2, 0, 0)
+        ));
+
+        // Prior to 1.34.0
+        assert!(!feature_supported(
+            Feature::DnsOverTls,
+            encode_version(1, 33, 9)
+        ));
+        assert!(!feature_supported(
+            Feature::DnsOverTls,
+            encode_version(1, 22, 0)
+        ));
+    }
+
+    #[test]
+    fn ap_isolation_version() {
+        // 1.28.0 and later
+        assert!(feature_supported(
+            Feature::ApIsolation,
+            encode_version(1, 28, 0)
+        ));
+        assert!(feature_supported(
+            Feature::ApIsolation,
+            encode_version(1, 42, 4)
+        ));
+
+        // Prior to 1.28.0
+        assert!(!feature_supported(
+            Feature::ApIsolation,
+            encode_version(1, 27, 9)
+        ));
+        assert!(!feature_supported(
+            Feature::ApIsolation,
+            encode_version(1, 22, 0)
+        ));
+    }
+
+    #[test]
+    fn sae_version() {
+        assert!(feature_supported(Feature::Sae, encode_version(1, 16, 0)));
+        assert!(!feature_supported(Feature::Sae, encode_version(1, 14, 6)));
+    }
+}
@@ -1,17 +1,198 @@
-use clap::{Args, Parser, Subcommand, ValueEnum};
+use std::io::Write;
+use std::time::Duration;
 
-use crate::bond::BondMode;
+use clap::{Args, CommandFactory, Parser, Subcommand, ValueEnum};
+use clap_complete::Shell;
+use ipnet::Ipv6Net;
+
+use crate::access_point::{WirelessBand, WirelessMode};
+use crate::bond::{BondMode, SlaveIdScheme};
+use crate::connection::{ActiveGuard, DnsOverTls, Ip4Method, KeyMgmt, MultiConnect, RetryPolicy};
+use crate::station::EapMethod;
+use crate::status::OutputFormat;
 
 #[derive(Parser, Debug)]
 #[command(name = "nutil")]
 #[command(author = "A. Gavin <a_gavin@icloud.com>")]
 #[command(about = "Utility for creating and managing bond devices using libnm", long_about = None)]
 pub struct App {
+    /// Required unless querying versions with "--nm-version"
     #[clap(subcommand)]
-    pub command: Command,
+    pub command: Option<Command>,
 
+    /// YAML config file used instead of connection arguments, or "-" to read from stdin
     #[arg(short, long)]
     pub config: Option<String>,
+
+    /// Override a config file field, e.g. "bond_mode=RoundRobin". Values
+    /// are parsed as YAML. May be specified multiple times
+    #[arg(long = "set", value_name = "FIELD=VALUE", requires = "config")]
+    pub overrides: Vec<String>,
+
+    /// Increase log verbosity (-v for debug, -vv for trace)
+    #[arg(short, long, action = clap::ArgAction::Count)]
+    pub verbose: u8,
+
+    /// Log level. Takes precedence over "--verbose" and "NUTIL_LOG"
+    /// environment variable. If not specified, default to info
+    #[arg(long, value_enum)]
+    pub log_level: Option<LogLevel>,
+
+    /// Seconds to wait for connection to activate during creation.
+    /// If not specified, wait indefinitely. During deletion, seconds to
+    /// wait for connection to deactivate before deleting (default 5)
+    #[arg(long)]
+    pub timeout: Option<u64>,
+
+    /// Times to retry connection activation after transient NetworkManager failures
+    #[arg(long, default_value_t = 0)]
+    pub retries: u32,
+
+    /// Milliseconds to wait before first activation retry, doubled for each retry after
+    #[arg(long, default_value_t = 500)]
+    pub retry_delay: u64,
+
+    /// Seconds to wait for connecting to NetworkManager before giving up,
+    /// e.g. when NetworkManager is slow to start or masked
+    #[arg(long, alias = "timeout-connect", default_value_t = 10)]
+    pub connect_timeout: u64,
+
+    /// Format used when printing connection status. With JSON, errors are also
+    /// printed to stderr as JSON
+    #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+    pub output: OutputFormat,
+
+    /// Print connection status after successful creation
+    #[arg(long)]
+    pub show_status: bool,
+
+    /// Validate and build connections during creation, but print
+    /// what would be done rather than modifying NetworkManager state
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// Return once connection activation starts during creation, rather than
+    /// waiting for it to finish, e.g. for slow-to-join wireless networks
+    #[arg(long)]
+    pub no_wait: bool,
+
+    /// Abort creation if an active connection conflicts with the desired
+    /// connection, rather than deactivating the conflicting connection
+    #[arg(long)]
+    pub no_preempt: bool,
+
+    /// Skip confirmation before destructive operations, e.g. deleting connections or
+    /// deactivating conflicting ones. Required when stdin is not a terminal
+    #[arg(short, long)]
+    pub yes: bool,
+
+    /// Replace any existing matching connection during creation by deleting it first,
+    /// rather than failing. Only supported for bonds and access points
+    #[arg(long)]
+    pub force: bool,
+
+    /// Target the connection with this UUID rather than one matching the given
+    /// options, e.g. after an interface rename (delete, status, activate, and deactivate only)
+    #[arg(long)]
+    pub uuid: Option<String>,
+
+    /// Include secrets, e.g. passwords, when exporting a connection.
+    /// Reading secrets may require running as root
+    #[arg(long)]
+    pub include_secrets: bool,
+
+    /// Print the NetworkManager settings a created connection would
+    /// have, secrets redacted, rather than creating it
+    #[arg(long)]
+    pub explain: bool,
+
+    /// Only run command if target connection is currently active
+    #[arg(long, conflicts_with = "only_if_inactive")]
+    pub only_if_active: bool,
+
+    /// Only run command if target connection is not currently active
+    #[arg(long)]
+    pub only_if_inactive: bool,
+
+    /// Print linked libnm and running NetworkManager versions, which determine
+    /// supported settings, e.g. DNS-over-TLS requires 1.34.0 or newer
+    #[arg(long)]
+    pub nm_version: bool,
+}
+
+#[derive(ValueEnum, PartialEq, Copy, Clone, Debug)]
+pub enum LogLevel {
+    Trace,
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+impl App {
+    // Subcommand required unless a query flag takes its place, like "--version".
+    // Checked after parsing, as clap cannot require one or the other
+    pub fn check_command(&self) -> Result<(), clap::Error> {
+        if self.command.is_none() && !self.nm_version {
+            return Err(App::command().error(
+                clap::error::ErrorKind::MissingSubcommand,
+                "A subcommand is required unless \"--nm-version\" is specified",
+            ));
+        }
+
+        Ok(())
+    }
+
+    // Log filter directive from command line options, if any specified
+    pub fn log_directive(&self) -> Option<&'static str> {
+        if let Some(level) = self.log_level {
+            return Some(get_log_level_str(level));
+        }
+
+        match self.verbose {
+            0 => None,
+            1 => Some(get_log_level_str(LogLevel::Debug)),
+            _ => Some(get_log_level_str(LogLevel::Trace)),
+        }
+    }
+
+    pub fn active_guard(&self) -> Option<ActiveGuard> {
+        if self.only_if_active {
+            Some(ActiveGuard::Active)
+        } else if self.only_if_inactive {
+            Some(ActiveGuard::Inactive)
+        } else {
+            None
+        }
+    }
+
+    pub fn retry_policy(&self) -> RetryPolicy {
+        RetryPolicy {
+            retries: self.retries,
+            delay: Duration::from_millis(self.retry_delay),
+        }
+    }
+
+    // Global options which have no meaning when applying a config directory, e.g.
+    // "--set" overrides of a single config file. Rejected rather than silently ignored
+    pub fn unsupported_apply_flags(&self) -> Vec<&'static str> {
+        let flags = [
+            ("--config", self.config.is_some()),
+            ("--set", !self.overrides.is_empty()),
+            ("--uuid", self.uuid.is_some()),
+            ("--include-secrets", self.include_secrets),
+            ("--explain", self.explain),
+            ("--show-status", self.show_status),
+            ("--only-if-active", self.only_if_active),
+            ("--only-if-inactive", self.only_if_inactive),
+        ];
+
+        flags
+            .into_iter()
+            .filter(|(_, set)| *set)
+            .map(|(flag, _)| flag)
+            .collect()
+    }
 }
 
 #[derive(Subcommand, Debug)]
@@ -34,22 +215,101 @@ pub enum Command {
     },
     /// Configure NetworkManager-managed bond connections
     Bond {
-        // TODO: Make bond_mode optional for CLI too (only optional in config atm)
         /// Bond creation requires a bond interface name and one or more
-        /// backing wired slave interface names. Bond mode defaults to
-        /// ActiveBackup when unspecified (only optional in config).
+        /// backing wired slave interface names ("--slaves"). Bond mode
+        /// ("--mode") defaults to ActiveBackup when unspecified.
         ///
-        /// Bond status requires only a bond interface name.
+        /// Bond status requires only a bond interface name, unless "--all"
+        /// is specified to list every bond.
         ///
         /// Bond deletion requires a bond interface name and an optional
         /// bond mode. Will delete a bond with a differing bond mode.
         /// If specified, optional backing slave interfaces will be deleted
+        ///
+        /// Bond slave move requires the slave interface name in place of
+        /// the bond interface name, as well as both "--from" and "--to"
+        /// bond interface names
+        ///
+        /// Bond slave addition and removal require a bond interface name
+        /// and one or more slave interface names ("--slaves")
         #[clap(value_enum)]
         action: Action,
 
         #[clap(flatten)]
         c_args: BondArgs,
     },
+    /// Manage wireless connections by SSID, whether created as a station or access point
+    Wifi {
+        #[clap(value_enum)]
+        action: WifiAction,
+
+        /// SSID of the wireless connection
+        ssid: String,
+
+        /// Wireless interface used by the connection. If not specified, match any interface
+        wireless_ifname: Option<String>,
+    },
+    /// List every bond, wired, and wireless connection with its active state
+    List,
+    /// Scan for and list visible wireless networks, strongest signal first
+    Scan {
+        /// Wireless interface to scan with
+        wireless_ifname: String,
+    },
+    /// Export every bond, access point, and station connection to a single
+    /// multi-document YAML config bundle. Secrets (e.g. passwords) are not exported
+    ExportAll {
+        /// Output file. If not specified, print to stdout
+        out: Option<String>,
+    },
+    /// Create every connection configured in a directory of YAML config files, bonds
+    /// first. Each file's connection type is inferred from its fields, e.g.
+    /// "bond_interface" or "ssid". Access point configs may need an "!AccessPoint" tag.
+    /// TOML config files are not supported and fail, other files are skipped
+    Apply {
+        /// Directory containing config files
+        dir: String,
+
+        /// Stop at the first config which fails, rather than continuing with the rest
+        #[arg(long)]
+        fail_fast: bool,
+
+        /// All or nothing. If any config fails, stop and roll back every connection
+        /// created or deleted so far, restoring deleted ones from snapshots
+        #[arg(long)]
+        atomic: bool,
+    },
+    /// Generate shell completion script and print to stdout
+    #[command(hide = true)]
+    Completions {
+        #[clap(value_enum)]
+        shell: Shell,
+    },
+}
+
+fn get_log_level_str(level: LogLevel) -> &'static str {
+    match level {
+        LogLevel::Trace => "trace",
+        LogLevel::Debug => "debug",
+        LogLevel::Info => "info",
+        LogLevel::Warn => "warn",
+        LogLevel::Error => "error",
+    }
+}
+
+// Generate completion script for the given shell, covering all subcommands and options
+pub fn write_completions(shell: Shell, buf: &mut impl Write) {
+    let mut cmd = App::command();
+    let name = cmd.get_name().to_string();
+
+    clap_complete::generate(shell, &mut cmd, name, buf);
+}
+
+#[derive(ValueEnum, Clone, Debug)]
+pub enum WifiAction {
+    /// Delete the matching station, access point, or ad-hoc connection. Fails
+    /// when more than one connection matches
+    Delete,
 }
 
 #[derive(ValueEnum, Clone, Debug)]
@@ -62,6 +322,26 @@ pub enum Action {
     /// are associated with the connection to be deleted are also deleted.
     Delete,
     Status,
+    /// Move a slave interface from one bond to another, preserving any
+    /// slave-specific settings. Only supported for bond connections
+    MoveSlave,
+    /// Add slave interfaces to an existing bond without recreating it.
+    /// Only supported for bond connections
+    AddSlave,
+    /// Remove and delete slave interfaces from an existing bond without
+    /// recreating it. Only supported for bond connections
+    RemoveSlave,
+    /// Change bond mode, MII monitoring interval, or primary slave of an existing
+    /// bond without recreating it. Only supported for bond connections
+    Modify,
+    /// Print an existing connection as YAML config usable with "--config".
+    /// Secrets are omitted unless "--include-secrets" specified
+    Export,
+    /// Bring up an existing connection without recreating it
+    Activate,
+    /// Bring down an existing connection without deleting it. Any bond
+    /// slave connections are deactivated along with their bond
+    Deactivate,
 }
 
 #[derive(Args, Debug)]
@@ -69,17 +349,166 @@ pub struct StationArgs {
     /// SSID used for station association
     pub ssid: Option<String>,
 
-    /// Wireless radio used to create station
+    /// Wireless radio used to create station. If not specified during creation,
+    /// use the available radio with the strongest signal for the SSID
     pub wireless_ifname: Option<String>,
 
-    /// Password for SSID (currently WPA-PSK only). If not specified, default to Open
+    /// Connection name, if different from the SSID. Lookups still match by interface and SSID
+    #[arg(long = "name")]
+    pub connection_id: Option<String>,
+
+    /// Password for SSID. If not specified, default to Open
     pub password: Option<String>,
 
     /// Static IPv4 address. If not specified, default to DHCP
     pub ip4_addr: Option<String>,
 
+    /// Additional static IPv4 address. May be specified multiple times
+    #[arg(long = "ip4-addr")]
+    pub extra_ip4_addrs: Vec<String>,
+
+    /// IPv4 method. If not specified, static with IPv4 addresses, otherwise DHCP
+    #[arg(long, value_enum)]
+    pub ip4_method: Option<Ip4Method>,
+
+    /// Lock station to the AP with this BSSID. If not specified,
+    /// station may roam between APs which share the same SSID
+    #[arg(long)]
+    pub lock_bssid: Option<String>,
+
+    /// Minimum signal strength (0-100) required to create the station
+    #[arg(long)]
+    pub min_signal: Option<u8>,
+
+    /// IPv4 gateway, e.g. "192.168.0.1". Requires a static IPv4 address
+    #[arg(long)]
+    pub gateway: Option<String>,
+
+    /// IPv4 DNS server. May be specified multiple times
+    #[arg(long)]
+    pub dns: Vec<String>,
+
+    /// Static IPv4 route, "dest/prefix via gateway [metric]", e.g.
+    /// "198.51.100.0/24 via 192.0.2.254". May be specified multiple times
+    #[arg(long = "route")]
+    pub routes: Vec<String>,
+
+    /// Seconds to wait for a DHCP lease before failing activation
+    #[arg(long, allow_negative_numbers = true)]
+    pub dhcp_timeout: Option<i32>,
+
+    /// DHCP client identifier, e.g. "mac", "duid", or "01:00:11:22:aa:bb:cc"
+    #[arg(long)]
+    pub dhcp_client_id: Option<String>,
+
+    /// Hostname sent to DHCP server, e.g. for predictable lease reservations
+    #[arg(long)]
+    pub dhcp_hostname: Option<String>,
+
+    /// Activate even if IPv4 configuration fails, e.g. no DHCP server responds
+    #[arg(long)]
+    pub ip4_may_fail: Option<bool>,
+
+    /// Static IPv6 address, including prefix length, e.g. "2001:db8::1/64".
+    /// If not specified, default to auto
+    #[arg(long)]
+    pub ip6_addr: Option<Ipv6Net>,
+
+    /// Allow connection profile to be active on multiple devices at once
+    #[arg(long, value_enum)]
+    pub multi_connect: Option<MultiConnect>,
+
+    /// Automatically activate connection when possible. If not specified, default to on
+    #[arg(long)]
+    pub autoconnect: Option<bool>,
+
+    /// Encrypt DNS with DNS-over-TLS. If not specified, use global DNS configuration
+    #[arg(long, value_enum)]
+    pub dns_over_tls: Option<DnsOverTls>,
+
+    /// Seed for stable generated addresses, e.g. "${CONNECTION}" or a literal string
+    #[arg(long)]
+    pub stable_id: Option<String>,
+
+    /// Firewalld zone to place the connection in, e.g. "public"
+    #[arg(long)]
+    pub zone: Option<String>,
+
+    /// MAC address used instead of device's permanent address. Either an
+    /// explicit MAC address or one of "random", "stable", or "permanent"
+    #[arg(long)]
+    pub cloned_mac: Option<String>,
+
+    /// Maximum transmission unit in bytes, between 68 and 9000
+    #[arg(long)]
+    pub mtu: Option<u32>,
+
+    /// Wireless power saving. Disable to reduce latency
+    #[arg(long)]
+    pub powersave: Option<bool>,
+
+    /// IPv4 route metric, lower is preferred. -1 lets NetworkManager choose
+    #[arg(long, allow_negative_numbers = true)]
+    pub route_metric: Option<i64>,
+
+    /// Key management used with password, e.g. "sae" for WPA3. If not specified, default to WPA-PSK
+    #[arg(long, value_enum)]
+    pub key_mgmt: Option<KeyMgmt>,
+
+    /// Minimum password length, between 8 and 63. Does not apply to 64 character hex keys
+    #[arg(long)]
+    pub psk_min_len: Option<usize>,
+
+    /// Enterprise (WPA-EAP) authentication method. Requires "--identity" and "--eap-password"
+    #[arg(long, value_enum)]
+    pub eap_method: Option<EapMethod>,
+
+    /// Enterprise network user name
+    #[arg(long)]
+    pub identity: Option<String>,
+
+    /// Path to CA certificate used to verify the enterprise authentication server
+    #[arg(long)]
+    pub ca_cert: Option<String>,
+
+    /// Enterprise network password
+    #[arg(long)]
+    pub eap_password: Option<String>,
+
+    /// Reject static IPv4 addresses overlapping subnets of active connections
+    /// rather than warning (create only)
+    #[arg(long)]
+    pub strict: bool,
+
     #[clap(skip)]
     pub config: Option<String>,
+
+    #[clap(skip)]
+    pub overrides: Vec<String>,
+
+    #[clap(skip)]
+    pub timeout: Option<u64>,
+
+    #[clap(skip)]
+    pub retry: RetryPolicy,
+
+    #[clap(skip)]
+    pub output: OutputFormat,
+
+    #[clap(skip)]
+    pub dry_run: bool,
+
+    #[clap(skip)]
+    pub no_wait: bool,
+
+    #[clap(skip)]
+    pub no_preempt: bool,
+
+    #[clap(skip)]
+    pub yes: bool,
+
+    #[clap(skip)]
+    pub uuid: Option<String>,
 }
 
 #[derive(Args, Debug)]
@@ -87,40 +516,549 @@ pub struct AccessPointArgs {
     /// SSID used for access point
     pub ssid: Option<String>,
 
-    /// Wireless radio used to create access point
+    /// Wireless radio used to create access point. Optional for status and deletion
     pub wireless_ifname: Option<String>,
 
+    /// Connection name, if different from the SSID. Lookups still match by interface and SSID
+    #[arg(long = "name")]
+    pub connection_id: Option<String>,
+
     /// Static IPv4 address. If not specified, default to DHCP
     /// When specified, include subnet mask, e.g. "192.168.0.10/24"
     pub ip4_addr: Option<String>,
 
-    /// Password for SSID (currently WPA-PSK only). If not specified, default to Open
+    /// Additional static IPv4 address. May be specified multiple times
+    #[arg(long = "ip4-addr")]
+    pub extra_ip4_addrs: Vec<String>,
+
+    /// IPv4 method. If not specified, static using IPv4 addresses or the default 192.0.2.1/24
+    #[arg(long, value_enum)]
+    pub ip4_method: Option<Ip4Method>,
+
+    /// Password for SSID. Required for creation unless "--open" specified
     pub password: Option<String>,
 
+    /// Create an open (passwordless) access point
+    #[arg(long)]
+    pub open: bool,
+
+    /// Wireless mode. Ad-hoc (IBSS) networks must be open. If not specified, default to AP
+    #[arg(long, value_enum)]
+    pub mode: Option<WirelessMode>,
+
+    /// Wireless band. If not specified, NetworkManager chooses automatically
+    #[arg(long, value_enum)]
+    pub band: Option<WirelessBand>,
+
+    /// Channel width in MHz (20, 40, 80, or 160). 80MHz and wider require band "a"
+    #[arg(long)]
+    pub channel_width: Option<u32>,
+
+    /// Maximum number of associated clients. Validated only, as
+    /// NetworkManager does not support limiting clients
+    #[arg(long)]
+    pub max_clients: Option<u32>,
+
+    /// Prevent associated clients from communicating with each other.
+    /// Requires NetworkManager 1.28 or newer
+    #[arg(long)]
+    pub client_isolation: Option<bool>,
+
+    /// Two-letter regulatory domain country code, e.g. "US". Only validated and
+    /// compared against the system regulatory domain, which must be set system-wide
+    #[arg(long)]
+    pub country: Option<String>,
+
+    /// IPv4 gateway, e.g. "192.168.0.1". Requires a static IPv4 address
+    #[arg(long)]
+    pub gateway: Option<String>,
+
+    /// IPv4 DNS server. May be specified multiple times
+    #[arg(long)]
+    pub dns: Vec<String>,
+
+    /// Static IPv4 route, "dest/prefix via gateway [metric]", e.g.
+    /// "198.51.100.0/24 via 192.0.2.254". May be specified multiple times
+    #[arg(long = "route")]
+    pub routes: Vec<String>,
+
+    /// Static IPv6 address, including prefix length, e.g. "2001:db8::1/64".
+    /// If not specified, default to auto
+    #[arg(long)]
+    pub ip6_addr: Option<Ipv6Net>,
+
+    /// Allow connection profile to be active on multiple devices at once
+    #[arg(long, value_enum)]
+    pub multi_connect: Option<MultiConnect>,
+
+    /// Automatically activate connection when possible. If not specified, default to off
+    #[arg(long)]
+    pub autoconnect: Option<bool>,
+
+    /// Encrypt DNS with DNS-over-TLS. If not specified, use global DNS configuration
+    #[arg(long, value_enum)]
+    pub dns_over_tls: Option<DnsOverTls>,
+
+    /// Seed for stable generated addresses, e.g. "${CONNECTION}" or a literal string
+    #[arg(long)]
+    pub stable_id: Option<String>,
+
+    /// Firewalld zone to place the connection in, e.g. "public"
+    #[arg(long)]
+    pub zone: Option<String>,
+
+    /// MAC address used instead of device's permanent address. Either an
+    /// explicit MAC address or one of "random", "stable", or "permanent"
+    #[arg(long)]
+    pub cloned_mac: Option<String>,
+
+    /// Maximum transmission unit in bytes, between 68 and 9000
+    #[arg(long)]
+    pub mtu: Option<u32>,
+
+    /// Wireless power saving. Disable to reduce latency
+    #[arg(long)]
+    pub powersave: Option<bool>,
+
+    /// IPv4 route metric, lower is preferred. -1 lets NetworkManager choose
+    #[arg(long, allow_negative_numbers = true)]
+    pub route_metric: Option<i64>,
+
+    /// Key management used with password, e.g. "sae" for WPA3. If not specified, default to WPA-PSK
+    #[arg(long, value_enum)]
+    pub key_mgmt: Option<KeyMgmt>,
+
+    /// Minimum password length, between 8 and 63. Does not apply to 64 character hex keys
+    #[arg(long)]
+    pub psk_min_len: Option<usize>,
+
+    /// Reject static IPv4 addresses overlapping subnets of active connections
+    /// rather than warning (create only)
+    #[arg(long)]
+    pub strict: bool,
+
     #[clap(skip)]
     pub config: Option<String>,
+
+    #[clap(skip)]
+    pub overrides: Vec<String>,
+
+    #[clap(skip)]
+    pub timeout: Option<u64>,
+
+    #[clap(skip)]
+    pub retry: RetryPolicy,
+
+    #[clap(skip)]
+    pub output: OutputFormat,
+
+    #[clap(skip)]
+    pub dry_run: bool,
+
+    #[clap(skip)]
+    pub no_wait: bool,
+
+    #[clap(skip)]
+    pub no_preempt: bool,
+
+    #[clap(skip)]
+    pub yes: bool,
+
+    #[clap(skip)]
+    pub uuid: Option<String>,
+
+    #[clap(skip)]
+    pub force: bool,
 }
 
 #[derive(Args, Debug)]
 pub struct BondArgs {
-    /// Bond connection and backing device name (must match)
+    /// Bond backing device name, also used as connection name unless "--name" specified
     #[clap(name = "bond_interface")]
     pub ifname: Option<String>,
 
+    /// Connection name, if different from the bond interface name. Lookups still match by interface
+    #[arg(long = "name")]
+    pub connection_id: Option<String>,
+
     /// Bond mode of operation (defaults to ActiveBackup)
-    #[clap(value_enum)]
+    #[arg(long = "mode", value_enum)]
     pub bond_mode: Option<BondMode>,
 
-    /// Static IPv4 address. Use "DHCP" if no static IPv4 address desired.
-    /// When specified, nclude subnet mask, e.g. "192.168.0.10/24"
-    // TODO: Make this truly optional (after slave_ifnames,
-    //       won't compile that way tho as slave_ifnames is variable length)
-    pub ip4_addr: Option<String>,
+    /// MII link monitoring interval in milliseconds (defaults to 100)
+    #[arg(long)]
+    pub miimon: Option<u32>,
+
+    /// Milliseconds to wait before enabling a slave after link up. Multiple of miimon
+    #[arg(long)]
+    pub updelay: Option<u32>,
+
+    /// Milliseconds to wait before disabling a slave after link down. Multiple of miimon
+    #[arg(long)]
+    pub downdelay: Option<u32>,
+
+    /// Deliver frames received on inactive slaves (ActiveBackup mode only)
+    #[arg(long)]
+    pub all_slaves_active: Option<bool>,
+
+    /// Preferred slave interface, active whenever available (ActiveBackup mode only)
+    #[arg(long)]
+    pub primary: Option<String>,
+
+    /// Slave selection hash policy, e.g. "layer2+3" or "layer3+4" (XOR and 802.3ad modes only)
+    #[arg(long)]
+    pub xmit_hash_policy: Option<String>,
+
+    /// Naming scheme for slave connection ids. If not specified, use slave interface name
+    #[arg(long, value_enum)]
+    pub slave_id_scheme: Option<SlaveIdScheme>,
+
+    /// LACP actor system priority, 1-65535 (802.3ad mode only)
+    #[arg(long)]
+    pub ad_actor_sys_prio: Option<u16>,
+
+    /// LACP actor system MAC address, e.g. "00:11:22:aa:bb:cc" (802.3ad mode only)
+    #[arg(long)]
+    pub ad_actor_system: Option<String>,
 
-    /// Bond backing wired device interface names (required for creation and deletion)
-    #[clap(name = "slave_interfaces")]
+    /// LACP user port key, 0-1023 (802.3ad mode only)
+    #[arg(long)]
+    pub ad_user_port_key: Option<u16>,
+
+    /// Rate partner is asked to send LACPDUs, "slow" or "fast" (802.3ad mode only)
+    #[arg(long)]
+    pub lacp_rate: Option<String>,
+
+    /// Static IPv4 address, including subnet mask, e.g. "192.168.0.10/24".
+    /// May be specified multiple times. If not specified, default to DHCP
+    #[arg(long = "ip4-addr")]
+    pub ip4_addrs: Vec<String>,
+
+    /// IPv4 method. If not specified, static with IPv4 addresses, otherwise DHCP
+    #[arg(long, value_enum)]
+    pub ip4_method: Option<Ip4Method>,
+
+    /// Bond backing wired device interface names, comma-separated or specified
+    /// multiple times (required for creation, optional for deletion)
+    #[arg(long = "slaves", value_delimiter = ',')]
     pub slave_ifnames: Vec<String>,
 
+    /// IPv4 gateway, e.g. "192.168.0.1". Requires a static IPv4 address
+    #[arg(long)]
+    pub gateway: Option<String>,
+
+    /// IPv4 DNS server. May be specified multiple times
+    #[arg(long)]
+    pub dns: Vec<String>,
+
+    /// Static IPv4 route, "dest/prefix via gateway [metric]", e.g.
+    /// "198.51.100.0/24 via 192.0.2.254". May be specified multiple times
+    #[arg(long = "route")]
+    pub routes: Vec<String>,
+
+    /// Seconds to wait for a DHCP lease before failing activation
+    #[arg(long, allow_negative_numbers = true)]
+    pub dhcp_timeout: Option<i32>,
+
+    /// DHCP client identifier, e.g. "mac", "duid", or "01:00:11:22:aa:bb:cc"
+    #[arg(long)]
+    pub dhcp_client_id: Option<String>,
+
+    /// Hostname sent to DHCP server, e.g. for predictable lease reservations
+    #[arg(long)]
+    pub dhcp_hostname: Option<String>,
+
+    /// Activate even if IPv4 configuration fails, e.g. no DHCP server responds
+    #[arg(long)]
+    pub ip4_may_fail: Option<bool>,
+
+    /// Static IPv6 address, including prefix length, e.g. "2001:db8::1/64".
+    /// If not specified, default to auto
+    #[arg(long)]
+    pub ip6_addr: Option<Ipv6Net>,
+
+    /// Allow connection profile to be active on multiple devices at once
+    #[arg(long, value_enum)]
+    pub multi_connect: Option<MultiConnect>,
+
+    /// Automatically activate connection when possible. If not specified, default to on
+    #[arg(long)]
+    pub autoconnect: Option<bool>,
+
+    /// Encrypt DNS with DNS-over-TLS. If not specified, use global DNS configuration
+    #[arg(long, value_enum)]
+    pub dns_over_tls: Option<DnsOverTls>,
+
+    /// Seed for stable generated addresses, e.g. "${CONNECTION}" or a literal string
+    #[arg(long)]
+    pub stable_id: Option<String>,
+
+    /// Firewalld zone to place the connection in, e.g. "public"
+    #[arg(long)]
+    pub zone: Option<String>,
+
+    /// MAC address used by slave devices instead of their permanent address. Either
+    /// an explicit MAC address or one of "random", "stable", or "permanent"
+    #[arg(long)]
+    pub cloned_mac: Option<String>,
+
+    /// Maximum transmission unit in bytes, between 68 and 9000
+    #[arg(long)]
+    pub mtu: Option<u32>,
+
+    /// IPv4 route metric, lower is preferred. -1 lets NetworkManager choose
+    #[arg(long, allow_negative_numbers = true)]
+    pub route_metric: Option<i64>,
+
+    /// List every bond with a one-line summary (status only)
+    #[arg(long)]
+    pub all: bool,
+
+    /// Keep bond slave connections as standalone wired connections (deletion only)
+    #[arg(long, conflicts_with = "slave_ifnames")]
+    pub keep_slaves: bool,
+
+    /// Bond interface the slave currently belongs to (slave move only)
+    #[arg(long = "from")]
+    pub from_bond_ifname: Option<String>,
+
+    /// Bond interface to move the slave to (slave move only)
+    #[arg(long = "to")]
+    pub to_bond_ifname: Option<String>,
+
+    /// Reject ineffective bond option combinations and static IPv4 addresses overlapping
+    /// subnets of active connections rather than warning (create only)
+    #[arg(long)]
+    pub strict: bool,
+
     #[clap(skip)]
     pub config: Option<String>,
+
+    #[clap(skip)]
+    pub overrides: Vec<String>,
+
+    #[clap(skip)]
+    pub timeout: Option<u64>,
+
+    #[clap(skip)]
+    pub retry: RetryPolicy,
+
+    #[clap(skip)]
+    pub output: OutputFormat,
+
+    #[clap(skip)]
+    pub dry_run: bool,
+
+    #[clap(skip)]
+    pub no_wait: bool,
+
+    #[clap(skip)]
+    pub no_preempt: bool,
+
+    #[clap(skip)]
+    pub yes: bool,
+
+    #[clap(skip)]
+    pub uuid: Option<String>,
+
+    #[clap(skip)]
+    pub force: bool,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn app_definition() {
+        App::command().debug_assert();
+    }
+
+    #[test]
+    fn completions() {
+        for shell in Shell::value_variants() {
+            let mut buf: Vec<u8> = vec![];
+            write_completions(*shell, &mut buf);
+
+            let script = String::from_utf8(buf).unwrap();
+            assert!(script.contains("access-point"), "{shell}");
+        }
+
+        // Action values completed where the shell supports value completion
+        for shell in [Shell::Bash, Shell::Zsh] {
+            let mut buf: Vec<u8> = vec![];
+            write_completions(shell, &mut buf);
+
+            let script = String::from_utf8(buf).unwrap();
+            assert!(script.contains("move-slave"), "{shell}");
+        }
+    }
+
+    #[test]
+    fn log_directive() {
+        let args = App::parse_from(["nutil", "bond", "status", "bond0"]);
+        assert_eq!(args.log_directive(), None);
+
+        let args = App::parse_from(["nutil", "-v", "bond", "status", "bond0"]);
+        assert_eq!(args.log_directive(), Some("debug"));
+
+        let args = App::parse_from(["nutil", "-vvv", "bond", "status", "bond0"]);
+        assert_eq!(args.log_directive(), Some("trace"));
+
+        // Explicit log level takes precedence over verbosity
+        let args = App::parse_from(["nutil", "-v", "--log-level", "warn", "bond", "status"]);
+        assert_eq!(args.log_directive(), Some("warn"));
+    }
+
+    #[test]
+    fn force() {
+        let args = App::parse_from(["nutil", "bond", "create", "bond0", "--slaves", "eth0"]);
+        assert!(!args.force);
+
+        let args = App::parse_from([
+            "nutil", "--force", "bond", "create", "bond0", "--slaves", "eth0",
+        ]);
+        assert!(args.force);
+    }
+
+    #[test]
+    fn bond_named_args() {
+        // Bond mode optional, defaults applied when converting to options
+        let args = App::parse_from([
+            "nutil",
+            "bond",
+            "create",
+            "bond0",
+            "--slaves",
+            "eth0,eth1",
+            "--ip4-addr",
+            "192.0.2.1/24",
+        ]);
+        let Some(Command::Bond { c_args, .. }) = args.command else {
+            panic!("Expected bond command");
+        };
+        assert_eq!(c_args.bond_mode, None);
+        assert_eq!(c_args.slave_ifnames, vec!["eth0", "eth1"]);
+        assert_eq!(c_args.ip4_addrs, vec!["192.0.2.1/24"]);
+
+        let args = App::parse_from([
+            "nutil",
+            "bond",
+            "create",
+            "bond0",
+            "--mode",
+            "round-robin",
+            "--slaves",
+            "eth0",
+            "--slaves",
+            "eth1",
+        ]);
+        let Some(Command::Bond { c_args, .. }) = args.command else {
+            panic!("Expected bond command");
+        };
+        assert_eq!(c_args.bond_mode, Some(BondMode::RoundRobin));
+        assert_eq!(c_args.slave_ifnames, vec!["eth0", "eth1"]);
+    }
+
+    #[test]
+    fn bond_slave_actions() {
+        let parse = |action: &str| {
+            let args = App::parse_from(["nutil", "bond", action, "bond0", "--slaves", "eth2"]);
+            match args.command.unwrap() {
+                Command::Bond { action, c_args } => (action, c_args),
+                _ => panic!("Expected bond command"),
+            }
+        };
+
+        let (action, c_args) = parse("add-slave");
+        assert!(matches!(action, Action::AddSlave));
+        assert_eq!(c_args.ifname.as_deref(), Some("bond0"));
+        assert_eq!(c_args.slave_ifnames, vec!["eth2"]);
+
+        let (action, _) = parse("remove-slave");
+        assert!(matches!(action, Action::RemoveSlave));
+    }
+
+    #[test]
+    fn activate_actions() {
+        let parse = |action: &str| {
+            let args = App::parse_from(["nutil", "access-point", action, "test_ssid", "wlan0"]);
+            match args.command.unwrap() {
+                Command::AccessPoint { action, c_args } => (action, c_args),
+                _ => panic!("Expected access point command"),
+            }
+        };
+
+        let (action, c_args) = parse("activate");
+        assert!(matches!(action, Action::Activate));
+        assert_eq!(c_args.ssid.as_deref(), Some("test_ssid"));
+
+        let (action, _) = parse("deactivate");
+        assert!(matches!(action, Action::Deactivate));
+    }
+
+    #[test]
+    fn wifi_delete() {
+        let args = App::parse_from(["nutil", "wifi", "delete", "test_ssid"]);
+        match args.command.unwrap() {
+            Command::Wifi {
+                action,
+                ssid,
+                wireless_ifname,
+            } => {
+                assert!(matches!(action, WifiAction::Delete));
+                assert_eq!(ssid, "test_ssid");
+                assert!(wireless_ifname.is_none());
+            }
+            _ => panic!("Expected wifi command"),
+        }
+    }
+
+    #[test]
+    fn apply_atomic() {
+        let args = App::parse_from(["nutil", "apply", "configs", "--atomic"]);
+        assert!(args.unsupported_apply_flags().is_empty());
+        match args.command.unwrap() {
+            Command::Apply {
+                dir,
+                fail_fast,
+                atomic,
+            } => {
+                assert_eq!(dir, "configs");
+                assert!(!fail_fast);
+                assert!(atomic);
+            }
+            _ => panic!("Expected apply command"),
+        }
+    }
+
+    #[test]
+    fn apply_unsupported_flags() {
+        let args = App::parse_from([
+            "nutil",
+            "--explain",
+            "--only-if-active",
+            "--timeout",
+            "30",
+            "apply",
+            "configs",
+        ]);
+        assert_eq!(
+            args.unsupported_apply_flags(),
+            vec!["--explain", "--only-if-active"]
+        );
+    }
+
+    #[test]
+    fn nm_version() {
+        let args = App::parse_from(["nutil", "--output", "json", "--nm-version"]);
+        assert!(args.nm_version);
+        assert!(args.command.is_none());
+        assert!(args.check_command().is_ok());
+        assert_eq!(args.output, OutputFormat::Json);
+
+        // Otherwise, subcommand still required
+        let args = App::parse_from(["nutil", "--output", "json"]);
+        assert!(args.check_command().is_err());
+    }
 }
@@ -1,11 +1,94 @@
 use std::cell::RefCell;
+use std::future::{poll_fn, Future};
+use std::net::Ipv4Addr;
+use std::pin::{pin, Pin};
 use std::rc::Rc;
+use std::str::FromStr;
+use std::task::Poll;
+use std::time::Duration;
 
-use anyhow::{anyhow, Result};
+use anyhow::{anyhow, Context, Result};
+use clap::ValueEnum;
 use futures_channel::oneshot;
+use glib::prelude::ObjectExt;
 use glib::translate::FromGlib;
+use ipnet::{Ipv4Net, Ipv6Net};
 use nm::*;
-use tracing::{debug, error, instrument, warn};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, error, info, instrument, warn};
+
+use crate::capability::{check_feature, feature_supported, require_feature, Feature};
+use crate::util::{confirm_action, validate_psk, validate_psk_min_len, validate_ssid, MIN_PSK_LEN};
+
+/// Route metric which lets NetworkManager choose based on device type
+pub const AUTO_ROUTE_METRIC: i64 = -1;
+
+/// Whether a single connection profile may be active on multiple devices at once.
+/// Values match NetworkManager's `NMConnectionMultiConnect`
+#[derive(ValueEnum, Serialize, Deserialize, PartialEq, Copy, Clone, Debug)]
+pub enum MultiConnect {
+    Default = 0,
+    Single = 1,
+    Multiple = 3,
+}
+
+/// DNS-over-TLS mode used when resolving names over the connection.
+/// Values match NetworkManager's `NMSettingConnectionDnsOverTls`
+#[derive(ValueEnum, Serialize, Deserialize, PartialEq, Copy, Clone, Debug)]
+pub enum DnsOverTls {
+    No = 0,
+    Opportunistic = 1,
+    Yes = 2,
+}
+
+/// Values of NetworkManager's `NMSettingWirelessPowersave` which explicitly
+/// disable or enable wireless power saving. Others defer to global configuration
+const WIRELESS_POWERSAVE_DISABLE: u32 = 2;
+const WIRELESS_POWERSAVE_ENABLE: u32 = 3;
+
+/// Only run command when target connection is (or isn't) active
+#[derive(PartialEq, Copy, Clone, Debug)]
+pub enum ActiveGuard {
+    Active,
+    Inactive,
+}
+
+/// Wireless security key management. Only applicable when a password is specified
+#[derive(ValueEnum, Serialize, Deserialize, Default, PartialEq, Copy, Clone, Debug)]
+pub enum KeyMgmt {
+    /// WPA2 personal
+    #[default]
+    WpaPsk,
+    /// WPA3 personal
+    Sae,
+}
+
+/// IPv4 addressing method. If not specified, static when IPv4 addresses are specified
+#[derive(ValueEnum, Serialize, Deserialize, PartialEq, Copy, Clone, Debug)]
+pub enum Ip4Method {
+    /// DHCP
+    Auto,
+    /// Static addresses only
+    Manual,
+    /// IPv4 link-local (169.254.0.0/16) addresses
+    LinkLocal,
+    /// Share connection with other devices, i.e. NAT and DHCP server
+    Shared,
+    /// No IPv4 configuration
+    Disabled,
+}
+
+/// Device considered when choosing where to activate a connection
+/// which is not bound to a specific interface
+#[derive(PartialEq, Clone, Debug)]
+pub struct DeviceCandidate {
+    pub ifname: String,
+
+    pub state: DeviceState,
+
+    /// Signal strength (0-100) of target network, if visible. Only for wireless devices
+    pub signal: Option<u8>,
+}
 
 // Create a wired SimpleConnection for use in activating, deactivating, finding, etc
 // If bond_ifname is Some, create the wired connection as a bond slave with bond_ifname as master.
@@ -17,6 +100,16 @@ use tracing::{debug, error, instrument, warn};
 pub fn create_wired_connection(
     wired_ifname: &str,
     bond_ifname: Option<&str>,
+) -> Result<SimpleConnection> {
+    create_wired_connection_with_id(wired_ifname, bond_ifname, wired_ifname)
+}
+
+// Same as create_wired_connection(), but with an explicit connection id rather than
+// the wired interface name. Connection ids are not used when matching connections
+pub fn create_wired_connection_with_id(
+    wired_ifname: &str,
+    bond_ifname: Option<&str>,
+    conn_id: &str,
 ) -> Result<SimpleConnection> {
     let connection = SimpleConnection::new();
 
@@ -24,7 +117,7 @@ pub fn create_wired_connection(
 
     // General settings
     s_connection.set_type(Some(SETTING_WIRED_SETTING_NAME));
-    s_connection.set_id(Some(wired_ifname));
+    s_connection.set_id(Some(conn_id));
     s_connection.set_interface_name(Some(wired_ifname));
 
     // Master is bond interface name, slave type is type of master interface (i.e. bond)
@@ -38,6 +131,801 @@ pub fn create_wired_connection(
     Ok(connection)
 }
 
+// Add wired settings which override device defaults, e.g. using a cloned MAC address
+// rather than the device's permanent address. Not added when nothing is overridden
+pub fn add_wired_setting(conn: &SimpleConnection, cloned_mac: Option<&str>, mtu: Option<u32>) {
+    if cloned_mac.is_none() && mtu.is_none() {
+        return;
+    }
+
+    let s_wired = SettingWired::new();
+    if let Some(cloned_mac) = cloned_mac {
+        s_wired.set_cloned_mac_address(Some(cloned_mac));
+    }
+    if let Some(mtu) = mtu {
+        s_wired.set_mtu(mtu);
+    }
+    conn.add_setting(s_wired);
+}
+
+// Configured MTU, if any. NetworkManager uses zero for the device's default
+pub fn get_mtu(mtu: u32) -> Option<u32> {
+    match mtu {
+        0 => None,
+        mtu => Some(mtu),
+    }
+}
+
+pub fn get_powersave_value(powersave: bool) -> u32 {
+    match powersave {
+        true => WIRELESS_POWERSAVE_ENABLE,
+        false => WIRELESS_POWERSAVE_DISABLE,
+    }
+}
+
+// Explicitly configured wireless power saving, if any
+pub fn get_powersave(value: u32) -> Option<bool> {
+    match value {
+        WIRELESS_POWERSAVE_ENABLE => Some(true),
+        WIRELESS_POWERSAVE_DISABLE => Some(false),
+        _ => None,
+    }
+}
+
+// Kernel route metrics are unsigned 32-bit values, with -1 letting NetworkManager choose
+pub fn validate_route_metric(metric: i64) -> Result<()> {
+    if !(AUTO_ROUTE_METRIC..=u32::MAX as i64).contains(&metric) {
+        return Err(anyhow!(
+            "Invalid route metric \"{}\", expected -1 (automatic) or between 0 and {}",
+            metric,
+            u32::MAX
+        ));
+    }
+    Ok(())
+}
+
+pub fn apply_route_metric(s_ip4: &SettingIP4Config, metric: Option<i64>) -> Result<()> {
+    if let Some(metric) = metric {
+        validate_route_metric(metric)?;
+        s_ip4.set_route_metric(metric);
+    }
+    Ok(())
+}
+
+// Route metric, if not chosen automatically by NetworkManager
+pub fn get_route_metric(metric: i64) -> Option<i64> {
+    match metric {
+        AUTO_ROUTE_METRIC => None,
+        metric => Some(metric),
+    }
+}
+
+// Create IPv6 settings for use in any connection type. If ip6_addr is Some,
+// configure it as a static address. Otherwise, use automatic configuration
+pub fn create_ip6_setting(ip6_addr: Option<&Ipv6Net>) -> Result<SettingIP6Config> {
+    let s_ip6 = SettingIP6Config::new();
+
+    match ip6_addr {
+        Some(ip6_net) => {
+            let ip6_addr = IPAddress::new(
+                libc::AF_INET6,
+                ip6_net.addr().to_string().as_str(),
+                ip6_net.prefix_len() as u32,
+            )?;
+
+            s_ip6.add_address(&ip6_addr);
+            s_ip6.set_method(Some(SETTING_IP6_CONFIG_METHOD_MANUAL));
+        }
+        None => {
+            s_ip6.set_method(Some(SETTING_IP6_CONFIG_METHOD_AUTO));
+        }
+    }
+
+    Ok(s_ip6)
+}
+
+// Create wireless security settings for the given password and key management.
+// No security settings (i.e. an open network) when no password specified
+pub fn create_wireless_security_setting(
+    password: Option<&str>,
+    key_mgmt: Option<KeyMgmt>,
+    psk_min_len: Option<usize>,
+) -> Result<Option<SettingWirelessSecurity>> {
+    validate_key_mgmt(password, key_mgmt)?;
+    if key_mgmt == Some(KeyMgmt::Sae) {
+        require_feature(Feature::Sae)?;
+    }
+
+    let password = match password {
+        Some(p) => p,
+        None => return Ok(None),
+    };
+
+    if let Some(min_len) = psk_min_len {
+        validate_psk_min_len(min_len)?;
+    }
+    validate_psk(password, psk_min_len.unwrap_or(MIN_PSK_LEN))?;
+
+    let s_wireless_security = SettingWirelessSecurity::new();
+    s_wireless_security.set_key_mgmt(Some(get_key_mgmt_str(key_mgmt.unwrap_or_default())));
+    s_wireless_security.set_psk(Some(password));
+
+    Ok(Some(s_wireless_security))
+}
+
+// Key management only makes sense for password-protected networks, so
+// require a password whenever key management is explicitly requested
+pub fn validate_key_mgmt(password: Option<&str>, key_mgmt: Option<KeyMgmt>) -> Result<()> {
+    match (password, key_mgmt) {
+        (None, Some(key_mgmt)) => Err(anyhow!(
+            "Key management \"{}\" requires a password",
+            get_key_mgmt_str(key_mgmt)
+        )),
+        _ => Ok(()),
+    }
+}
+
+// Inverse of get_key_mgmt_str(). None for key management nutil does not configure
+pub fn get_key_mgmt(key_mgmt: &str) -> Option<KeyMgmt> {
+    KeyMgmt::value_variants()
+        .iter()
+        .find(|k| get_key_mgmt_str(**k) == key_mgmt)
+        .copied()
+}
+
+pub fn get_key_mgmt_str(key_mgmt: KeyMgmt) -> &'static str {
+    match key_mgmt {
+        KeyMgmt::WpaPsk => "wpa-psk",
+        KeyMgmt::Sae => "sae",
+    }
+}
+
+// Stable ID may be any literal string and/or NetworkManager substitution
+// tokens (e.g. "${CONNECTION}", "${DEVICE}", "${BOOT}"), but must not be empty
+pub fn validate_stable_id(stable_id: &str) -> Result<()> {
+    if stable_id.trim().is_empty() {
+        return Err(anyhow!("Stable ID must not be empty"));
+    }
+
+    Ok(())
+}
+
+// Zones are site-defined in firewalld, so only reject empty zones
+pub fn validate_zone(zone: &str) -> Result<()> {
+    if zone.trim().is_empty() {
+        return Err(anyhow!("Firewall zone must not be empty"));
+    }
+
+    Ok(())
+}
+
+// Firewall zone configured for a connection, if any. NetworkManager
+// stores an unset zone as empty, meaning firewalld default zone
+pub fn get_zone(conn: &impl ConnectionExt) -> Option<String> {
+    conn.setting_connection()?
+        .zone()
+        .filter(|z| !z.is_empty())
+        .map(|z| z.to_string())
+}
+
+// Set DNS-over-TLS mode in connection settings. Older libnm versions
+// lack the property entirely, so warn and ignore rather than fail
+pub fn set_dns_over_tls(s_connection: &SettingConnection, dns_over_tls: DnsOverTls) {
+    if !check_feature(Feature::DnsOverTls, get_dns_over_tls_str(dns_over_tls)) {
+        return;
+    }
+
+    s_connection.set_property("dns-over-tls", dns_over_tls as i32);
+}
+
+// Get DNS-over-TLS mode for use in status commands. None if
+// unsupported or left as NetworkManager default
+pub fn get_dns_over_tls_status(conn: &impl ConnectionExt) -> Option<String> {
+    get_dns_over_tls(conn).map(|mode| get_dns_over_tls_str(mode).to_string())
+}
+
+// DNS-over-TLS mode explicitly configured for a connection, if any
+pub fn get_dns_over_tls(conn: &impl ConnectionExt) -> Option<DnsOverTls> {
+    if !feature_supported(Feature::DnsOverTls, utils_version()) {
+        return None;
+    }
+
+    match conn.setting_connection()?.property::<i32>("dns-over-tls") {
+        0 => Some(DnsOverTls::No),
+        1 => Some(DnsOverTls::Opportunistic),
+        2 => Some(DnsOverTls::Yes),
+        _ => None,
+    }
+}
+
+pub fn get_dns_over_tls_str(dns_over_tls: DnsOverTls) -> &'static str {
+    match dns_over_tls {
+        DnsOverTls::No => "no",
+        DnsOverTls::Opportunistic => "opportunistic",
+        DnsOverTls::Yes => "yes",
+    }
+}
+
+// Add static IPv4 addresses, each including prefix length, to IPv4 settings
+pub fn add_ip4_addrs(s_ip4: &SettingIP4Config, addrs: &[String]) -> Result<()> {
+    for addr in addrs.iter() {
+        let ip4_net = Ipv4Net::from_str(addr)?;
+
+        let ip4_addr = IPAddress::new(
+            libc::AF_INET,
+            ip4_net.addr().to_string().as_str(),
+            ip4_net.prefix_len() as u32,
+        )?;
+
+        s_ip4.add_address(&ip4_addr);
+    }
+
+    Ok(())
+}
+
+/// Static IPv4 route to a network beyond directly-connected subnets
+#[derive(PartialEq, Clone, Debug)]
+pub struct Ip4Route {
+    pub dest: Ipv4Net,
+    pub gateway: Ipv4Addr,
+    /// When unspecified, use connection's route metric
+    pub metric: Option<u32>,
+}
+
+// Parse static IPv4 route in "dest/prefix via gateway [metric]" form,
+// e.g. "198.51.100.0/24 via 192.0.2.254 100"
+pub fn parse_ip4_route(route: &str) -> Result<Ip4Route> {
+    let invalid = |reason: &str| anyhow!("Invalid IPv4 route \"{}\": {}", route, reason);
+
+    let parts: Vec<&str> = route.split_whitespace().collect();
+    let (dest, gateway, metric) = match parts.as_slice() {
+        [dest, "via", gateway] => (dest, gateway, None),
+        [dest, "via", gateway, metric] => (dest, gateway, Some(metric)),
+        _ => return Err(invalid("expected \"dest/prefix via gateway [metric]\"")),
+    };
+
+    let dest = Ipv4Net::from_str(dest)
+        .map_err(|_| invalid(&format!("invalid destination \"{}\"", dest)))?;
+    if dest.addr() != dest.network() {
+        return Err(invalid(&format!(
+            "destination has host bits set, did you mean \"{}\"?",
+            dest.trunc()
+        )));
+    }
+
+    let gateway = Ipv4Addr::from_str(gateway)
+        .map_err(|_| invalid(&format!("invalid gateway \"{}\"", gateway)))?;
+
+    let metric = match metric {
+        Some(m) => Some(
+            m.parse::<u32>()
+                .map_err(|_| invalid(&format!("invalid metric \"{}\"", m)))?,
+        ),
+        None => None,
+    };
+
+    Ok(Ip4Route {
+        dest,
+        gateway,
+        metric,
+    })
+}
+
+pub fn add_ip4_routes(s_ip4: &SettingIP4Config, routes: &[String]) -> Result<()> {
+    for route in routes.iter() {
+        let route = parse_ip4_route(route)?;
+
+        let ip4_route = IPRoute::new(
+            libc::AF_INET,
+            route.dest.network().to_string().as_str(),
+            route.dest.prefix_len() as u32,
+            Some(route.gateway.to_string().as_str()),
+            route.metric.map_or(AUTO_ROUTE_METRIC, i64::from),
+        )?;
+
+        s_ip4.add_route(&ip4_route);
+    }
+
+    Ok(())
+}
+
+// Static IPv4 route in the form accepted by parse_ip4_route(). Metric
+// omitted when the connection's route metric is used
+pub fn get_ip4_route_str(route: &IPRoute) -> String {
+    let mut route_str = format!(
+        "{}/{} via {}",
+        route.dest().unwrap_or_default(),
+        route.prefix(),
+        route.next_hop().unwrap_or_default()
+    );
+
+    if let Some(metric) = get_route_metric(route.metric()) {
+        route_str.push_str(&format!(" {metric}"));
+    }
+    route_str
+}
+
+// Apply IPv4 gateway and DNS servers to IPv4 settings. A gateway
+// is only meaningful alongside a static IPv4 address
+pub fn apply_ip4_gateway_dns(
+    s_ip4: &SettingIP4Config,
+    gateway: Option<&str>,
+    dns: &[String],
+    static_addr: bool,
+) -> Result<()> {
+    validate_ip4_gateway_dns(gateway, dns, static_addr)?;
+
+    if let Some(gateway) = gateway {
+        s_ip4.set_gateway(Some(gateway));
+    }
+
+    for server in dns.iter() {
+        s_ip4.add_dns(server);
+    }
+
+    Ok(())
+}
+
+// Set IPv4 method and any static addresses. Without an explicit method, static
+// addresses imply manual configuration, otherwise the given default method is used
+pub fn apply_ip4_method(
+    s_ip4: &SettingIP4Config,
+    method: Option<Ip4Method>,
+    addrs: &[String],
+    default: Ip4Method,
+) -> Result<()> {
+    validate_ip4_method(method, !addrs.is_empty())?;
+
+    let method = match method {
+        Some(m) => m,
+        None if !addrs.is_empty() => Ip4Method::Manual,
+        None => default,
+    };
+
+    add_ip4_addrs(s_ip4, addrs)?;
+    s_ip4.set_method(Some(get_ip4_method_str(method)));
+
+    Ok(())
+}
+
+// Warn about static IPv4 addresses whose subnets overlap those of other active
+// connections, as overlapping subnets break routing. Rejected instead when strict.
+// Connections on the given interfaces are ignored, as they are about to be replaced
+pub fn check_ip4_conflicts(
+    client: &impl NmClient,
+    addrs: &[String],
+    ignore_ifnames: &[&str],
+    strict: bool,
+) -> Result<()> {
+    for addr in addrs.iter() {
+        let ip4_net = Ipv4Net::from_str(addr)?;
+
+        let conflicts = get_conflicting_ip4_connections(client, &ip4_net, ignore_ifnames);
+        if conflicts.is_empty() {
+            continue;
+        }
+
+        let msg = format!(
+            "IPv4 address \"{}\" overlaps subnet of active connections {:?}",
+            addr, conflicts
+        );
+        if strict {
+            return Err(anyhow!("{}", msg));
+        }
+        warn!("{}", msg);
+    }
+
+    Ok(())
+}
+
+// IDs of active connections with an IPv4 address in a subnet overlapping the given one
+pub fn get_conflicting_ip4_connections(
+    client: &impl NmClient,
+    ip4_net: &Ipv4Net,
+    ignore_ifnames: &[&str],
+) -> Vec<String> {
+    let mut conflicts: Vec<String> = vec![];
+
+    for active_conn in client.active_connections().into_iter() {
+        let ignored = active_conn
+            .devices()
+            .iter()
+            .filter_map(|d| d.iface())
+            .any(|i| ignore_ifnames.contains(&i.as_str()));
+        if ignored {
+            continue;
+        }
+
+        let cfg = match active_conn.ip4_config() {
+            Some(cfg) => cfg,
+            None => continue,
+        };
+
+        let overlaps = cfg.addresses().iter().any(|a| {
+            a.address()
+                .and_then(|addr| Ipv4Addr::from_str(&addr).ok())
+                .and_then(|addr| Ipv4Net::new(addr, a.prefix() as u8).ok())
+                .is_some_and(|active_net| ip4_nets_overlap(ip4_net, &active_net))
+        });
+
+        if overlaps {
+            conflicts.push(active_conn.id().map_or(String::new(), |i| i.to_string()));
+        }
+    }
+
+    conflicts
+}
+
+// Subnets overlap when either contains the other, as subnets never partially overlap
+fn ip4_nets_overlap(a: &Ipv4Net, b: &Ipv4Net) -> bool {
+    a.contains(&b.network()) || b.contains(&a.network())
+}
+
+// Manual configuration is meaningless without an address, and NetworkManager
+// rejects addresses when IPv4 is disabled
+pub fn validate_ip4_method(method: Option<Ip4Method>, static_addr: bool) -> Result<()> {
+    match (method, static_addr) {
+        (Some(Ip4Method::Manual), false) => Err(anyhow!(
+            "IPv4 method \"manual\" requires at least one static IPv4 address"
+        )),
+        (Some(Ip4Method::Disabled), true) => Err(anyhow!(
+            "IPv4 method \"disabled\" does not allow static IPv4 addresses"
+        )),
+        _ => Ok(()),
+    }
+}
+
+// Inverse of get_ip4_method_str()
+pub fn get_ip4_method(method: &str) -> Option<Ip4Method> {
+    Ip4Method::value_variants()
+        .iter()
+        .find(|m| get_ip4_method_str(**m) == method)
+        .copied()
+}
+
+pub fn get_ip4_method_str(method: Ip4Method) -> &'static str {
+    match method {
+        Ip4Method::Auto => SETTING_IP4_CONFIG_METHOD_AUTO,
+        Ip4Method::Manual => SETTING_IP4_CONFIG_METHOD_MANUAL,
+        Ip4Method::LinkLocal => SETTING_IP4_CONFIG_METHOD_LINK_LOCAL,
+        Ip4Method::Shared => SETTING_IP4_CONFIG_METHOD_SHARED,
+        Ip4Method::Disabled => SETTING_IP4_CONFIG_METHOD_DISABLED,
+    }
+}
+
+// Ensure gateway and DNS servers are valid IPv4 addresses before applying
+pub fn validate_ip4_gateway_dns(
+    gateway: Option<&str>,
+    dns: &[String],
+    static_addr: bool,
+) -> Result<()> {
+    if let Some(gateway) = gateway {
+        if !static_addr {
+            return Err(anyhow!(
+                "IPv4 gateway \"{}\" requires a static IPv4 address",
+                gateway
+            ));
+        }
+
+        if Ipv4Addr::from_str(gateway).is_err() {
+            return Err(anyhow!("Invalid IPv4 gateway \"{}\"", gateway));
+        }
+    }
+
+    for server in dns.iter() {
+        if Ipv4Addr::from_str(server).is_err() {
+            return Err(anyhow!("Invalid IPv4 DNS server \"{}\"", server));
+        }
+    }
+
+    Ok(())
+}
+
+// Fail activation when no DHCP lease is obtained within timeout seconds, rather than
+// leaving connection up without IPv4 connectivity. Only meaningful for automatic IPv4.
+// Zero timeout uses the NetworkManager default, leaving may-fail unchanged
+pub fn apply_ip4_dhcp_timeout(
+    s_ip4: &SettingIP4Config,
+    dhcp_timeout: Option<i32>,
+    static_addr: bool,
+) -> Result<()> {
+    validate_ip4_dhcp_timeout(dhcp_timeout, static_addr)?;
+
+    if let Some(dhcp_timeout) = dhcp_timeout {
+        s_ip4.set_dhcp_timeout(dhcp_timeout);
+
+        // Otherwise, connection activates without IPv4 when IPv6 succeeds
+        if dhcp_timeout != 0 {
+            s_ip4.set_may_fail(false);
+        }
+    }
+
+    Ok(())
+}
+
+// Identify connection to DHCP server by client ID and hostname, e.g. for lease
+// reservations. Only meaningful for automatic IPv4, so call after setting method
+pub fn apply_ip4_dhcp_identity(
+    s_ip4: &SettingIP4Config,
+    client_id: Option<&str>,
+    hostname: Option<&str>,
+) {
+    if client_id.is_none() && hostname.is_none() {
+        return;
+    }
+
+    let auto = s_ip4
+        .method()
+        .is_some_and(|m| m == get_ip4_method_str(Ip4Method::Auto));
+    if !auto {
+        warn!("IPv4 DHCP client ID and hostname require automatic IPv4 configuration, ignoring");
+        return;
+    }
+
+    if let Some(client_id) = client_id {
+        s_ip4.set_dhcp_client_id(Some(client_id));
+    }
+
+    if let Some(hostname) = hostname {
+        s_ip4.set_dhcp_hostname(Some(hostname));
+        s_ip4.set_dhcp_send_hostname(true);
+    }
+}
+
+// Explicitly set may-fail, rather than just leaving it as set by any DHCP timeout
+pub fn apply_ip4_may_fail(
+    s_ip4: &SettingIP4Config,
+    may_fail: Option<bool>,
+    dhcp_timeout: Option<i32>,
+) -> Result<()> {
+    validate_ip4_may_fail(may_fail, dhcp_timeout)?;
+
+    if let Some(may_fail) = may_fail {
+        s_ip4.set_may_fail(may_fail);
+    }
+
+    Ok(())
+}
+
+// DHCP timeout only fails activation when IPv4 may not fail, so
+// allowing failure would silently make the timeout ineffective
+pub fn validate_ip4_may_fail(may_fail: Option<bool>, dhcp_timeout: Option<i32>) -> Result<()> {
+    if may_fail == Some(true) && dhcp_timeout.is_some_and(|t| t != 0) {
+        return Err(anyhow!(
+            "IPv4 DHCP timeout requires IPv4 configuration not be allowed to fail"
+        ));
+    }
+
+    Ok(())
+}
+
+pub fn validate_ip4_dhcp_timeout(dhcp_timeout: Option<i32>, static_addr: bool) -> Result<()> {
+    if let Some(dhcp_timeout) = dhcp_timeout {
+        if static_addr {
+            return Err(anyhow!(
+                "IPv4 DHCP timeout requires automatic IPv4 configuration"
+            ));
+        }
+
+        if dhcp_timeout < 0 {
+            return Err(anyhow!(
+                "Invalid IPv4 DHCP timeout \"{}\", expected zero or more seconds",
+                dhcp_timeout
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Single libnm setting property, e.g. "connection" -> "id" -> "'bond0'"
+#[derive(PartialEq, Clone, Debug)]
+pub struct SettingEntry {
+    pub setting: String,
+    pub key: String,
+    pub value: String,
+}
+
+/// Printed in place of secret values, e.g. PSKs
+const REDACTED_VALUE: &str = "<redacted>";
+
+// Print every setting of a built connection, i.e. exactly what would be sent
+// to NetworkManager, with secrets redacted. Used to explain connection creation
+pub fn print_connection_settings(conn: &impl ConnectionExt) -> Result<()> {
+    let entries = get_setting_entries(conn, ConnectionSerializationFlags::ALL)?;
+    let public_entries = get_setting_entries(conn, ConnectionSerializationFlags::NO_SECRETS)?;
+
+    print!(
+        "{}",
+        get_setting_entries_str(&redact_secrets(entries, &public_entries))
+    );
+    Ok(())
+}
+
+// Flatten connection's D-Bus representation, a dict of setting name
+// to dict of property name to value ("a{sa{sv}}"), into entries
+fn get_setting_entries(
+    conn: &impl ConnectionExt,
+    flags: ConnectionSerializationFlags,
+) -> Result<Vec<SettingEntry>> {
+    let settings = match conn.to_dbus(flags) {
+        Some(s) => s,
+        None => return Err(anyhow!("Unable to serialize connection settings")),
+    };
+
+    let mut entries: Vec<SettingEntry> = vec![];
+    for setting in settings.iter() {
+        let setting_name = setting.child_value(0).str().unwrap_or_default().to_string();
+
+        for property in setting.child_value(1).iter() {
+            let value = property.child_value(1);
+            entries.push(SettingEntry {
+                setting: setting_name.clone(),
+                key: property
+                    .child_value(0)
+                    .str()
+                    .unwrap_or_default()
+                    .to_string(),
+                value: value.as_variant().unwrap_or(value).print(false).to_string(),
+            });
+        }
+    }
+
+    Ok(entries)
+}
+
+// Secrets are only present when serializing with secrets, so any entry
+// missing from the secret-free serialization has its value redacted
+fn redact_secrets(
+    entries: Vec<SettingEntry>,
+    public_entries: &[SettingEntry],
+) -> Vec<SettingEntry> {
+    entries
+        .into_iter()
+        .map(|mut entry| {
+            let public = public_entries
+                .iter()
+                .any(|e| e.setting == entry.setting && e.key == entry.key);
+
+            if !public {
+                entry.value = REDACTED_VALUE.to_string();
+            }
+            entry
+        })
+        .collect()
+}
+
+// Group entries by setting, one property per line indented below the setting name
+fn get_setting_entries_str(entries: &[SettingEntry]) -> String {
+    let mut s = String::new();
+    let mut prev_setting: Option<&str> = None;
+
+    for entry in entries.iter() {
+        if prev_setting != Some(entry.setting.as_str()) {
+            s.push_str(&format!("{}:\n", entry.setting));
+            prev_setting = Some(entry.setting.as_str());
+        }
+        s.push_str(&format!("  {}: {}\n", entry.key, entry.value));
+    }
+
+    s
+}
+
+// Report change to NetworkManager which would have been made, were this not a dry run
+pub fn print_dry_run(action: &str) {
+    println!("Dry run, would {action}");
+}
+
+// Error rather than allow deactivating a conflicting active connection on the
+// given interface, when the user has disabled preempting existing connections
+pub fn check_preempt(no_preempt: bool, ifname: &str) -> Result<()> {
+    if no_preempt {
+        return Err(anyhow!(
+            "Found active connection on interface \"{}\" conflicting with desired connection, \
+             not deactivating as preemption disabled",
+            ifname
+        ));
+    }
+    Ok(())
+}
+
+// Deactivate any active connections of the given device type on an interface, freeing
+// it for the desired connection. Returns number of connections deactivated, or which
+// would have been when a dry run. Errors when any are active and preemption is disabled
+#[instrument(skip(client), err)]
+pub async fn deactivate_connections_on_iface(
+    client: &impl NmClient,
+    ifname: &str,
+    device_type: DeviceType,
+    no_preempt: bool,
+    dry_run: bool,
+    yes: bool,
+) -> Result<usize> {
+    let conn_type = get_device_type_setting_name(device_type);
+    let active_conns: Vec<ActiveConnection> = client
+        .active_connections()
+        .into_iter()
+        .filter(|c| c.type_().as_deref() == conn_type)
+        .filter(|c| {
+            c.devices()
+                .iter()
+                .any(|d| d.iface().is_some_and(|i| i == ifname))
+        })
+        .collect();
+
+    if active_conns.is_empty() {
+        debug!("No active connections on interface \"{}\"", ifname);
+        return Ok(0);
+    }
+    check_preempt(no_preempt, ifname)?;
+
+    for c in active_conns.iter() {
+        let name = c.id().unwrap_or_default();
+        let action = format!(
+            "deactivate existing connection \"{}\" on interface \"{}\"",
+            name, ifname
+        );
+
+        if dry_run {
+            print_dry_run(&action);
+            continue;
+        }
+
+        confirm_action(&action, yes)?;
+        debug!(
+            "Deactivating connection \"{}\" on interface \"{}\"",
+            name, ifname
+        );
+        client.deactivate_connection_future(c).await?;
+    }
+
+    Ok(active_conns.len())
+}
+
+// Report state of connection whose activation was started but not waited for
+pub fn print_activation_state(conn: &ActiveConnection, name: &str) {
+    println!(
+        "Not waiting for connection \"{}\" to activate, currently {}",
+        name,
+        get_connection_state_str(conn.state())
+    );
+}
+
+// Check whether command may proceed given guard on target connection's activation
+// state. Target connection only built and searched for when a guard is specified
+pub fn active_guard_allows<F>(
+    client: &impl NmClient,
+    guard: Option<ActiveGuard>,
+    device_type: DeviceType,
+    create_conn: F,
+) -> Result<bool>
+where
+    F: FnOnce() -> Result<SimpleConnection>,
+{
+    let guard = match guard {
+        Some(g) => g,
+        None => return Ok(true),
+    };
+
+    let active = get_active_connection(client, device_type, &create_conn()?).is_some();
+    let allowed = check_active_guard(guard, active);
+
+    if !allowed {
+        warn!(
+            "Connection is {}, skipping",
+            if active { "active" } else { "inactive" }
+        );
+    }
+    Ok(allowed)
+}
+
+fn check_active_guard(guard: ActiveGuard, active: bool) -> bool {
+    match guard {
+        ActiveGuard::Active => active,
+        ActiveGuard::Inactive => !active,
+    }
+}
+
 // Search for connection that matches the specified
 // device type and properties in provided connection.
 //
@@ -45,11 +933,16 @@ pub fn create_wired_connection(
 // This done to enable verbose logging
 #[instrument(skip(client, conn), parent=None)]
 pub fn get_connection(
-    client: &Client,
+    client: &impl NmClient,
     device_type: DeviceType,
     conn: &SimpleConnection,
 ) -> Option<RemoteConnection> {
-    let ifname = conn.interface_name()?;
+    // Wireless connections may be matched on SSID alone, without an interface
+    let ifname = match conn.interface_name() {
+        Some(ifname) => ifname.to_string(),
+        None if device_type == DeviceType::Wifi => "any".to_string(),
+        None => return None,
+    };
     debug!("Searching for connection with ifname \"{}\"", ifname);
 
     // Only Bond and Ethernet DeviceType supported
@@ -132,11 +1025,16 @@ pub fn get_connection(
 // This done to enable verbose logging
 #[instrument(skip(client, conn), parent=None)]
 pub fn get_active_connection(
-    client: &Client,
+    client: &impl NmClient,
     device_type: DeviceType,
     conn: &SimpleConnection,
 ) -> Option<ActiveConnection> {
-    let ifname = conn.interface_name()?;
+    // Wireless connections may be matched on SSID alone, without an interface
+    let ifname = match conn.interface_name() {
+        Some(ifname) => ifname.to_string(),
+        None if device_type == DeviceType::Wifi => "any".to_string(),
+        None => return None,
+    };
     debug!("Searching for active connection with ifname \"{}\"", ifname);
 
     // Only Bond, Ethernet, and Wifi (STA and AP) DeviceType supported
@@ -163,158 +1061,615 @@ pub fn get_active_connection(
             }
         };
 
-        let cmp_conn = cmp_remote_conn.upcast::<Connection>();
+        let cmp_conn = cmp_remote_conn.upcast::<Connection>();
+
+        // Get connection interface name for logging
+        let cmp_conn_ifname = match cmp_conn.interface_name() {
+            Some(c) => c,
+            None => {
+                error!(
+                    "Unable to get connection interface name for connection {}",
+                    cmp_conn
+                );
+                continue;
+            }
+        };
+
+        let found_matching = match device_type {
+            DeviceType::Bond => matching_bond_connection(conn, &cmp_conn),
+            DeviceType::Ethernet => matching_wired_connection(conn, &cmp_conn),
+            DeviceType::Wifi => matching_wifi_connection(conn, &cmp_conn),
+            _ => {
+                // Should never get here given check at beginning of func
+                panic!("Unsupported device type \"{device_type}\" for get_connection()",)
+            }
+        };
+
+        if found_matching && matching_conn.is_none() {
+            // Found matching for first time. Save matching and continue
+            // to log any other connections with the same interface name
+            debug!(
+                "Found connection with matching interface name \"{}\"",
+                ifname
+            );
+            matching_conn = Some(cmp_active_conn);
+        } else if found_matching {
+            // Already found and saved a matching connection, log any further connections
+            warn!(
+                "Ignoring duplicate connection with matching interface name \"{}\"",
+                ifname
+            );
+        } else {
+            debug!(
+                "Skipping non-matching connection with interface name \"{}\"",
+                cmp_conn_ifname
+            );
+        }
+    }
+
+    matching_conn
+}
+
+// Search for connection with the given UUID. Unlike interface names,
+// UUIDs are stable and unique, so no further matching required
+#[instrument(skip(client), parent=None)]
+pub fn get_connection_by_uuid(client: &impl NmClient, uuid: &str) -> Option<RemoteConnection> {
+    debug!("Searching for connection with UUID \"{}\"", uuid);
+
+    client.connections().into_iter().find(|c| {
+        c.setting_connection()
+            .and_then(|s| s.uuid())
+            .is_some_and(|u| u.as_str() == uuid)
+    })
+}
+
+// Connection with the given UUID when specified, otherwise search for connection
+// that matches the specified device type and properties in provided connection
+pub fn get_target_connection(
+    client: &impl NmClient,
+    device_type: DeviceType,
+    conn: &SimpleConnection,
+    uuid: Option<&str>,
+) -> Result<Option<RemoteConnection>> {
+    let uuid = match uuid {
+        Some(u) => u,
+        None => return Ok(get_connection(client, device_type, conn)),
+    };
+
+    let uuid_conn = match get_connection_by_uuid(client, uuid) {
+        Some(c) => c,
+        None => return Ok(None),
+    };
+
+    // Guard against e.g. deleting a bond with an access point's UUID
+    let conn_type = uuid_conn.connection_type().unwrap_or_default();
+    if Some(conn_type.as_str()) != get_device_type_setting_name(device_type) {
+        return Err(anyhow!(
+            "Connection with UUID \"{}\" has unexpected type \"{}\"",
+            uuid,
+            conn_type
+        ));
+    }
+    Ok(Some(uuid_conn))
+}
+
+// Active connection with the given UUID when specified, otherwise search for active
+// connection that matches the specified device type and properties in provided connection
+pub fn get_target_active_connection(
+    client: &impl NmClient,
+    device_type: DeviceType,
+    conn: &SimpleConnection,
+    uuid: Option<&str>,
+) -> Option<ActiveConnection> {
+    match uuid {
+        Some(uuid) => client
+            .active_connections()
+            .into_iter()
+            .find(|c| c.uuid().is_some_and(|u| u.as_str() == uuid)),
+        None => get_active_connection(client, device_type, conn),
+    }
+}
+
+// Give connection about to replace an existing one the existing connection's UUID.
+// Must be called before the existing connection is deleted
+pub fn preserve_connection_uuid(conn: &SimpleConnection, existing_conn: &RemoteConnection) {
+    let uuid = match existing_conn.uuid() {
+        Some(u) => u,
+        None => {
+            warn!("Unable to get UUID of connection being replaced, generating new UUID");
+            return;
+        }
+    };
+
+    if let Some(s_connection) = conn.setting_connection() {
+        debug!("Preserving UUID \"{}\" of replaced connection", uuid);
+        s_connection.set_uuid(Some(&uuid));
+    }
+}
+
+// Connection type setting name for device types nutil manages
+fn get_device_type_setting_name(device_type: DeviceType) -> Option<&'static str> {
+    match device_type {
+        DeviceType::Bond => Some(SETTING_BOND_SETTING_NAME),
+        DeviceType::Ethernet => Some(SETTING_WIRED_SETTING_NAME),
+        DeviceType::Wifi => Some(SETTING_WIRELESS_SETTING_NAME),
+        _ => None,
+    }
+}
+
+#[instrument(skip(client), parent=None)]
+pub fn get_slave_connections(
+    client: &impl NmClient,
+    master_ifname: &str,
+    slave_device_type: DeviceType,
+) -> Option<Vec<RemoteConnection>> {
+    debug!(
+        "Searching for slave connection with master ifname \"{}\"",
+        master_ifname
+    );
+
+    let mut slave_conns: Vec<RemoteConnection> = vec![];
+
+    // Iterate through connections attempting to match connection's master ifname with provided
+    for conn in client.connections().into_iter() {
+        let conn = conn.upcast::<Connection>();
+
+        let conn_settings = match conn.setting_connection() {
+            Some(c) => c,
+            None => {
+                error!("Unable to get connection settings");
+                continue;
+            }
+        };
+
+        let conn_id = match conn_settings.id() {
+            Some(c) => c,
+            None => {
+                error!("Unable to get connection id");
+                return None;
+            }
+        };
+        let conn_id_str = conn_id.as_str();
+
+        if !has_slave_device_setting(&conn, slave_device_type) {
+            debug!(
+                "Skipping connection \"{}\" not for device type \"{}\"",
+                conn_id_str, slave_device_type
+            );
+            continue;
+        }
+
+        match conn_settings.master() {
+            Some(conn_master) => {
+                if conn_master != master_ifname {
+                    debug!(
+                        "Master interface \"{}\" for connection \"{}\" does not match desired master interface \"{}\"",
+                        conn_master, conn_id_str, master_ifname
+                    );
+                } else {
+                    debug!(
+                        "Master interface \"{}\" for connection \"{}\" matches desired master interface \"{}\"",
+                        conn_master, conn_id_str, master_ifname
+                    );
+
+                    // Expect unwrap to succeed as we just upcasted from a RemoteConnection earlier
+                    slave_conns.push(conn.downcast::<RemoteConnection>().unwrap());
+                }
+            }
+            None => {
+                debug!("Skipping connection without master \"{}\"", conn_id_str);
+                continue;
+            }
+        }
+    }
+
+    Some(slave_conns)
+}
+
+// Whether connection has the settings for slave device type, e.g. wired settings for
+// Ethernet. Types without dedicated settings match any connection, relying on the master
+fn has_slave_device_setting(conn: &impl ConnectionExt, slave_device_type: DeviceType) -> bool {
+    match slave_device_type {
+        DeviceType::Ethernet => conn.setting_wired().is_some(),
+        DeviceType::Wifi => conn.setting_wireless().is_some(),
+        _ => true,
+    }
+}
+
+/// Time to wait for a deactivated connection to fully deactivate
+/// before deleting it, when no timeout specified
+pub const DELETE_DEACTIVATE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Connection did not reach target state before timeout expired
+#[derive(Debug)]
+pub struct StateWaitTimeout {
+    target: ActiveConnectionState,
+    timeout: Duration,
+}
+
+impl std::fmt::Display for StateWaitTimeout {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "Connection failed to reach state \"{}\" within {}s",
+            get_connection_state_str(self.target),
+            self.timeout.as_secs()
+        )
+    }
+}
+
+impl std::error::Error for StateWaitTimeout {}
+
+// Wait for deactivated connection to fully deactivate before deleting it. Deleting
+// while still deactivating occasionally causes NetworkManager to error, but a slow
+// deactivation shouldn't prevent deletion, so only wait a short time by default
+pub async fn wait_for_deactivate_before_delete(
+    conn: &ActiveConnection,
+    timeout: Option<Duration>,
+) -> Result<()> {
+    let timeout = timeout.unwrap_or(DELETE_DEACTIVATE_TIMEOUT);
+    let res = wait_for_state(conn, ActiveConnectionState::Deactivated, Some(timeout)).await;
+
+    get_delete_wait_result(res)
+}
+
+// Timing out while deactivating only warrants a warning before deleting anyway.
+// Anything else, e.g. connection reactivating, is still an error
+fn get_delete_wait_result(res: Result<()>) -> Result<()> {
+    match res {
+        Err(e) if e.is::<StateWaitTimeout>() => {
+            warn!("{}, deleting anyway", e);
+            Ok(())
+        }
+        res => res,
+    }
+}
+
+// Activate an existing connection matching provided connection, or with the given
+// UUID, without recreating it. NetworkManager chooses the device to activate on
+#[instrument(skip(client, conn), err)]
+pub async fn activate_existing_connection(
+    client: &impl NmClient,
+    device_type: DeviceType,
+    conn: &SimpleConnection,
+    uuid: Option<&str>,
+    timeout: Option<Duration>,
+    no_wait: bool,
+    dry_run: bool,
+) -> Result<()> {
+    let name = conn.id().unwrap_or_default();
+
+    let remote_conn = match get_target_connection(client, device_type, conn, uuid)? {
+        Some(c) => c,
+        None => return Err(anyhow!("Connection \"{}\" does not exist", name)),
+    };
+
+    if get_target_active_connection(client, device_type, conn, uuid).is_some() {
+        info!("Connection \"{}\" is already active", name);
+        return Ok(());
+    }
+
+    if dry_run {
+        print_dry_run(&format!("activate connection \"{}\"", name));
+        return Ok(());
+    }
+
+    info!("Activating connection \"{}\"", name);
+    let active_conn = client
+        .activate_connection_future(Some(&remote_conn), None::<&Device>, None)
+        .await
+        .map_err(map_nm_error)?;
+
+    if no_wait {
+        print_activation_state(&active_conn, &name);
+        return Ok(());
+    }
+
+    wait_for_connection_to_activate(&active_conn, timeout).await?;
+    info!("Activated connection \"{}\"", name);
+    Ok(())
+}
+
+// Deactivate an existing connection matching provided connection, or with
+// the given UUID, without deleting it. No-op when already inactive
+#[instrument(skip(client, conn), err)]
+pub async fn deactivate_existing_connection(
+    client: &impl NmClient,
+    device_type: DeviceType,
+    conn: &SimpleConnection,
+    uuid: Option<&str>,
+    timeout: Option<Duration>,
+    no_wait: bool,
+    dry_run: bool,
+) -> Result<()> {
+    let name = conn.id().unwrap_or_default();
+
+    if get_target_connection(client, device_type, conn, uuid)?.is_none() {
+        return Err(anyhow!("Connection \"{}\" does not exist", name));
+    }
+
+    let active_conn = match get_target_active_connection(client, device_type, conn, uuid) {
+        Some(c) => c,
+        None => {
+            info!("Connection \"{}\" is not active", name);
+            return Ok(());
+        }
+    };
+
+    if dry_run {
+        print_dry_run(&format!("deactivate connection \"{}\"", name));
+        return Ok(());
+    }
+
+    info!("Deactivating connection \"{}\"", name);
+    client.deactivate_connection_future(&active_conn).await?;
+
+    if !no_wait {
+        wait_for_state(&active_conn, ActiveConnectionState::Deactivated, timeout).await?;
+    }
+    info!("Deactivated connection \"{}\"", name);
+    Ok(())
+}
+
+// Delete wireless connection with the given SSID, whether created as a station,
+// access point, or ad-hoc network. Refuses to guess when more than one matches
+#[instrument(skip(client), err)]
+pub async fn delete_wifi_connection(
+    client: &impl NmClient,
+    ssid: &str,
+    wireless_ifname: Option<&str>,
+    timeout: Option<Duration>,
+    yes: bool,
+    dry_run: bool,
+) -> Result<()> {
+    let wifi_conn = create_wifi_match_connection(ssid, wireless_ifname)?;
+
+    let mut matching_conns: Vec<RemoteConnection> = client
+        .connections()
+        .into_iter()
+        .filter(|c| matching_wifi_connection(&wifi_conn, c.upcast_ref::<Connection>()))
+        .collect();
+
+    let remote_conn = match matching_conns.len() {
+        0 => {
+            return Err(anyhow!(
+                "Required wireless connection \"{}\" does not exist, quitting...",
+                ssid
+            ))
+        }
+        1 => matching_conns.remove(0),
+        n => {
+            return Err(anyhow!(
+                "Found {} wireless connections with SSID \"{}\", specify interface \
+                 or delete using the station or access point command",
+                n,
+                ssid
+            ))
+        }
+    };
+    let name = remote_conn.id().unwrap_or_default();
+
+    if dry_run {
+        print_dry_run(&format!("delete wireless connection \"{}\"", name));
+        return Ok(());
+    }
+
+    confirm_action(&format!("delete wireless connection \"{}\"", name), yes)?;
+
+    let uuid = remote_conn.uuid().unwrap_or_default();
+    let active_conn = client
+        .active_connections()
+        .into_iter()
+        .find(|c| c.uuid().is_some_and(|u| u == uuid));
+    if let Some(c) = active_conn {
+        info!("Deactivating wireless connection \"{}\"", name);
+        client.deactivate_connection_future(&c).await?;
+
+        // Give connection a chance to fully deactivate before deleting
+        wait_for_deactivate_before_delete(&c, timeout).await?;
+        info!("Wireless connection deactivated");
+    }
+
+    info!("Deleting wireless connection \"{}\"", name);
+    remote_conn.delete_future().await?;
+    info!("Wireless connection deleted");
+
+    Ok(())
+}
+
+// Wireless connection used only for matching. Mode and security are left
+// unset so matching skips them, as is the interface when not specified
+pub fn create_wifi_match_connection(
+    ssid: &str,
+    wireless_ifname: Option<&str>,
+) -> Result<SimpleConnection> {
+    validate_ssid(ssid)?;
+
+    let connection = SimpleConnection::new();
+    let s_connection = SettingConnection::new();
+    let s_wireless = SettingWireless::new();
+
+    s_connection.set_type(Some(SETTING_WIRELESS_SETTING_NAME));
+    s_connection.set_id(Some(ssid));
+    if let Some(ifname) = wireless_ifname {
+        s_connection.set_interface_name(Some(ifname));
+    }
+
+    s_wireless.set_ssid(Some(&(ssid.as_bytes().into())));
+
+    connection.add_setting(s_connection);
+    connection.add_setting(s_wireless);
+
+    Ok(connection)
+}
+
+/// Future returned by NetworkManager client calls, as returned by libnm bindings
+pub type NmFuture<T> = Pin<Box<dyn Future<Output = Result<T, glib::Error>> + 'static>>;
+
+/// NetworkManager client calls used by commands. Implemented by `nm::Client`, and
+/// abstracted so command logic may be tested without a running NetworkManager
+pub trait NmClient {
+    fn connections(&self) -> Vec<RemoteConnection>;
+
+    fn active_connections(&self) -> Vec<ActiveConnection>;
+
+    fn devices(&self) -> Vec<Device>;
 
-        // Get connection interface name for logging
-        let cmp_conn_ifname = match cmp_conn.interface_name() {
-            Some(c) => c,
-            None => {
-                error!(
-                    "Unable to get connection interface name for connection {}",
-                    cmp_conn
-                );
-                continue;
-            }
-        };
+    fn device_by_iface(&self, iface: &str) -> Option<Device>;
 
-        let found_matching = match device_type {
-            DeviceType::Bond => matching_bond_connection(conn, &cmp_conn),
-            DeviceType::Ethernet => matching_wired_connection(conn, &cmp_conn),
-            DeviceType::Wifi => matching_wifi_connection(conn, &cmp_conn),
-            _ => {
-                // Should never get here given check at beginning of func
-                panic!("Unsupported device type \"{device_type}\" for get_connection()",)
-            }
-        };
+    fn add_connection_future(
+        &self,
+        conn: &SimpleConnection,
+        save_to_disk: bool,
+    ) -> NmFuture<RemoteConnection>;
 
-        if found_matching && matching_conn.is_none() {
-            // Found matching for first time. Save matching and continue
-            // to log any other connections with the same interface name
-            debug!(
-                "Found connection with matching interface name \"{}\"",
-                ifname
-            );
-            matching_conn = Some(cmp_active_conn);
-        } else if found_matching {
-            // Already found and saved a matching connection, log any further connections
-            warn!(
-                "Ignoring duplicate connection with matching interface name \"{}\"",
-                ifname
-            );
-        } else {
-            debug!(
-                "Skipping non-matching connection with interface name \"{}\"",
-                cmp_conn_ifname
-            );
-        }
-    }
+    fn activate_connection_future(
+        &self,
+        conn: Option<&RemoteConnection>,
+        device: Option<&Device>,
+        specific_object: Option<&str>,
+    ) -> NmFuture<ActiveConnection>;
 
-    matching_conn
+    fn deactivate_connection_future(&self, conn: &ActiveConnection) -> NmFuture<()>;
+
+    /// Running NetworkManager daemon version, if known
+    fn version(&self) -> Option<String>;
 }
 
-#[instrument(skip(client), parent=None)]
-pub fn get_slave_connections(
-    client: &Client,
-    master_ifname: &str,
-    slave_device_type: DeviceType,
-) -> Option<Vec<RemoteConnection>> {
-    debug!(
-        "Searching for slave connection with master ifname \"{}\"",
-        master_ifname
-    );
+impl NmClient for Client {
+    fn connections(&self) -> Vec<RemoteConnection> {
+        Client::connections(self)
+    }
 
-    // Only Ethernet DeviceType supported
-    if slave_device_type != DeviceType::Ethernet {
-        error!(
-            "Unsupported device type \"{}\" for get_connection()",
-            slave_device_type
-        );
-        return None;
+    fn active_connections(&self) -> Vec<ActiveConnection> {
+        Client::active_connections(self)
     }
 
-    let mut slave_conns: Vec<RemoteConnection> = vec![];
+    fn devices(&self) -> Vec<Device> {
+        Client::devices(self)
+    }
 
-    // Iterate through connections attempting to match connection's master ifname with provided
-    for conn in client.connections().into_iter() {
-        let conn = conn.upcast::<Connection>();
+    fn device_by_iface(&self, iface: &str) -> Option<Device> {
+        Client::device_by_iface(self, iface)
+    }
 
-        let conn_settings = match conn.setting_connection() {
-            Some(c) => c,
-            None => {
-                error!("Unable to get connection settings");
-                continue;
-            }
-        };
+    fn add_connection_future(
+        &self,
+        conn: &SimpleConnection,
+        save_to_disk: bool,
+    ) -> NmFuture<RemoteConnection> {
+        Client::add_connection_future(self, conn, save_to_disk)
+    }
 
-        let conn_id = match conn_settings.id() {
-            Some(c) => c,
-            None => {
-                error!("Unable to get connection id");
-                return None;
-            }
-        };
-        let conn_id_str = conn_id.as_str();
+    fn activate_connection_future(
+        &self,
+        conn: Option<&RemoteConnection>,
+        device: Option<&Device>,
+        specific_object: Option<&str>,
+    ) -> NmFuture<ActiveConnection> {
+        Client::activate_connection_future(self, conn, device, specific_object)
+    }
 
-        if conn.setting_wired().is_none() {
-            debug!("Skipping non-wired connection \"{}\"", conn_id_str);
-            continue;
-        }
+    fn deactivate_connection_future(&self, conn: &ActiveConnection) -> NmFuture<()> {
+        Client::deactivate_connection_future(self, conn)
+    }
 
-        match conn_settings.master() {
-            Some(conn_master) => {
-                if conn_master != master_ifname {
-                    debug!(
-                        "Master interface \"{}\" for connection \"{}\" does not match desired master interface \"{}\"",
-                        conn_master, conn_id_str, master_ifname
-                    );
-                } else {
-                    debug!(
-                        "Master interface \"{}\" for connection \"{}\" matches desired master interface \"{}\"",
-                        conn_master, conn_id_str, master_ifname
-                    );
+    fn version(&self) -> Option<String> {
+        Client::version(self).map(|v| v.to_string())
+    }
+}
 
-                    // Expect unwrap to succeed as we just upcasted from a RemoteConnection earlier
-                    slave_conns.push(conn.downcast::<RemoteConnection>().unwrap());
-                }
-            }
-            None => {
-                debug!("Skipping connection without master \"{}\"", conn_id_str);
-                continue;
-            }
-        }
+// Create NetworkManager client, giving up once timeout expires rather
+// than hanging indefinitely when D-Bus or NetworkManager is unresponsive
+pub async fn create_client(timeout: Duration) -> Result<Client> {
+    match race_timeout(Client::new_future(), glib::timeout_future(timeout)).await {
+        Some(res) => res.context("Failed to create NM Client"),
+        None => Err(anyhow!(
+            "Failed to connect to NetworkManager within {}s",
+            timeout.as_secs()
+        )),
     }
+}
 
-    Some(slave_conns)
+// Wait for future to complete, unless timeout future completes first.
+// Returns None on timeout. Future takes precedence should both be ready
+async fn race_timeout<F, T>(fut: F, timeout: T) -> Option<F::Output>
+where
+    F: Future,
+    T: Future<Output = ()>,
+{
+    let mut fut = pin!(fut);
+    let mut timeout = pin!(timeout);
+
+    poll_fn(|cx| {
+        if let Poll::Ready(output) = fut.as_mut().poll(cx) {
+            return Poll::Ready(Some(output));
+        }
+
+        match timeout.as_mut().poll(cx) {
+            Poll::Ready(()) => Poll::Ready(None),
+            Poll::Pending => Poll::Pending,
+        }
+    })
+    .await
 }
 
-// Spawn a new helper thread to poll until connection is fully activated
-pub async fn wait_for_connection_to_activate(conn: &ActiveConnection) -> Result<()> {
-    // No sense polling for activated if already up
-    if conn.state() == ActiveConnectionState::Activated {
+// Spawn a new helper thread to poll until connection is fully activated.
+// If timeout is Some, give up and return an error once timeout expires
+pub async fn wait_for_connection_to_activate(
+    conn: &ActiveConnection,
+    timeout: Option<Duration>,
+) -> Result<()> {
+    wait_for_state(conn, ActiveConnectionState::Activated, timeout).await
+}
+
+// Spawn a new helper thread to poll until connection reaches target state,
+// either Activated or Deactivated. If timeout is Some, give up and return
+// an error once timeout expires
+pub async fn wait_for_state(
+    conn: &ActiveConnection,
+    target: ActiveConnectionState,
+    timeout: Option<Duration>,
+) -> Result<()> {
+    // No sense polling for target state if already there
+    if conn.state() == target {
         return Ok(());
     }
 
     let (sender, receiver) = oneshot::channel::<Result<()>>();
     let sender = Rc::new(RefCell::new(Some(sender)));
 
-    // TODO: Impl timeout
-    conn.connect_state_changed(move |_, state, _| {
+    // Race connection state change against timeout. Whichever finishes
+    // first takes the sender, so only one result is ever sent
+    if let Some(timeout) = timeout {
+        let sender = sender.clone();
+
+        glib::MainContext::ref_thread_default().spawn_local(async move {
+            glib::timeout_future(timeout).await;
+
+            let sender = sender.borrow_mut().take();
+
+            if let Some(sender) = sender {
+                sender
+                    .send(Err(StateWaitTimeout { target, timeout }.into()))
+                    .expect("Sender dropped");
+            }
+        });
+    }
+
+    conn.connect_state_changed(move |_, state, reason| {
         let sender = sender.clone();
 
         glib::MainContext::ref_thread_default().spawn_local(async move {
             let state = unsafe { ActiveConnectionState::from_glib(state as _) };
-            debug!("Connection state: {}", get_connection_state_str(state));
-
-            let exit = match state {
-                ActiveConnectionState::Activating => None,
-                ActiveConnectionState::Activated => Some(Ok(())),
-                _ => Some(Err(anyhow!(
-                    "Unexpected connection state \"{}\"",
-                    get_connection_state_str(state)
-                ))),
-            };
+            let reason = unsafe { ActiveConnectionStateReason::from_glib(reason as _) };
+            debug!(
+                "Connection state: {} ({})",
+                get_connection_state_str(state),
+                get_state_reason_str(reason)
+            );
 
-            if let Some(result) = exit {
+            if let Some(result) = get_state_wait_result(state, reason, target) {
                 let sender = sender.borrow_mut().take();
 
                 if let Some(sender) = sender {
@@ -327,6 +1682,133 @@ pub async fn wait_for_connection_to_activate(conn: &ActiveConnection) -> Result<
     receiver.await?
 }
 
+// Determine whether to finish waiting for a connection to reach target state
+// given its current state. Returns None if connection is still transitioning
+// towards target state, i.e. should keep waiting. Reason explains unexpected states
+fn get_state_wait_result(
+    state: ActiveConnectionState,
+    reason: ActiveConnectionStateReason,
+    target: ActiveConnectionState,
+) -> Option<Result<()>> {
+    let transitioning = match target {
+        ActiveConnectionState::Activated => ActiveConnectionState::Activating,
+        ActiveConnectionState::Deactivated => ActiveConnectionState::Deactivating,
+        _ => {
+            return Some(Err(anyhow!(
+                "Unsupported target connection state \"{}\"",
+                get_connection_state_str(target)
+            )))
+        }
+    };
+
+    if state == target {
+        Some(Ok(()))
+    } else if state == transitioning {
+        None
+    } else {
+        Some(Err(anyhow!(
+            "Unexpected connection state \"{}\": {}",
+            get_connection_state_str(state),
+            get_state_reason_str(reason)
+        )))
+    }
+}
+
+// Explain why NetworkManager changed an active connection's state, e.g. why activation failed
+fn get_state_reason_str(reason: ActiveConnectionStateReason) -> &'static str {
+    match reason {
+        ActiveConnectionStateReason::None => "no reason given",
+        ActiveConnectionStateReason::UserDisconnected => "disconnected by user",
+        ActiveConnectionStateReason::DeviceDisconnected => "device disconnected",
+        ActiveConnectionStateReason::ServiceStopped => "VPN service stopped",
+        ActiveConnectionStateReason::IpConfigInvalid => "IP config unavailable",
+        ActiveConnectionStateReason::ConnectTimeout => "timed out connecting",
+        ActiveConnectionStateReason::ServiceStartTimeout => "timed out starting VPN service",
+        ActiveConnectionStateReason::ServiceStartFailed => "VPN service failed to start",
+        ActiveConnectionStateReason::NoSecrets => {
+            "authentication failed, password missing or incorrect"
+        }
+        ActiveConnectionStateReason::LoginFailed => "authentication failed",
+        ActiveConnectionStateReason::ConnectionRemoved => "connection removed",
+        ActiveConnectionStateReason::DependencyFailed => {
+            "dependency failed, e.g. bond master or slave"
+        }
+        ActiveConnectionStateReason::DeviceRealizeFailed => "unable to create virtual device",
+        ActiveConnectionStateReason::DeviceRemoved => "device removed",
+        _ => "unknown reason",
+    }
+}
+
+// Choose wireless device for a station connection not bound to an interface.
+// Returns interface name of device selected by select_device()
+pub fn select_wireless_device(client: &impl NmClient, ssid: &str) -> Result<String> {
+    let mut candidates: Vec<DeviceCandidate> = vec![];
+
+    for device in client.devices().into_iter() {
+        if device.device_type() != DeviceType::Wifi {
+            continue;
+        }
+
+        let ifname = match device.iface() {
+            Some(ifname) => ifname.to_string(),
+            None => continue,
+        };
+
+        // Strongest signal from any AP broadcasting the SSID, if visible
+        let signal = device.clone().downcast::<DeviceWifi>().ok().and_then(|d| {
+            d.access_points()
+                .into_iter()
+                .filter(|ap| ap.ssid().is_some_and(|s| s.as_ref() == ssid.as_bytes()))
+                .map(|ap| ap.strength())
+                .max()
+        });
+
+        candidates.push(DeviceCandidate {
+            ifname,
+            state: device.state(),
+            signal,
+        });
+    }
+
+    match select_device(&candidates) {
+        Some(c) => {
+            info!(
+                "Selected wireless device \"{}\" for SSID \"{}\"",
+                c.ifname, ssid
+            );
+            Ok(c.ifname.clone())
+        }
+        None => Err(anyhow!(
+            "No usable wireless device found for SSID \"{}\"",
+            ssid
+        )),
+    }
+}
+
+// Choose best device to activate a connection on. Devices which can see the target
+// network come first, then idle devices over those already in use, then strongest
+// signal. Unusable devices (e.g. unmanaged) never chosen. Ties go to the earliest device
+pub fn select_device(candidates: &[DeviceCandidate]) -> Option<&DeviceCandidate> {
+    candidates
+        .iter()
+        .rev()
+        .filter(|c| device_usable(c.state))
+        .max_by_key(|c| {
+            (
+                c.signal.is_some(),
+                c.state == DeviceState::Disconnected,
+                c.signal.unwrap_or(0),
+            )
+        })
+}
+
+fn device_usable(state: DeviceState) -> bool {
+    !matches!(
+        state,
+        DeviceState::Unknown | DeviceState::Unmanaged | DeviceState::Unavailable
+    )
+}
+
 // Determine if provided connection for comparison `cmp_conn` is a bond connection
 // and matches desired connection `conn`
 //
@@ -509,7 +1991,6 @@ pub fn matching_wired_connection(conn: &SimpleConnection, cmp_conn: &Connection)
         }
     }
 
-    // TODO
     // Compare both's master connections, if either is a slave connection
     let conn_master = conn_settings.master();
     let cmp_conn_master = cmp_conn_settings.master();
@@ -528,28 +2009,27 @@ pub fn matching_wired_connection(conn: &SimpleConnection, cmp_conn: &Connection)
         return false;
     }
 
-    if conn_master.is_some() && cmp_conn_master.is_some() {
-        let conn_master = conn_master.unwrap();
-        let cmp_conn_master = cmp_conn_master.unwrap();
-
+    if let (Some(conn_master), Some(cmp_conn_master)) = (conn_master, cmp_conn_master) {
         // Empty string master is reserved to indicate we're searching for
         // any wired connection with all matching properties save
         // the master device.
         //
         // In other words, we're looking for any wired connection we want to mess with
         // that's already being used for something else.
-        if conn_master != cmp_conn_master && conn_master != "" {
+        //
+        // Either way, matching masters only continue on to the slave type
+        // comparison below, as slave type must match regardless.
+        if conn_master.is_empty() {
             debug!(
-                "Connection \"{}\" and compared connection \"{}\" have different master devices",
+                "Connection \"{}\" matches any master device, ignoring compared connection \"{}\" master",
                 conn_id_str, cmp_conn_id_str
             );
-            return false;
-        } else if conn_master == "" {
+        } else if conn_master != cmp_conn_master {
             debug!(
-                "Connection \"{}\" and compared connection \"{}\" have different master devices, but match otherwise",
+                "Connection \"{}\" and compared connection \"{}\" have different master devices",
                 conn_id_str, cmp_conn_id_str
             );
-            return true;
+            return false;
         }
     }
 
@@ -572,10 +2052,9 @@ pub fn matching_wired_connection(conn: &SimpleConnection, cmp_conn: &Connection)
     }
 
     // Both connections are slaves, compare slave type
-    if conn_slave_type.is_some() && cmp_conn_slave_type.is_some() {
-        let conn_slave_type = conn_slave_type.unwrap();
-        let cmp_conn_slave_type = cmp_conn_slave_type.unwrap();
-
+    if let (Some(conn_slave_type), Some(cmp_conn_slave_type)) =
+        (conn_slave_type, cmp_conn_slave_type)
+    {
         if conn_slave_type != cmp_conn_slave_type {
             debug!(
                 "Connection \"{}\" has different slave type than compared connection \"{}\"",
@@ -726,25 +2205,231 @@ pub fn matching_wifi_connection(conn: &SimpleConnection, cmp_conn: &Connection)
 
         if conn_ssid != cmp_conn_ssid {
             debug!(
-                "Connection \"{}\" SSID does not match desired SSID",
+                "Connection \"{}\" SSID \"{}\" does not match desired SSID \"{}\"",
                 cmp_conn_id_str,
+                get_ssid_display_str(&cmp_conn_ssid),
+                get_ssid_display_str(&conn_ssid)
             );
             return false;
         }
 
-        // TODO: Get SSID strs for logging purposes w/o resorting to nightly
-        //let cmp_conn_ssid = match cmp_conn_ssid.as_ref().as_ascii() {
-        //    Some(ssid) => ssid,
-        //    None => {
-        //        error!("Connection \"{}\" SSID is not ASCII", cmp_conn_id_str);
-        //        return false;
-        //    }
-        //};
+        debug!(
+            "Connection \"{}\" SSID \"{}\" matches desired SSID",
+            cmp_conn_id_str,
+            get_ssid_display_str(&cmp_conn_ssid)
+        );
+    }
+
+    // Compare BSSID only if pinned in connection to compare against. Otherwise,
+    // the connection may roam between any APs sharing the SSID
+    if let Some(conn_bssid) = conn_wireless_settings.bssid() {
+        match cmp_conn_wireless_settings.bssid() {
+            Some(cmp_conn_bssid) if cmp_conn_bssid.eq_ignore_ascii_case(&conn_bssid) => {}
+            _ => {
+                debug!(
+                    "Connection \"{}\" BSSID does not match desired BSSID \"{}\"",
+                    cmp_conn_id_str, conn_bssid
+                );
+                return false;
+            }
+        }
+    }
+
+    // Compare key management only if connection is secured, so a secured connection never
+    // matches an open one. PSKs are secrets, which connections read back from NetworkManager
+    // do not include, so their presence can't be compared
+    if let Some(conn_security_settings) = conn.setting_wireless_security() {
+        let conn_key_mgmt = conn_security_settings.key_mgmt();
+        let cmp_conn_key_mgmt = cmp_conn
+            .setting_wireless_security()
+            .and_then(|s| s.key_mgmt());
+
+        if conn_key_mgmt != cmp_conn_key_mgmt {
+            debug!(
+                "Connection \"{}\" key management \"{}\" does not match desired key management \"{}\"",
+                cmp_conn_id_str,
+                cmp_conn_key_mgmt.as_deref().unwrap_or("none"),
+                conn_key_mgmt.as_deref().unwrap_or("none")
+            );
+            return false;
+        }
     }
 
     true
 }
 
+/// Retries for NetworkManager calls which may fail transiently on busy systems,
+/// e.g. connection activation. Default policy never retries
+#[derive(Default, PartialEq, Copy, Clone, Debug)]
+pub struct RetryPolicy {
+    /// Attempts after the first. Zero disables retrying
+    pub retries: u32,
+
+    /// Delay before first retry, doubled for each following retry
+    pub delay: Duration,
+}
+
+impl RetryPolicy {
+    // Exponential backoff, where first retry is attempt zero
+    fn retry_delay(&self, attempt: u32) -> Duration {
+        self.delay.saturating_mul(2u32.saturating_pow(attempt))
+    }
+}
+
+// Activate connection on device, retrying transient NetworkManager failures per
+// retry policy. Failures caused by the connection itself are returned immediately
+pub async fn activate_connection_with_retry(
+    client: &impl NmClient,
+    conn: &RemoteConnection,
+    device: &Device,
+    retry: RetryPolicy,
+) -> Result<ActiveConnection> {
+    let mut attempt = 0;
+
+    loop {
+        match client
+            .activate_connection_future(Some(conn), Some(device), None)
+            .await
+        {
+            Ok(c) => return Ok(c),
+            Err(e) if attempt < retry.retries && is_transient_nm_error(&e) => {
+                let delay = retry.retry_delay(attempt);
+                attempt += 1;
+
+                warn!(
+                    "Connection activation failed, retrying in {}ms (retry {} of {}): {}",
+                    delay.as_millis(),
+                    attempt,
+                    retry.retries,
+                    e
+                );
+                glib::timeout_future(delay).await;
+            }
+            Err(e) => return Err(map_nm_error(e)),
+        }
+    }
+}
+
+// Errors which may succeed when retried, e.g. device briefly unavailable while
+// a previous connection deactivates. Invalid connections and permissions never do
+fn is_transient_nm_error(err: &glib::Error) -> bool {
+    if let Some(code) = err.kind::<ManagerError>() {
+        matches!(
+            code,
+            ManagerError::Failed | ManagerError::ConnectionNotAvailable
+        )
+    } else if let Some(code) = err.kind::<DeviceError>() {
+        matches!(code, DeviceError::Failed | DeviceError::CreationFailed)
+    } else {
+        false
+    }
+}
+
+// SSIDs are arbitrary bytes, so replace any invalid UTF-8 for
+// display only. Not suitable for recreating the SSID, see get_ssid_str()
+pub fn get_ssid_display_str(ssid: &[u8]) -> String {
+    String::from_utf8_lossy(ssid).into_owned()
+}
+
+// Convert an error returned by NetworkManager when adding or activating a connection
+// into an `anyhow::Error`, attaching a more actionable message for common failures.
+// The original NetworkManager error is preserved as the source
+pub fn map_nm_error(err: glib::Error) -> anyhow::Error {
+    let hint = if let Some(code) = err.kind::<SettingsError>() {
+        get_settings_error_hint(code)
+    } else if let Some(code) = err.kind::<ConnectionError>() {
+        get_connection_error_hint(code)
+    } else if let Some(code) = err.kind::<ManagerError>() {
+        get_manager_error_hint(code)
+    } else if let Some(code) = err.kind::<DeviceError>() {
+        get_device_error_hint(code)
+    } else {
+        None
+    };
+
+    match hint {
+        Some(hint) => {
+            let msg = format!("{}: {}", hint, err.message());
+            anyhow::Error::new(err).context(msg)
+        }
+        None => anyhow::Error::new(err),
+    }
+}
+
+fn get_settings_error_hint(code: SettingsError) -> Option<&'static str> {
+    match code {
+        SettingsError::PermissionDenied => {
+            Some("Permission denied, run as root or with sufficient polkit authorization")
+        }
+        SettingsError::NotSupported => Some("No settings plugin supports adding this connection"),
+        SettingsError::InvalidConnection => Some("Invalid connection setting"),
+        SettingsError::ReadOnlyConnection => Some("Connection is read-only"),
+        SettingsError::UuidExists => Some("Connection with same UUID already exists"),
+        _ => None,
+    }
+}
+
+fn get_connection_error_hint(code: ConnectionError) -> Option<&'static str> {
+    match code {
+        ConnectionError::MissingSetting | ConnectionError::MissingProperty => {
+            Some("Connection is missing a required setting")
+        }
+        ConnectionError::InvalidSetting | ConnectionError::InvalidProperty => {
+            Some("Invalid connection setting")
+        }
+        _ => None,
+    }
+}
+
+fn get_manager_error_hint(code: ManagerError) -> Option<&'static str> {
+    match code {
+        ManagerError::PermissionDenied => {
+            Some("Permission denied, run as root or with sufficient polkit authorization")
+        }
+        ManagerError::UnknownConnection => Some("Connection does not exist"),
+        ManagerError::UnknownDevice => {
+            Some("Interface does not exist or is not managed by NetworkManager")
+        }
+        ManagerError::ConnectionNotAvailable => Some(
+            "Connection is not available on the interface, is the interface in use or disabled?",
+        ),
+        ManagerError::ConnectionAlreadyActive => Some("Connection is already active"),
+        ManagerError::DependencyFailed => {
+            Some("Connection depends on another connection which failed to activate")
+        }
+        _ => None,
+    }
+}
+
+fn get_device_error_hint(code: DeviceError) -> Option<&'static str> {
+    match code {
+        DeviceError::InvalidConnection => Some("Invalid connection setting"),
+        DeviceError::IncompatibleConnection => {
+            Some("Connection is incompatible with the interface type")
+        }
+        DeviceError::NotAllowed => Some("Interface does not allow this operation"),
+        _ => None,
+    }
+}
+
+pub fn get_device_state_str(state: DeviceState) -> &'static str {
+    match state {
+        DeviceState::Unmanaged => "unmanaged",
+        DeviceState::Unavailable => "unavailable",
+        DeviceState::Disconnected => "disconnected",
+        DeviceState::Prepare
+        | DeviceState::Config
+        | DeviceState::NeedAuth
+        | DeviceState::IpConfig
+        | DeviceState::IpCheck
+        | DeviceState::Secondaries => "activating",
+        DeviceState::Activated => "activated",
+        DeviceState::Deactivating => "deactivating",
+        DeviceState::Failed => "failed",
+        _ => "unknown",
+    }
+}
+
 pub fn get_connection_state_str(state: ActiveConnectionState) -> &'static str {
     match state {
         ActiveConnectionState::Activated => "activated",
@@ -756,6 +2441,77 @@ pub fn get_connection_state_str(state: ActiveConnectionState) -> &'static str {
     }
 }
 
+/// NetworkManager client for testing command logic without a running NetworkManager
+#[cfg(test)]
+pub mod mock {
+    use std::cell::RefCell;
+
+    use nm::*;
+
+    use super::{NmClient, NmFuture};
+
+    /// Client without any connections or devices. Calls which would change
+    /// NetworkManager state are recorded, e.g. "add_connection bond0", and fail
+    #[derive(Default)]
+    pub struct MockClient {
+        pub calls: RefCell<Vec<String>>,
+    }
+
+    impl MockClient {
+        fn fail<T: 'static>(&self, call: String) -> NmFuture<T> {
+            self.calls.borrow_mut().push(call);
+            Box::pin(async { Err(glib::Error::new(ClientError::Failed, "Mock client")) })
+        }
+    }
+
+    impl NmClient for MockClient {
+        fn connections(&self) -> Vec<RemoteConnection> {
+            vec![]
+        }
+
+        fn active_connections(&self) -> Vec<ActiveConnection> {
+            vec![]
+        }
+
+        fn devices(&self) -> Vec<Device> {
+            vec![]
+        }
+
+        fn device_by_iface(&self, _iface: &str) -> Option<Device> {
+            None
+        }
+
+        fn add_connection_future(
+            &self,
+            conn: &SimpleConnection,
+            _save_to_disk: bool,
+        ) -> NmFuture<RemoteConnection> {
+            self.fail(format!("add_connection {}", conn.id().unwrap_or_default()))
+        }
+
+        fn activate_connection_future(
+            &self,
+            conn: Option<&RemoteConnection>,
+            _device: Option<&Device>,
+            _specific_object: Option<&str>,
+        ) -> NmFuture<ActiveConnection> {
+            let id = conn.and_then(|c| c.id()).unwrap_or_default();
+            self.fail(format!("activate_connection {}", id))
+        }
+
+        fn deactivate_connection_future(&self, conn: &ActiveConnection) -> NmFuture<()> {
+            self.fail(format!(
+                "deactivate_connection {}",
+                conn.id().unwrap_or_default()
+            ))
+        }
+
+        fn version(&self) -> Option<String> {
+            None
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use std::str::FromStr;
@@ -1030,6 +2786,55 @@ mod test {
         assert!(!matching_wired_connection(&base_conn, &cmp_conn));
     }
 
+    #[test]
+    fn compare_wired_conns_master_slave_type() {
+        // 1. Same master and same slave type, expect pass
+        let base_conn = create_wired_slave_connection();
+        let cmp_conn = create_wired_slave_connection().upcast::<Connection>();
+        assert!(matching_wired_connection(&base_conn, &cmp_conn));
+
+        // 2. Same master but different slave type, expect fail
+        let base_conn = create_wired_slave_connection();
+        let cmp_conn = create_wired_slave_connection().upcast::<Connection>();
+
+        let s_conn = cmp_conn.setting_connection().unwrap();
+        s_conn.set_slave_type(Some(SETTING_BRIDGE_SETTING_NAME));
+        cmp_conn.add_setting(s_conn);
+
+        assert!(!matching_wired_connection(&base_conn, &cmp_conn));
+
+        // 3. Base uses custom empty string to match any master ifname,
+        //    but slave type still differs, expect fail
+        let base_conn = create_wired_slave_connection();
+        let cmp_conn = create_wired_slave_connection().upcast::<Connection>();
+
+        let s_conn = base_conn.setting_connection().unwrap();
+        s_conn.set_master(Some(""));
+        base_conn.add_setting(s_conn);
+
+        let s_conn = cmp_conn.setting_connection().unwrap();
+        s_conn.set_master(Some("other_master"));
+        s_conn.set_slave_type(Some(SETTING_BRIDGE_SETTING_NAME));
+        cmp_conn.add_setting(s_conn);
+
+        assert!(!matching_wired_connection(&base_conn, &cmp_conn));
+
+        // 4. Base uses custom empty string with different compare master
+        //    but same slave type, expect pass
+        let base_conn = create_wired_slave_connection();
+        let cmp_conn = create_wired_slave_connection().upcast::<Connection>();
+
+        let s_conn = base_conn.setting_connection().unwrap();
+        s_conn.set_master(Some(""));
+        base_conn.add_setting(s_conn);
+
+        let s_conn = cmp_conn.setting_connection().unwrap();
+        s_conn.set_master(Some("other_master"));
+        cmp_conn.add_setting(s_conn);
+
+        assert!(matching_wired_connection(&base_conn, &cmp_conn));
+    }
+
     #[test]
     fn compare_wifi_conns_wireless_settings() {
         // 1. All wifi connection fields same, expect pass
@@ -1096,6 +2901,52 @@ mod test {
         assert!(!matching_wifi_connection(&base_conn, &cmp_conn));
     }
 
+    #[test]
+    fn compare_wifi_any_mode() {
+        // 1. No base mode, should match both station and access point connections
+        let base_conn = create_wifi_match_connection(TEST_SSID, Some(TEST_IFNAME)).unwrap();
+        let cmp_conn = create_ap_connection().upcast::<Connection>();
+        assert!(matching_wifi_connection(&base_conn, &cmp_conn));
+
+        let cmp_conn = create_sta_connection().upcast::<Connection>();
+        assert!(matching_wifi_connection(&base_conn, &cmp_conn));
+
+        // 2. No base interface, should still match
+        let base_conn = create_wifi_match_connection(TEST_SSID, None).unwrap();
+        assert!(matching_wifi_connection(&base_conn, &cmp_conn));
+
+        // 3. Different SSID, should fail
+        let base_conn = create_wifi_match_connection("other_ssid", None).unwrap();
+        assert!(!matching_wifi_connection(&base_conn, &cmp_conn));
+    }
+
+    #[test]
+    fn delete_missing_wifi_connection() {
+        let client = mock::MockClient::default();
+
+        let res = glib::MainContext::default().block_on(delete_wifi_connection(
+            &client, TEST_SSID, None, None, true, false,
+        ));
+        assert!(res.is_err());
+        assert!(client.calls.borrow().is_empty());
+    }
+
+    #[test]
+    fn deactivate_idle_iface() {
+        let client = mock::MockClient::default();
+
+        let res = glib::MainContext::default().block_on(deactivate_connections_on_iface(
+            &client,
+            TEST_IFNAME,
+            DeviceType::Wifi,
+            true,
+            false,
+            false,
+        ));
+        assert_eq!(res.unwrap(), 0);
+        assert!(client.calls.borrow().is_empty());
+    }
+
     #[test]
     fn compare_wifi_ssid() {
         // 1. No SSID, should pass as matching function
@@ -1129,4 +2980,612 @@ mod test {
 
         assert!(!matching_wifi_connection(&base_conn, &cmp_conn));
     }
+
+    #[test]
+    fn nm_error_hints() {
+        // Common failures map to a hint
+        assert!(get_settings_error_hint(SettingsError::PermissionDenied).is_some());
+        assert!(get_settings_error_hint(SettingsError::UuidExists).is_some());
+        assert!(get_connection_error_hint(ConnectionError::InvalidProperty).is_some());
+        assert!(get_connection_error_hint(ConnectionError::MissingSetting).is_some());
+        assert!(get_manager_error_hint(ManagerError::UnknownDevice).is_some());
+        assert!(get_manager_error_hint(ManagerError::ConnectionNotAvailable).is_some());
+        assert!(get_device_error_hint(DeviceError::IncompatibleConnection).is_some());
+
+        // Generic failures have nothing more useful to say than the original error
+        assert!(get_settings_error_hint(SettingsError::Failed).is_none());
+        assert!(get_connection_error_hint(ConnectionError::Failed).is_none());
+        assert!(get_manager_error_hint(ManagerError::Failed).is_none());
+        assert!(get_device_error_hint(DeviceError::Failed).is_none());
+    }
+
+    #[test]
+    fn ssid_display_str() {
+        assert_eq!(get_ssid_display_str(b"test_ssid"), "test_ssid");
+        assert_eq!(get_ssid_display_str("café".as_bytes()), "café");
+
+        // Invalid UTF-8 replaced rather than dropped
+        assert_eq!(get_ssid_display_str(b"test\xffssid"), "test\u{fffd}ssid");
+    }
+
+    #[test]
+    fn retry_delay() {
+        let retry = RetryPolicy {
+            retries: 3,
+            delay: Duration::from_millis(100),
+        };
+        assert_eq!(retry.retry_delay(0), Duration::from_millis(100));
+        assert_eq!(retry.retry_delay(1), Duration::from_millis(200));
+        assert_eq!(retry.retry_delay(2), Duration::from_millis(400));
+
+        // Saturates rather than overflowing
+        let retry = RetryPolicy {
+            retries: 3,
+            delay: Duration::MAX,
+        };
+        assert_eq!(retry.retry_delay(1), Duration::MAX);
+    }
+
+    #[test]
+    fn transient_nm_error() {
+        let err = glib::Error::new(ManagerError::ConnectionNotAvailable, "Device unavailable");
+        assert!(is_transient_nm_error(&err));
+        let err = glib::Error::new(DeviceError::Failed, "Activation failed");
+        assert!(is_transient_nm_error(&err));
+
+        // Retrying won't fix the connection or permissions
+        let err = glib::Error::new(DeviceError::IncompatibleConnection, "Incompatible");
+        assert!(!is_transient_nm_error(&err));
+        let err = glib::Error::new(ManagerError::PermissionDenied, "Not authorized");
+        assert!(!is_transient_nm_error(&err));
+        let err = glib::Error::new(SettingsError::InvalidConnection, "Invalid");
+        assert!(!is_transient_nm_error(&err));
+    }
+
+    #[test]
+    fn map_nm_error_keeps_source() {
+        let err = glib::Error::new(ManagerError::UnknownDevice, "No device found for \"wlan9\"");
+        let err = map_nm_error(err);
+
+        assert!(err.to_string().contains("not managed by NetworkManager"));
+        assert!(err.to_string().contains("wlan9"));
+        assert!(err.downcast_ref::<glib::Error>().is_some());
+    }
+
+    #[test]
+    fn compare_wifi_bssid() {
+        // 1. No base BSSID, should pass as roaming connection
+        //    matches regardless of compared connection BSSID
+        let base_conn = create_sta_connection();
+        let cmp_conn = create_sta_connection().upcast::<Connection>();
+
+        let s_wireless = cmp_conn.setting_wireless().unwrap();
+        s_wireless.set_bssid(Some("00:11:22:33:44:55"));
+        cmp_conn.add_setting(s_wireless);
+
+        assert!(matching_wifi_connection(&base_conn, &cmp_conn));
+
+        // 2. Same BSSID, should pass
+        let base_conn = create_sta_connection();
+        let cmp_conn = create_sta_connection().upcast::<Connection>();
+
+        let s_wireless = base_conn.setting_wireless().unwrap();
+        s_wireless.set_bssid(Some("00:11:22:33:44:55"));
+        base_conn.add_setting(s_wireless);
+
+        let s_wireless = cmp_conn.setting_wireless().unwrap();
+        s_wireless.set_bssid(Some("00:11:22:33:44:55"));
+        cmp_conn.add_setting(s_wireless);
+
+        assert!(matching_wifi_connection(&base_conn, &cmp_conn));
+
+        // 3. Base BSSID but no compare BSSID, should fail
+        let base_conn = create_sta_connection();
+        let cmp_conn = create_sta_connection().upcast::<Connection>();
+
+        let s_wireless = base_conn.setting_wireless().unwrap();
+        s_wireless.set_bssid(Some("00:11:22:33:44:55"));
+        base_conn.add_setting(s_wireless);
+
+        assert!(!matching_wifi_connection(&base_conn, &cmp_conn));
+
+        // 4. Different BSSID, should fail
+        let base_conn = create_sta_connection();
+        let cmp_conn = create_sta_connection().upcast::<Connection>();
+
+        let s_wireless = base_conn.setting_wireless().unwrap();
+        s_wireless.set_bssid(Some("00:11:22:33:44:55"));
+        base_conn.add_setting(s_wireless);
+
+        let s_wireless = cmp_conn.setting_wireless().unwrap();
+        s_wireless.set_bssid(Some("66:77:88:99:aa:bb"));
+        cmp_conn.add_setting(s_wireless);
+
+        assert!(!matching_wifi_connection(&base_conn, &cmp_conn));
+    }
+
+    #[test]
+    fn compare_wifi_security() {
+        // 1. Same key management, should pass
+        let base_conn = create_ap_connection();
+        let cmp_conn = create_ap_connection().upcast::<Connection>();
+
+        assert!(matching_wifi_connection(&base_conn, &cmp_conn));
+
+        // 2. Secured base, open compared connection, should fail
+        let base_conn = create_ap_connection();
+        let cmp_conn = create_base_connection();
+
+        let s_connection = cmp_conn.setting_connection().unwrap();
+        s_connection.set_type(Some(SETTING_WIRELESS_SETTING_NAME));
+
+        let s_wireless = SettingWireless::new();
+        s_wireless.set_ssid(Some(&(TEST_SSID.as_bytes().into())));
+        s_wireless.set_mode(Some(SETTING_WIRELESS_MODE_AP));
+        cmp_conn.add_setting(s_wireless);
+        let cmp_conn = cmp_conn.upcast::<Connection>();
+
+        assert!(!matching_wifi_connection(&base_conn, &cmp_conn));
+
+        // 3. WPA-PSK base, SAE compared connection, should fail
+        let base_conn = create_ap_connection();
+        let cmp_conn = create_ap_connection().upcast::<Connection>();
+
+        let s_wireless_security = cmp_conn.setting_wireless_security().unwrap();
+        s_wireless_security.set_key_mgmt(Some("sae"));
+        cmp_conn.add_setting(s_wireless_security);
+
+        assert!(!matching_wifi_connection(&base_conn, &cmp_conn));
+    }
+
+    // Returns first result from sequence of connection states, None if never finished waiting
+    fn drive_state_wait(
+        states: &[ActiveConnectionState],
+        target: ActiveConnectionState,
+    ) -> Option<Result<()>> {
+        states.iter().find_map(|state| {
+            get_state_wait_result(*state, ActiveConnectionStateReason::None, target)
+        })
+    }
+
+    #[test]
+    fn state_wait_sequence() {
+        use ActiveConnectionState::*;
+
+        // 1. Normal activation, expect pass
+        let res = drive_state_wait(&[Activating, Activating, Activated], Activated);
+        assert!(res.unwrap().is_ok());
+
+        // 2. Normal deactivation, expect pass
+        let res = drive_state_wait(&[Deactivating, Deactivated], Deactivated);
+        assert!(res.unwrap().is_ok());
+
+        // 3. Failed activation, expect fail
+        let res = drive_state_wait(&[Activating, Deactivating, Deactivated], Activated);
+        assert!(res.unwrap().is_err());
+
+        // 4. Reactivated while waiting for deactivation, expect fail
+        let res = drive_state_wait(&[Deactivating, Activating], Deactivated);
+        assert!(res.unwrap().is_err());
+
+        // 5. Still transitioning, expect to keep waiting
+        let res = drive_state_wait(&[Activating, Activating], Activated);
+        assert!(res.is_none());
+
+        // 6. Target state which connection never settles in, expect fail
+        let res = drive_state_wait(&[Activating], Activating);
+        assert!(res.unwrap().is_err());
+    }
+
+    #[test]
+    fn state_wait_reason() {
+        let res = get_state_wait_result(
+            ActiveConnectionState::Deactivating,
+            ActiveConnectionStateReason::NoSecrets,
+            ActiveConnectionState::Activated,
+        );
+        let err = res.unwrap().unwrap_err().to_string();
+        assert!(err.contains("deactivating"));
+        assert!(err.contains("authentication failed"));
+
+        let res = get_state_wait_result(
+            ActiveConnectionState::Deactivated,
+            ActiveConnectionStateReason::IpConfigInvalid,
+            ActiveConnectionState::Activated,
+        );
+        let err = res.unwrap().unwrap_err().to_string();
+        assert!(err.contains("IP config unavailable"));
+    }
+
+    #[test]
+    fn key_mgmt_requires_password() {
+        assert!(validate_key_mgmt(None, None).is_ok());
+        assert!(validate_key_mgmt(Some("test_password"), None).is_ok());
+        assert!(validate_key_mgmt(Some("test_password"), Some(KeyMgmt::WpaPsk)).is_ok());
+        assert!(validate_key_mgmt(Some("test_password"), Some(KeyMgmt::Sae)).is_ok());
+
+        assert!(validate_key_mgmt(None, Some(KeyMgmt::WpaPsk)).is_err());
+        assert!(validate_key_mgmt(None, Some(KeyMgmt::Sae)).is_err());
+    }
+
+    #[test]
+    fn stable_id() {
+        assert!(validate_stable_id("${CONNECTION}").is_ok());
+        assert!(validate_stable_id("${CONNECTION}/${BOOT}").is_ok());
+        assert!(validate_stable_id("my-stable-id").is_ok());
+
+        assert!(validate_stable_id("").is_err());
+        assert!(validate_stable_id("  ").is_err());
+    }
+
+    #[test]
+    fn powersave() {
+        assert_eq!(get_powersave(get_powersave_value(true)), Some(true));
+        assert_eq!(get_powersave(get_powersave_value(false)), Some(false));
+
+        // Default and ignore leave power saving to global configuration
+        assert_eq!(get_powersave(0), None);
+        assert_eq!(get_powersave(1), None);
+    }
+
+    #[test]
+    fn zone() {
+        assert!(validate_zone("public").is_ok());
+        assert!(validate_zone("trusted").is_ok());
+
+        assert!(validate_zone("").is_err());
+        assert!(validate_zone("  ").is_err());
+    }
+
+    #[test]
+    fn ip4_gateway_dns() {
+        let dns = vec!["192.0.2.53".to_string(), "198.51.100.53".to_string()];
+
+        assert!(validate_ip4_gateway_dns(None, &[], false).is_ok());
+        assert!(validate_ip4_gateway_dns(Some("192.0.2.254"), &dns, true).is_ok());
+
+        // DNS servers usable with DHCP too
+        assert!(validate_ip4_gateway_dns(None, &dns, false).is_ok());
+
+        // Gateway without static address
+        assert!(validate_ip4_gateway_dns(Some("192.0.2.254"), &[], false).is_err());
+
+        // Invalid addresses
+        assert!(validate_ip4_gateway_dns(Some("192.0.2.254/24"), &[], true).is_err());
+        assert!(validate_ip4_gateway_dns(Some("2001:db8::1"), &[], true).is_err());
+        assert!(validate_ip4_gateway_dns(None, &["dns.example".to_string()], true).is_err());
+    }
+
+    #[test]
+    fn ip4_dhcp_timeout() {
+        assert!(validate_ip4_dhcp_timeout(None, true).is_ok());
+        assert!(validate_ip4_dhcp_timeout(Some(0), false).is_ok());
+        assert!(validate_ip4_dhcp_timeout(Some(30), false).is_ok());
+
+        // Negative, or no DHCP with a static address
+        assert!(validate_ip4_dhcp_timeout(Some(-1), false).is_err());
+        assert!(validate_ip4_dhcp_timeout(Some(30), true).is_err());
+    }
+
+    #[test]
+    fn ip4_may_fail() {
+        assert!(validate_ip4_may_fail(None, Some(30)).is_ok());
+        assert!(validate_ip4_may_fail(Some(false), Some(30)).is_ok());
+        assert!(validate_ip4_may_fail(Some(true), None).is_ok());
+        assert!(validate_ip4_may_fail(Some(true), Some(0)).is_ok());
+
+        // Timeout would never fail activation
+        assert!(validate_ip4_may_fail(Some(true), Some(30)).is_err());
+    }
+
+    #[test]
+    fn delete_wait_sequence() {
+        use ActiveConnectionState::*;
+
+        let timeout_err = || -> Result<()> {
+            Err(StateWaitTimeout {
+                target: Deactivated,
+                timeout: DELETE_DEACTIVATE_TIMEOUT,
+            }
+            .into())
+        };
+
+        // 1. Deactivates in time, expect delete
+        let res = drive_state_wait(&[Deactivating, Deactivated], Deactivated).unwrap();
+        assert!(get_delete_wait_result(res).is_ok());
+
+        // 2. Slow deactivation, still deactivating when timeout
+        //    expires. Expect delete anyway
+        let res = drive_state_wait(&[Deactivating, Deactivating], Deactivated);
+        assert!(res.is_none());
+        assert!(get_delete_wait_result(timeout_err()).is_ok());
+
+        // 3. Reactivated while waiting, expect fail
+        let res = drive_state_wait(&[Deactivating, Activating], Deactivated).unwrap();
+        assert!(get_delete_wait_result(res).is_err());
+    }
+
+    #[test]
+    fn active_guard() {
+        assert!(check_active_guard(ActiveGuard::Active, true));
+        assert!(!check_active_guard(ActiveGuard::Active, false));
+
+        assert!(check_active_guard(ActiveGuard::Inactive, false));
+        assert!(!check_active_guard(ActiveGuard::Inactive, true));
+    }
+
+    #[test]
+    fn ip4_method() {
+        assert!(validate_ip4_method(None, false).is_ok());
+        assert!(validate_ip4_method(None, true).is_ok());
+
+        assert!(validate_ip4_method(Some(Ip4Method::Manual), true).is_ok());
+        assert!(validate_ip4_method(Some(Ip4Method::Manual), false).is_err());
+
+        assert!(validate_ip4_method(Some(Ip4Method::Shared), true).is_ok());
+        assert!(validate_ip4_method(Some(Ip4Method::Shared), false).is_ok());
+        assert!(validate_ip4_method(Some(Ip4Method::LinkLocal), true).is_ok());
+
+        assert!(validate_ip4_method(Some(Ip4Method::Disabled), false).is_ok());
+        assert!(validate_ip4_method(Some(Ip4Method::Disabled), true).is_err());
+
+        for method in Ip4Method::value_variants() {
+            assert_eq!(get_ip4_method(get_ip4_method_str(*method)), Some(*method));
+        }
+        assert_eq!(get_ip4_method("unknown"), None);
+    }
+
+    #[test]
+    fn ip4_overlap() {
+        let net = |s: &str| Ipv4Net::from_str(s).unwrap();
+
+        // Same subnet, different hosts
+        assert!(ip4_nets_overlap(
+            &net("192.0.2.1/24"),
+            &net("192.0.2.10/24")
+        ));
+
+        // One subnet contains the other, either way around
+        assert!(ip4_nets_overlap(
+            &net("192.0.2.1/24"),
+            &net("192.0.2.129/25")
+        ));
+        assert!(ip4_nets_overlap(
+            &net("192.0.2.129/25"),
+            &net("192.0.2.1/24")
+        ));
+        assert!(ip4_nets_overlap(&net("10.1.2.3/24"), &net("10.0.0.1/8")));
+
+        // Disjoint subnets
+        assert!(!ip4_nets_overlap(
+            &net("192.0.2.1/24"),
+            &net("198.51.100.1/24")
+        ));
+        assert!(!ip4_nets_overlap(
+            &net("192.0.2.1/25"),
+            &net("192.0.2.129/25")
+        ));
+    }
+
+    #[test]
+    fn preempt() {
+        // Preempting by default, conflicting connections deactivated
+        assert!(check_preempt(false, TEST_IFNAME).is_ok());
+
+        // Preemption disabled, creation aborted
+        let err = check_preempt(true, TEST_IFNAME).unwrap_err();
+        assert!(err.to_string().contains(TEST_IFNAME));
+    }
+
+    // Poll future once. Futures used here never need waking, so a single poll suffices
+    fn poll_once<F: Future>(fut: F) -> Poll<F::Output> {
+        use std::task::{Context, Waker};
+
+        let mut cx = Context::from_waker(Waker::noop());
+        pin!(fut).poll(&mut cx)
+    }
+
+    #[test]
+    fn connect_timeout_race() {
+        use std::future::{pending, ready};
+
+        // 1. Client created before timeout, expect client
+        let res = poll_once(race_timeout(ready(1), pending::<()>()));
+        assert_eq!(res, Poll::Ready(Some(1)));
+
+        // 2. Timeout expires first, expect timeout
+        let res = poll_once(race_timeout(pending::<i32>(), ready(())));
+        assert_eq!(res, Poll::Ready(None));
+
+        // 3. Both ready at once, expect client preferred over timeout
+        let res = poll_once(race_timeout(ready(1), ready(())));
+        assert_eq!(res, Poll::Ready(Some(1)));
+
+        // 4. Neither ready, expect to keep waiting
+        let res = poll_once(race_timeout(pending::<i32>(), pending::<()>()));
+        assert_eq!(res, Poll::Pending);
+    }
+
+    #[test]
+    fn device_selection() {
+        let candidate = |ifname: &str, state, signal| DeviceCandidate {
+            ifname: ifname.to_string(),
+            state,
+            signal,
+        };
+        let select =
+            |candidates: &[DeviceCandidate]| select_device(candidates).map(|c| c.ifname.clone());
+
+        // 1. Strongest signal preferred
+        let candidates = [
+            candidate("wlan0", DeviceState::Disconnected, Some(40)),
+            candidate("wlan1", DeviceState::Disconnected, Some(80)),
+        ];
+        assert_eq!(select(&candidates), Some("wlan1".to_string()));
+
+        // 2. Idle device preferred over stronger in-use device
+        let candidates = [
+            candidate("wlan0", DeviceState::Activated, Some(80)),
+            candidate("wlan1", DeviceState::Disconnected, Some(40)),
+        ];
+        assert_eq!(select(&candidates), Some("wlan1".to_string()));
+
+        // 3. Device which can see network preferred over idle device which can't
+        let candidates = [
+            candidate("wlan0", DeviceState::Disconnected, None),
+            candidate("wlan1", DeviceState::Activated, Some(10)),
+        ];
+        assert_eq!(select(&candidates), Some("wlan1".to_string()));
+
+        // 4. Unusable devices never chosen
+        let candidates = [
+            candidate("wlan0", DeviceState::Unmanaged, Some(80)),
+            candidate("wlan1", DeviceState::Unavailable, Some(80)),
+        ];
+        assert_eq!(select(&candidates), None);
+
+        // 5. Ties go to earliest device
+        let candidates = [
+            candidate("wlan0", DeviceState::Disconnected, Some(50)),
+            candidate("wlan1", DeviceState::Disconnected, Some(50)),
+        ];
+        assert_eq!(select(&candidates), Some("wlan0".to_string()));
+    }
+
+    fn setting_entry(setting: &str, key: &str, value: &str) -> SettingEntry {
+        SettingEntry {
+            setting: setting.to_string(),
+            key: key.to_string(),
+            value: value.to_string(),
+        }
+    }
+
+    #[test]
+    fn setting_entries_str() {
+        let entries = vec![
+            setting_entry("connection", "id", "'test_ssid'"),
+            setting_entry("connection", "type", "'802-11-wireless'"),
+            setting_entry("802-11-wireless-security", "key-mgmt", "'wpa-psk'"),
+            setting_entry("802-11-wireless-security", "psk", "'test_password'"),
+        ];
+        let public_entries: Vec<SettingEntry> = entries[..3].to_vec();
+
+        let s = get_setting_entries_str(&redact_secrets(entries, &public_entries));
+        assert_eq!(
+            s,
+            "connection:\n  id: 'test_ssid'\n  type: '802-11-wireless'\n\
+             802-11-wireless-security:\n  key-mgmt: 'wpa-psk'\n  psk: <redacted>\n"
+        );
+        assert!(!s.contains("test_password"));
+    }
+
+    #[test]
+    fn explain_bond_and_access_point() {
+        use crate::access_point::{create_access_point_connection, AccessPointOpts};
+        use crate::bond::{create_bond_connection, BondOpts};
+
+        let opts: BondOpts = serde_yaml::from_str(
+            "
+            bond_interface: bond0
+            bond_mode: !ActiveBackup
+            ",
+        )
+        .unwrap();
+        let conn = create_bond_connection(&opts).unwrap();
+        let entries = get_setting_entries(&conn, ConnectionSerializationFlags::ALL).unwrap();
+        assert!(entries.contains(&setting_entry("connection", "interface-name", "'bond0'")));
+        assert!(entries.iter().any(|e| e.setting == "bond"
+            && e.key == "options"
+            && e.value.contains("active-backup")));
+
+        let opts = AccessPointOpts {
+            wireless_ifname: Some("wlan0".to_string()),
+            ssid: Some("test_ssid".to_string()),
+            password: Some("test_password".to_string()),
+            ..Default::default()
+        };
+        let conn = create_access_point_connection(&opts).unwrap();
+        let entries = get_setting_entries(&conn, ConnectionSerializationFlags::ALL).unwrap();
+        assert!(entries.contains(&setting_entry("802-11-wireless", "mode", "'ap'")));
+        assert!(entries.contains(&setting_entry(
+            "802-11-wireless-security",
+            "key-mgmt",
+            "'wpa-psk'"
+        )));
+    }
+
+    #[test]
+    fn slave_device_setting() {
+        // Wired settings only added by NetworkManager when normalizing, so add explicitly
+        let wired_conn = create_wired_slave_connection();
+        wired_conn.add_setting(SettingWired::new());
+        assert!(has_slave_device_setting(&wired_conn, DeviceType::Ethernet));
+        assert!(!has_slave_device_setting(&wired_conn, DeviceType::Wifi));
+
+        let wireless_conn = create_wifi_connection();
+        assert!(has_slave_device_setting(&wireless_conn, DeviceType::Wifi));
+        assert!(!has_slave_device_setting(
+            &wireless_conn,
+            DeviceType::Ethernet
+        ));
+
+        // No dedicated settings, so any slave connection matches
+        assert!(has_slave_device_setting(&wireless_conn, DeviceType::Team));
+    }
+
+    #[test]
+    fn ip4_route() {
+        let route = parse_ip4_route("198.51.100.0/24 via 192.0.2.254").unwrap();
+        assert_eq!(route.dest, Ipv4Net::from_str("198.51.100.0/24").unwrap());
+        assert_eq!(route.gateway, Ipv4Addr::new(192, 0, 2, 254));
+        assert_eq!(route.metric, None);
+
+        let route = parse_ip4_route("0.0.0.0/0  via 192.0.2.1 100").unwrap();
+        assert_eq!(route.metric, Some(100));
+    }
+
+    #[test]
+    fn invalid_ip4_route() {
+        let get_err = |route: &str| parse_ip4_route(route).unwrap_err().to_string();
+
+        // Bad entry always identified
+        let err = get_err("198.51.100.0/24 192.0.2.254");
+        assert!(err.contains("\"198.51.100.0/24 192.0.2.254\""));
+        assert!(err.contains("expected"));
+
+        assert!(get_err("198.51.100.0 via 192.0.2.254").contains("invalid destination"));
+        assert!(get_err("198.51.100.1/24 via 192.0.2.254").contains("198.51.100.0/24"));
+        assert!(get_err("198.51.100.0/24 via 192.0.2").contains("invalid gateway"));
+        assert!(get_err("198.51.100.0/24 via 192.0.2.254 -1").contains("invalid metric"));
+        assert!(get_err("198.51.100.0/24 via 192.0.2.254 1 2").contains("expected"));
+    }
+
+    #[test]
+    fn route_metric() {
+        assert!(validate_route_metric(AUTO_ROUTE_METRIC).is_ok());
+        assert!(validate_route_metric(0).is_ok());
+        assert!(validate_route_metric(u32::MAX as i64).is_ok());
+        assert!(validate_route_metric(-2).is_err());
+        assert!(validate_route_metric(u32::MAX as i64 + 1).is_err());
+
+        assert_eq!(get_route_metric(AUTO_ROUTE_METRIC), None);
+        assert_eq!(get_route_metric(100), Some(100));
+    }
+
+    #[test]
+    fn device_type_setting_name() {
+        assert_eq!(
+            get_device_type_setting_name(DeviceType::Bond),
+            Some(SETTING_BOND_SETTING_NAME)
+        );
+        assert_eq!(
+            get_device_type_setting_name(DeviceType::Ethernet),
+            Some(SETTING_WIRED_SETTING_NAME)
+        );
+        assert_eq!(
+            get_device_type_setting_name(DeviceType::Wifi),
+            Some(SETTING_WIRELESS_SETTING_NAME)
+        );
+        assert_eq!(get_device_type_setting_name(DeviceType::Bridge), None);
+    }
 }
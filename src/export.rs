@@ -0,0 +1,953 @@
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+use std::str::FromStr;
+
+use anyhow::{anyhow, Result};
+use ipnet::Ipv6Net;
+use nm::*;
+use serde::{Deserialize, Serialize};
+use serde_yaml::Value;
+use tracing::{info, instrument, warn};
+
+use crate::{
+    access_point::{create_access_point_connection, export_access_point_opts, AccessPointOpts},
+    bond::{export_bond_opts, get_replaced_bond_connections, BondOpts},
+    connection::*,
+    station::{create_sta_connection, export_station_opts, StationOpts},
+    status::get_static_routes,
+    util::read_config,
+};
+
+/// Fields only access point configs contain, distinguishing them from station configs
+const ACCESS_POINT_ONLY_FIELDS: [&str; 7] = [
+    "open",
+    "mode",
+    "band",
+    "channel_width",
+    "country",
+    "max_clients",
+    "client_isolation",
+];
+
+/// Single connection in an export bundle. Serialized as a tagged
+/// YAML document, e.g. "!Bond", so the type survives re-import
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
+pub enum ExportedConnection {
+    Bond(BondOpts),
+    AccessPoint(AccessPointOpts),
+    Station(StationOpts),
+}
+
+/// Options shared by every connection type, recovered from an existing connection
+#[derive(Default, PartialEq, Clone, Debug)]
+pub struct CommonOpts {
+    pub ip4_addrs: Vec<String>,
+    pub ip4_method: Option<Ip4Method>,
+    pub gateway: Option<String>,
+    pub dns: Vec<String>,
+    pub ip6_addr: Option<Ipv6Net>,
+    pub multi_connect: Option<MultiConnect>,
+    pub autoconnect: Option<bool>,
+    pub dns_over_tls: Option<DnsOverTls>,
+    pub stable_id: Option<String>,
+    pub zone: Option<String>,
+    pub id: Option<String>,
+    pub route_metric: Option<i64>,
+    pub ip4_may_fail: Option<bool>,
+    pub dhcp_timeout: Option<i32>,
+    pub dhcp_client_id: Option<String>,
+    pub dhcp_hostname: Option<String>,
+    pub routes: Vec<String>,
+}
+
+// Export every bond, access point, and station connection to a single multi-document
+// YAML bundle. Secrets are never exported, so passwords must be re-added before import
+#[instrument(skip(client), err)]
+pub fn export_all(client: &impl NmClient, out: Option<&str>) -> Result<()> {
+    let mut conns: Vec<ExportedConnection> = vec![];
+
+    for remote_conn in client.connections().into_iter() {
+        let conn = remote_conn.upcast::<Connection>();
+
+        let conn_type = match conn.connection_type() {
+            Some(t) => t,
+            None => {
+                warn!("Unable to get type for connection {}, skipping", conn);
+                continue;
+            }
+        };
+
+        let exported = match get_exported_connection(client, &conn, &conn_type) {
+            Some(e) => e,
+            None => continue,
+        };
+
+        match exported {
+            Ok(c) => conns.push(c),
+            Err(e) => warn!("Unable to export connection {}, skipping: {}", conn, e),
+        }
+    }
+
+    let bundle = get_export_bundle_str(&conns)?;
+    match out {
+        Some(path) => {
+            let mut file = File::create(path)?;
+            file.write_all(bundle.as_bytes())?;
+            info!("Exported {} connections to \"{}\"", conns.len(), path);
+        }
+        None => print!("{bundle}"),
+    }
+
+    Ok(())
+}
+
+// Export a single connection as YAML config usable with "--config". Secrets are
+// only included when requested, as they must be separately fetched from NetworkManager
+#[instrument(skip(client), err)]
+pub async fn export_connection(
+    client: &impl NmClient,
+    remote_conn: &RemoteConnection,
+    include_secrets: bool,
+) -> Result<()> {
+    let conn = remote_conn.clone().upcast::<Connection>();
+
+    let conn_type = match conn.connection_type() {
+        Some(t) => t,
+        None => return Err(anyhow!("Unable to get type for connection {}", conn)),
+    };
+
+    let mut exported = match get_exported_connection(client, &conn, &conn_type) {
+        Some(e) => e?,
+        None => {
+            return Err(anyhow!(
+                "Connection {} is not a bond, access point, or station connection",
+                conn
+            ))
+        }
+    };
+
+    if include_secrets {
+        exported.apply_secrets(get_connection_secrets(remote_conn).await?);
+    }
+
+    print!("{}", exported.to_config_str()?);
+    Ok(())
+}
+
+// Recover options for connection types nutil manages. None for other connection
+// types, e.g. bond slaves, or wireless connections in an unsupported mode
+fn get_exported_connection(
+    client: &impl NmClient,
+    conn: &Connection,
+    conn_type: &str,
+) -> Option<Result<ExportedConnection>> {
+    match conn_type {
+        SETTING_BOND_SETTING_NAME => {
+            Some(export_bond_opts(client, conn).map(ExportedConnection::Bond))
+        }
+        SETTING_WIRELESS_SETTING_NAME => {
+            let mode = conn.setting_wireless().and_then(|s| s.mode());
+
+            match mode.as_deref() {
+                Some(SETTING_WIRELESS_MODE_AP) | Some(SETTING_WIRELESS_MODE_ADHOC) => {
+                    Some(export_access_point_opts(conn).map(ExportedConnection::AccessPoint))
+                }
+                Some(SETTING_WIRELESS_MODE_INFRA) => {
+                    Some(export_station_opts(conn).map(ExportedConnection::Station))
+                }
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Connection secrets, which NetworkManager only returns when explicitly requested
+#[derive(Default, PartialEq, Clone, Debug)]
+pub struct ConnectionSecrets {
+    pub psk: Option<String>,
+    pub eap_password: Option<String>,
+}
+
+// Request secrets of any security settings the connection has. Requires
+// permission to read secrets, which may mean running as root
+pub async fn get_connection_secrets(remote_conn: &RemoteConnection) -> Result<ConnectionSecrets> {
+    let mut secrets = ConnectionSecrets::default();
+
+    for setting_name in [
+        SETTING_WIRELESS_SECURITY_SETTING_NAME,
+        SETTING_802_1X_SETTING_NAME,
+    ] {
+        if remote_conn.setting_by_name(setting_name).is_none() {
+            continue;
+        }
+
+        // Only updates local copy of the connection, nothing committed
+        let setting_secrets = remote_conn
+            .secrets_future(setting_name)
+            .await
+            .map_err(map_nm_error)?;
+        remote_conn
+            .update_secrets(setting_name, &setting_secrets)
+            .map_err(map_nm_error)?;
+    }
+
+    secrets.psk = remote_conn
+        .setting_wireless_security()
+        .and_then(|s| s.psk())
+        .map(|p| p.to_string());
+    secrets.eap_password = remote_conn
+        .setting_802_1x()
+        .and_then(|s| s.password())
+        .map(|p| p.to_string());
+
+    Ok(secrets)
+}
+
+impl ExportedConnection {
+    pub fn apply_secrets(&mut self, secrets: ConnectionSecrets) {
+        match self {
+            ExportedConnection::Bond(_) => (),
+            ExportedConnection::AccessPoint(opts) => opts.password = secrets.psk,
+            ExportedConnection::Station(opts) => {
+                opts.password = secrets.psk;
+                opts.eap_password = secrets.eap_password;
+            }
+        }
+    }
+
+    // Untagged, unlike bundle documents, as config files hold a single connection type
+    pub fn to_config_str(&self) -> Result<String> {
+        let config = match self {
+            ExportedConnection::Bond(opts) => serde_yaml::to_string(opts)?,
+            ExportedConnection::AccessPoint(opts) => serde_yaml::to_string(opts)?,
+            ExportedConnection::Station(opts) => serde_yaml::to_string(opts)?,
+        };
+
+        Ok(config)
+    }
+}
+
+// Serialize connections as a multi-document YAML bundle, one document per connection
+pub fn get_export_bundle_str(conns: &[ExportedConnection]) -> Result<String> {
+    let mut bundle = String::new();
+
+    for conn in conns.iter() {
+        bundle.push_str("---\n");
+        bundle.push_str(&serde_yaml::to_string(conn)?);
+    }
+
+    Ok(bundle)
+}
+
+pub fn parse_export_bundle(bundle: &str) -> Result<Vec<ExportedConnection>> {
+    let mut conns: Vec<ExportedConnection> = vec![];
+
+    // Empty bundle still parses as a single (empty) document
+    if bundle.trim().is_empty() {
+        return Ok(conns);
+    }
+
+    for document in serde_yaml::Deserializer::from_str(bundle) {
+        conns.push(ExportedConnection::deserialize(document)?);
+    }
+
+    Ok(conns)
+}
+
+// Parse single-connection config, inferring connection type from its fields. Tagged
+// documents, e.g. "!AccessPoint" as in an export bundle, keep their tagged type.
+// Access point configs without any access point only fields (e.g. "open" or "band")
+// are otherwise indistinguishable from station configs, so must be tagged
+pub fn parse_config_file(config: &str) -> Result<ExportedConnection> {
+    let value: Value = serde_yaml::from_str(config)?;
+
+    let fields = match &value {
+        Value::Tagged(_) => return Ok(ExportedConnection::deserialize(value)?),
+        Value::Mapping(m) => m,
+        _ => return Err(anyhow!("Config file must be a mapping of fields to values")),
+    };
+
+    if fields.contains_key("bond_interface") {
+        return Ok(ExportedConnection::Bond(serde_yaml::from_value(value)?));
+    }
+
+    if !fields.contains_key("ssid") {
+        return Err(anyhow!(
+            "Unable to infer connection type, expected \"bond_interface\" or \"ssid\""
+        ));
+    }
+
+    let is_access_point = ACCESS_POINT_ONLY_FIELDS
+        .iter()
+        .any(|f| fields.contains_key(*f));
+    match is_access_point {
+        true => Ok(ExportedConnection::AccessPoint(serde_yaml::from_value(
+            value,
+        )?)),
+        false => Ok(ExportedConnection::Station(serde_yaml::from_value(value)?)),
+    }
+}
+
+// Read and parse every YAML config file in a directory, in the order their connections
+// should be created. Unparseable files come first, so failing fast creates nothing,
+// then bonds before wireless connections. Otherwise ordered by file name. TOML config
+// files are unsupported, so fail like unparseable files rather than being skipped
+pub fn read_config_dir(dir: &str) -> Result<Vec<(String, Result<ExportedConnection>)>> {
+    let mut paths: Vec<String> = vec![];
+
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if !path.is_file() {
+            continue;
+        }
+
+        if !is_yaml_config(&path) && !is_toml_config(&path) {
+            warn!(
+                "Skipping \"{}\", only YAML config files are supported",
+                path.display()
+            );
+            continue;
+        }
+        paths.push(path.to_string_lossy().to_string());
+    }
+    paths.sort();
+
+    let mut configs: Vec<(String, Result<ExportedConnection>)> = paths
+        .into_iter()
+        .map(|path| {
+            let conn = match is_toml_config(Path::new(&path)) {
+                true => Err(anyhow!("TOML config files not supported, convert to YAML")),
+                false => read_config(&path).and_then(|c| parse_config_file(&c)),
+            };
+            (path, conn)
+        })
+        .collect();
+    configs.sort_by_key(|(_, conn)| get_creation_rank(conn));
+
+    Ok(configs)
+}
+
+fn is_yaml_config(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|e| e.eq_ignore_ascii_case("yaml") || e.eq_ignore_ascii_case("yml"))
+}
+
+fn is_toml_config(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|e| e.eq_ignore_ascii_case("toml"))
+}
+
+// Bonds may back other connections, so are created first
+fn get_creation_rank(conn: &Result<ExportedConnection>) -> u8 {
+    match conn {
+        Err(_) => 0,
+        Ok(ExportedConnection::Bond(_)) => 1,
+        Ok(ExportedConnection::AccessPoint(_)) => 2,
+        Ok(ExportedConnection::Station(_)) => 3,
+    }
+}
+
+/// Connections created and deleted while applying a batch of configs, so the whole
+/// batch can be rolled back. Connections are keyed by D-Bus object path rather than
+/// UUID, as a connection replaced with "--force" keeps its UUID but not its path
+#[derive(Debug)]
+pub struct ApplyJournal<S = SimpleConnection> {
+    /// Paths of connections created during the batch, in creation order
+    created: Vec<String>,
+
+    /// Paths and snapshots of connections which existed before the
+    /// batch and were deleted during it, in deletion order
+    deleted: Vec<(String, S)>,
+
+    /// Paths of connections which existed and were active before the batch and were
+    /// deactivated during it, e.g. preempted by a new connection on the same device
+    deactivated: Vec<String>,
+}
+
+/// Connection state before one step of a batch, compared with the state after it
+#[derive(Debug)]
+pub struct StepState<S = SimpleConnection> {
+    /// Paths of every connection
+    pub paths: Vec<String>,
+
+    /// Paths of every active connection
+    pub active: Vec<String>,
+
+    /// Paths and snapshots of connections the step may delete
+    pub snapshots: Vec<(String, S)>,
+}
+
+impl<S> Default for ApplyJournal<S> {
+    fn default() -> Self {
+        ApplyJournal {
+            created: vec![],
+            deleted: vec![],
+            deactivated: vec![],
+        }
+    }
+}
+
+impl<S> ApplyJournal<S> {
+    // Record connections created, deleted, and deactivated by one step of the batch,
+    // given state before the step and paths of every (active) connection after it
+    pub fn record_step(&mut self, before: StepState<S>, after: &[String], after_active: &[String]) {
+        for path in after.iter() {
+            if !before.paths.contains(path) {
+                self.created.push(path.clone());
+            }
+        }
+
+        // Connections created during the batch need no reactivation, so check before
+        // forgetting deleted ones below. Deleted connections are included, so
+        // restored ones are reactivated too
+        for path in before.active.into_iter() {
+            if !after_active.contains(&path)
+                && !self.created.contains(&path)
+                && !self.deactivated.contains(&path)
+            {
+                self.deactivated.push(path);
+            }
+        }
+
+        let mut snapshots = before.snapshots;
+        for path in before.paths.iter() {
+            if after.contains(path) {
+                continue;
+            }
+
+            // Created earlier in the batch, so nothing to restore once deleted
+            if let Some(i) = self.created.iter().position(|p| p == path) {
+                self.created.remove(i);
+                continue;
+            }
+
+            match snapshots.iter().position(|(p, _)| p == path) {
+                Some(i) => self.deleted.push(snapshots.remove(i)),
+                None => warn!(
+                    "Connection \"{}\" deleted without a snapshot, unable to restore",
+                    path
+                ),
+            }
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.created.is_empty() && self.deleted.is_empty() && self.deactivated.is_empty()
+    }
+
+    // Paths of created connections in the order they should be deleted, newest first
+    pub fn created_rollback_order(&self) -> Vec<&str> {
+        self.created.iter().rev().map(|p| p.as_str()).collect()
+    }
+
+    // Paths and snapshots of deleted connections in the order they
+    // should be restored, newest first
+    pub fn deleted_rollback_order(&self) -> Vec<(&str, &S)> {
+        self.deleted
+            .iter()
+            .rev()
+            .map(|(p, s)| (p.as_str(), s))
+            .collect()
+    }
+
+    // Paths of deactivated connections in the order they should be reactivated,
+    // newest first. Includes deleted connections, which are reactivated once restored
+    pub fn deactivated_rollback_order(&self) -> Vec<&str> {
+        self.deactivated.iter().rev().map(|p| p.as_str()).collect()
+    }
+
+    pub fn was_deactivated(&self, path: &str) -> bool {
+        self.deactivated.iter().any(|p| p == path)
+    }
+}
+
+// Paths of every connection NetworkManager knows about
+pub fn get_connection_paths(client: &impl NmClient) -> Vec<String> {
+    client
+        .connections()
+        .iter()
+        .filter_map(|c| c.path().map(|p| p.to_string()))
+        .collect()
+}
+
+// Paths of every active connection's underlying connection
+pub fn get_active_connection_paths(client: &impl NmClient) -> Vec<String> {
+    client
+        .active_connections()
+        .iter()
+        .filter_map(|a| a.connection())
+        .filter_map(|c| c.path().map(|p| p.to_string()))
+        .collect()
+}
+
+// Existing connections applying a config may delete, i.e. the connection it
+// replaces with "--force" and, for bonds, that connection's slaves
+fn get_replaced_connections(
+    client: &impl NmClient,
+    conn: &ExportedConnection,
+) -> Result<Vec<RemoteConnection>> {
+    let (device_type, target_conn) = match conn {
+        ExportedConnection::Bond(opts) => return get_replaced_bond_connections(client, opts),
+        ExportedConnection::AccessPoint(opts) => {
+            (DeviceType::Wifi, create_access_point_connection(opts)?)
+        }
+        ExportedConnection::Station(opts) => (DeviceType::Wifi, create_sta_connection(opts)?),
+    };
+
+    Ok(get_connection(client, device_type, &target_conn)
+        .into_iter()
+        .collect())
+}
+
+// State before applying a config. Only connections the config may replace are
+// snapshotted, as reading secrets for every connection before every step is slow
+pub async fn get_step_state(client: &impl NmClient, conn: &ExportedConnection) -> StepState {
+    // Invalid config fails before changing anything, so nothing to snapshot
+    let replaced = get_replaced_connections(client, conn).unwrap_or_default();
+
+    StepState {
+        paths: get_connection_paths(client),
+        active: get_active_connection_paths(client),
+        snapshots: snapshot_connections(replaced).await,
+    }
+}
+
+// Snapshot connections, secrets included where readable, keyed by path. Used
+// to restore connections deleted while applying a batch when rolling back
+async fn snapshot_connections(conns: Vec<RemoteConnection>) -> Vec<(String, SimpleConnection)> {
+    let mut snapshots: Vec<(String, SimpleConnection)> = vec![];
+
+    for remote_conn in conns.into_iter() {
+        let path = match remote_conn.path() {
+            Some(p) => p.to_string(),
+            None => continue,
+        };
+
+        // Without secrets, a restored connection would be unable to authenticate
+        if let Err(e) = get_connection_secrets(&remote_conn).await {
+            warn!(
+                "Unable to read secrets of connection \"{}\", not included in snapshot: {}",
+                remote_conn.id().unwrap_or_default(),
+                e
+            );
+        }
+
+        match SimpleConnection::new_clone(&remote_conn).downcast::<SimpleConnection>() {
+            Ok(snapshot) => snapshots.push((path, snapshot)),
+            Err(_) => warn!("Unable to snapshot connection \"{}\"", path),
+        }
+    }
+
+    snapshots
+}
+
+// Undo every change recorded in the journal, deleting connections created during the
+// batch, restoring deleted ones from their snapshots, and reactivating deactivated ones.
+// Continues past failures to restore as much as possible, erroring afterwards if
+// anything could not be undone
+pub async fn rollback_journal(client: &impl NmClient, journal: &ApplyJournal) -> Result<()> {
+    let mut failed = 0;
+
+    for path in journal.created_rollback_order() {
+        let remote_conn = match client
+            .connections()
+            .into_iter()
+            .find(|c| c.path().is_some_and(|p| p == path))
+        {
+            Some(c) => c,
+            None => continue,
+        };
+
+        info!(
+            "Rolling back, deleting connection \"{}\"",
+            remote_conn.id().unwrap_or_default()
+        );
+        if let Err(e) = remote_conn.delete_future().await {
+            warn!("Unable to delete connection \"{}\": {}", path, e);
+            failed += 1;
+        }
+    }
+
+    for (path, snapshot) in journal.deleted_rollback_order() {
+        let id = snapshot.id().unwrap_or_default();
+
+        info!("Rolling back, restoring connection \"{}\"", id);
+        let remote_conn = match client.add_connection_future(snapshot, true).await {
+            Ok(c) => c,
+            Err(e) => {
+                warn!("Unable to restore connection \"{}\": {}", id, e);
+                failed += 1;
+                continue;
+            }
+        };
+
+        if journal.was_deactivated(path) && !reactivate_connection(client, &remote_conn).await {
+            failed += 1;
+        }
+    }
+
+    // Restored connections were reactivated above, as their paths changed
+    let active = get_active_connection_paths(client);
+    for path in journal.deactivated_rollback_order() {
+        if active.iter().any(|p| p == path) {
+            continue;
+        }
+
+        let remote_conn = match client
+            .connections()
+            .into_iter()
+            .find(|c| c.path().is_some_and(|p| p == path))
+        {
+            Some(c) => c,
+            None => continue,
+        };
+
+        if !reactivate_connection(client, &remote_conn).await {
+            failed += 1;
+        }
+    }
+
+    if failed > 0 {
+        return Err(anyhow!("Unable to roll back {} connection changes", failed));
+    }
+    Ok(())
+}
+
+// Reactivate connection deactivated while applying a batch, without waiting for it
+// to finish activating. Returns whether activation was successfully started
+async fn reactivate_connection(client: &impl NmClient, remote_conn: &RemoteConnection) -> bool {
+    let id = remote_conn.id().unwrap_or_default();
+
+    info!("Rolling back, reactivating connection \"{}\"", id);
+    match client
+        .activate_connection_future(Some(remote_conn), None::<&Device>, None)
+        .await
+    {
+        Ok(_) => true,
+        Err(e) => {
+            warn!("Unable to reactivate connection \"{}\": {}", id, e);
+            false
+        }
+    }
+}
+
+// Recover IP and general connection options common to all connection types
+pub fn get_common_opts(conn: &impl ConnectionExt) -> Result<CommonOpts> {
+    let mut opts = CommonOpts::default();
+
+    if let Some(s_connection) = conn.setting_connection() {
+        opts.multi_connect = match s_connection.multi_connect() {
+            ConnectionMultiConnect::Single => Some(MultiConnect::Single),
+            ConnectionMultiConnect::Multiple => Some(MultiConnect::Multiple),
+            _ => None,
+        };
+        opts.autoconnect = Some(s_connection.autoconnect());
+        opts.stable_id = s_connection.stable_id().map(|s| s.to_string());
+        opts.id = s_connection.id().map(|s| s.to_string());
+    }
+    opts.dns_over_tls = get_dns_over_tls(conn);
+    opts.zone = get_zone(conn);
+
+    if let Some(s_ip4) = conn.setting_ip4_config() {
+        // Settings only hold static addresses, automatic addresses are not configured
+        opts.ip4_addrs = get_ip_addr_strs(&s_ip4);
+
+        // Method is implied by whether static addresses are configured, so
+        // only export a method which differs from the implied one
+        let implied = match opts.ip4_addrs.is_empty() {
+            true => Ip4Method::Auto,
+            false => Ip4Method::Manual,
+        };
+        opts.ip4_method = s_ip4
+            .method()
+            .and_then(|m| get_ip4_method(&m))
+            .filter(|m| *m != implied);
+
+        opts.route_metric = get_route_metric(s_ip4.route_metric());
+        // Only export when differing from NetworkManager default
+        opts.ip4_may_fail = Some(s_ip4.may_fail()).filter(|m| !m);
+        opts.dhcp_timeout = Some(s_ip4.dhcp_timeout()).filter(|t| *t != 0);
+        opts.dhcp_client_id = s_ip4.dhcp_client_id().map(|c| c.to_string());
+        opts.dhcp_hostname = s_ip4.dhcp_hostname().map(|h| h.to_string());
+        opts.routes = get_static_routes(&s_ip4);
+        opts.gateway = s_ip4.gateway().map(|g| g.to_string());
+        opts.dns = (0..s_ip4.num_dns())
+            .filter_map(|ix| s_ip4.dns(ix as i32))
+            .map(|d| d.to_string())
+            .collect();
+    }
+
+    if let Some(s_ip6) = conn.setting_ip6_config() {
+        if s_ip6.method().as_deref() == Some(SETTING_IP6_CONFIG_METHOD_MANUAL) {
+            // Only a single static IPv6 address supported
+            if let Some(addr) = get_ip_addr_strs(&s_ip6).first() {
+                opts.ip6_addr = Some(Ipv6Net::from_str(addr)?);
+            }
+        }
+    }
+
+    Ok(opts)
+}
+
+// Static addresses in "address/prefix" form, e.g. "192.0.2.1/24"
+fn get_ip_addr_strs(settings: &impl SettingIPConfigExt) -> Vec<String> {
+    let mut addrs: Vec<String> = vec![];
+
+    for ix in 0..settings.num_addresses() {
+        match settings.address(ix as i32) {
+            Some(addr) => match addr.address() {
+                Some(a) => addrs.push(format!("{}/{}", a, addr.prefix())),
+                None => warn!("Unable to get address string with index \"{}\"", ix),
+            },
+            None => warn!("Unable to get address with index \"{}\"", ix),
+        }
+    }
+
+    addrs
+}
+
+// Wireless SSIDs are arbitrary bytes, but only UTF-8 SSIDs can be configured
+pub fn get_ssid_str(s_wireless: &SettingWireless) -> Result<String> {
+    let ssid = match s_wireless.ssid() {
+        Some(ssid) => ssid,
+        None => return Err(anyhow!("Unable to get wireless SSID")),
+    };
+
+    String::from_utf8(ssid.to_vec()).map_err(|_| anyhow!("Wireless SSID is not valid UTF-8"))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const BUNDLE: &str = "
+---
+!Bond
+bond_interface: bond0
+bond_mode: ActiveBackup
+miimon: 100
+slave_interfaces:
+- eth0
+- eth1
+ip4_addrs:
+- 192.0.2.10/24
+---
+!AccessPoint
+wireless_interface: wlan0
+ssid: test_ap
+open: true
+band: A
+---
+!Station
+wireless_interface: wlan1
+ssid: test_sta
+ip6_addr: 2001:db8::1/64
+dns:
+- 192.0.2.53
+";
+
+    #[test]
+    fn export_bundle_round_trip() {
+        let conns = parse_export_bundle(BUNDLE).unwrap();
+        assert_eq!(conns.len(), 3);
+
+        match &conns[0] {
+            ExportedConnection::Bond(opts) => {
+                assert_eq!(opts.ip4_addrs, vec!["192.0.2.10/24".to_string()])
+            }
+            c => panic!("Expected bond connection, got {:?}", c),
+        }
+        match &conns[2] {
+            ExportedConnection::Station(opts) => {
+                assert_eq!(opts.ssid, Some("test_sta".to_string()));
+                assert_eq!(opts.dns, vec!["192.0.2.53".to_string()]);
+            }
+            c => panic!("Expected station connection, got {:?}", c),
+        }
+
+        let bundle = get_export_bundle_str(&conns).unwrap();
+        assert_eq!(parse_export_bundle(&bundle).unwrap(), conns);
+    }
+
+    #[test]
+    fn export_config_secrets() {
+        let mut conns = parse_export_bundle(BUNDLE).unwrap();
+        let secrets = ConnectionSecrets {
+            psk: Some("test_password".to_string()),
+            eap_password: None,
+        };
+
+        // Secrets only applied to wireless connections
+        let mut sta_conn = conns.pop().unwrap();
+        sta_conn.apply_secrets(secrets.clone());
+
+        let mut bond_conn = conns.remove(0);
+        let bond_config = bond_conn.to_config_str().unwrap();
+        bond_conn.apply_secrets(secrets);
+        assert_eq!(bond_conn.to_config_str().unwrap(), bond_config);
+
+        // Config untagged, so usable directly as station config
+        let config = sta_conn.to_config_str().unwrap();
+        assert!(!config.contains("!Station"));
+
+        let opts: StationOpts = serde_yaml::from_str(&config).unwrap();
+        assert_eq!(opts.ssid, Some("test_sta".to_string()));
+        assert_eq!(opts.password, Some("test_password".to_string()));
+    }
+
+    #[test]
+    fn empty_export_bundle() {
+        assert_eq!(get_export_bundle_str(&[]).unwrap(), "");
+        assert!(parse_export_bundle("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn config_file_type() {
+        let bond = "
+            bond_interface: bond0
+            slave_interfaces: [eth0]
+        ";
+        assert!(matches!(
+            parse_config_file(bond).unwrap(),
+            ExportedConnection::Bond(_)
+        ));
+
+        let access_point = "
+            wireless_interface: wlan0
+            ssid: test_ssid
+            open: true
+        ";
+        assert!(matches!(
+            parse_config_file(access_point).unwrap(),
+            ExportedConnection::AccessPoint(_)
+        ));
+
+        let station = "
+            wireless_interface: wlan0
+            ssid: test_ssid
+            password: test_password
+        ";
+        assert!(matches!(
+            parse_config_file(station).unwrap(),
+            ExportedConnection::Station(_)
+        ));
+
+        // Tag takes precedence over inference
+        let tagged = "
+            !AccessPoint
+            wireless_interface: wlan0
+            ssid: test_ssid
+            password: test_password
+        ";
+        assert!(matches!(
+            parse_config_file(tagged).unwrap(),
+            ExportedConnection::AccessPoint(_)
+        ));
+
+        assert!(parse_config_file("wireless_interface: wlan0").is_err());
+    }
+
+    #[test]
+    fn config_creation_order() {
+        let mut configs: Vec<Result<ExportedConnection>> = vec![
+            Ok(ExportedConnection::Station(StationOpts::default())),
+            Ok(ExportedConnection::AccessPoint(AccessPointOpts::default())),
+            Ok(ExportedConnection::Bond(BondOpts::default())),
+            Err(anyhow!("Invalid config")),
+        ];
+        configs.sort_by_key(get_creation_rank);
+
+        assert!(configs[0].is_err());
+        assert!(matches!(configs[1], Ok(ExportedConnection::Bond(_))));
+        assert!(matches!(configs[2], Ok(ExportedConnection::AccessPoint(_))));
+        assert!(matches!(configs[3], Ok(ExportedConnection::Station(_))));
+    }
+
+    #[test]
+    fn toml_config_dir() {
+        let dir = std::env::temp_dir().join(format!("nutil-config-dir-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("bond0.yaml"), "bond_interface: bond0").unwrap();
+        std::fs::write(dir.join("wlan0.toml"), "ssid = \"test_ssid\"").unwrap();
+        std::fs::write(dir.join("README"), "Not a config").unwrap();
+
+        let configs = read_config_dir(dir.to_str().unwrap()).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        // TOML config fails rather than being skipped, other files skipped
+        assert_eq!(configs.len(), 2);
+        assert!(configs[0].0.ends_with("wlan0.toml"));
+        assert!(configs[0].1.is_err());
+        assert!(matches!(configs[1].1, Ok(ExportedConnection::Bond(_))));
+    }
+
+    #[test]
+    fn apply_journal() {
+        let paths = |ps: &[&str]| ps.iter().map(|p| p.to_string()).collect::<Vec<String>>();
+        let state = |ps: &[&str], active: &[&str], snapshotted: &[&str]| StepState {
+            paths: paths(ps),
+            active: paths(active),
+            snapshots: snapshotted
+                .iter()
+                .map(|p| (p.to_string(), format!("snapshot {p}")))
+                .collect(),
+        };
+        let mut journal: ApplyJournal<String> = ApplyJournal::default();
+
+        // 1. Bond created with two slaves, preempting an existing wired connection
+        journal.record_step(
+            state(&["/1"], &["/1"], &[]),
+            &paths(&["/1", "/2", "/3", "/4"]),
+            &paths(&["/2", "/3", "/4"]),
+        );
+
+        // 2. Active access point replaced with "--force", same UUID but new path
+        journal.record_step(
+            state(
+                &["/1", "/2", "/3", "/4", "/5"],
+                &["/2", "/3", "/4", "/5"],
+                &["/5"],
+            ),
+            &paths(&["/1", "/2", "/3", "/4", "/6"]),
+            &paths(&["/2", "/3", "/4", "/6"]),
+        );
+
+        // 3. Bond from step 1 and its slaves replaced with "--force", then
+        // failing mid-batch once the new bond connection is created
+        journal.record_step(
+            state(
+                &["/1", "/2", "/3", "/4", "/6"],
+                &["/2", "/3", "/4", "/6"],
+                &["/2", "/3", "/4"],
+            ),
+            &paths(&["/1", "/6", "/7"]),
+            &paths(&["/6"]),
+        );
+
+        // Created connections deleted newest first, including the failed step's.
+        // Connections created then deleted during the batch need no rollback
+        assert_eq!(journal.created_rollback_order(), vec!["/7", "/6"]);
+
+        // Only connections which existed before the batch restored
+        assert_eq!(
+            journal.deleted_rollback_order(),
+            vec![("/5", &"snapshot /5".to_string())]
+        );
+
+        // Both preempted and replaced connections which were active reactivated
+        assert_eq!(journal.deactivated_rollback_order(), vec!["/5", "/1"]);
+        assert!(journal.was_deactivated("/5"));
+        assert!(!journal.is_empty());
+
+        // Steps which change nothing, e.g. failing validation, need no rollback
+        let mut journal: ApplyJournal<String> = ApplyJournal::default();
+        journal.record_step(
+            state(&["/1"], &["/1"], &[]),
+            &paths(&["/1"]),
+            &paths(&["/1"]),
+        );
+        assert!(journal.is_empty());
+    }
+}
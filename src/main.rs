@@ -1,69 +1,403 @@
-use anyhow::{Context, Result};
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
 use clap::Parser;
 use nm::*;
 use tracing_subscriber::{fmt, prelude::*, EnvFilter};
 
 pub mod access_point;
 pub mod bond;
+pub mod capability;
 pub mod cli;
 pub mod connection;
+pub mod export;
 pub mod station;
+pub mod status;
 pub mod util;
 
 use crate::access_point::*;
 use crate::bond::*;
+use crate::capability::VersionReport;
 use crate::cli::*;
+use crate::connection::{
+    active_guard_allows, create_client, delete_wifi_connection, print_connection_settings, NmClient,
+};
+use crate::export::{
+    export_all, get_active_connection_paths, get_connection_paths, get_step_state, read_config_dir,
+    rollback_journal, ApplyJournal, ExportedConnection,
+};
 use crate::station::*;
+use crate::status::*;
 
 fn main() -> Result<()> {
-    // Defaults to printing logs at info level for all spans if not specified
-    // TODO: ^^^^
+    let opts = App::parse();
+
+    // Log level from command line, falling back to "NUTIL_LOG"
+    // environment variable, then to info level for all spans
+    let filter = match opts.log_directive() {
+        Some(directive) => EnvFilter::new(directive),
+        None => EnvFilter::try_from_env("NUTIL_LOG").unwrap_or_else(|_| EnvFilter::new("info")),
+    };
+
     tracing_subscriber::registry()
         .with(fmt::layer())
-        .with(EnvFilter::from_env("NUTIL_LOG"))
+        .with(filter)
         .init();
 
-    let opts = App::parse();
+    if let Err(e) = opts.check_command() {
+        e.exit();
+    }
+
+    // Completions don't require NetworkManager, so generate before connecting
+    if let Some(Command::Completions { shell }) = opts.command {
+        write_completions(shell, &mut std::io::stdout());
+        return Ok(());
+    }
 
+    // Report failures as JSON when output is JSON, otherwise as usual
+    let output = opts.output;
     let context = glib::MainContext::default();
-    context.block_on(run(opts))
+    match context.block_on(run(opts)) {
+        Err(e) if output == OutputFormat::Json => {
+            print_error(&e)?;
+            std::process::exit(1);
+        }
+        res => res,
+    }
 }
 
 async fn run(args: App) -> Result<()> {
-    let client = Client::new_future()
-        .await
-        .context("Failed to create NM Client")?;
+    let client = create_client(Duration::from_secs(args.connect_timeout)).await?;
+
+    // Nothing to show when creation only a dry run
+    let show_status = args.show_status && !args.dry_run;
+    let guard = args.active_guard();
+    let retry = args.retry_policy();
+
+    // Explaining only prints built settings, so takes priority over the guard
+    let explain = args.explain;
+    let include_secrets = args.include_secrets;
+
+    if args.nm_version {
+        return VersionReport::new(&client).print(args.output);
+    }
+
+    if let Some(Command::Apply {
+        dir,
+        fail_fast,
+        atomic,
+    }) = &args.command
+    {
+        return apply_config_dir(&client, &args, dir, *fail_fast, *atomic).await;
+    }
 
-    match args.command {
+    let command = match args.command {
+        Some(c) => c,
+        None => return Err(anyhow!("Required subcommand not specified")),
+    };
+
+    match command {
         Command::Station { action, mut c_args } => {
             c_args.config = args.config;
+            c_args.overrides = args.overrides;
+            c_args.timeout = args.timeout;
+            c_args.retry = retry;
+            c_args.output = args.output;
+            c_args.dry_run = args.dry_run;
+            c_args.no_wait = args.no_wait;
+            c_args.no_preempt = args.no_preempt;
+            c_args.yes = args.yes;
+            c_args.uuid = args.uuid;
+            if args.force {
+                return Err(anyhow!(
+                    "Replacing existing connections not supported for stations"
+                ));
+            }
             let opts = StationOpts::try_from(c_args)?;
 
+            if explain && matches!(action, Action::Create) {
+                return print_connection_settings(&create_sta_connection(&opts)?);
+            }
+
+            if !active_guard_allows(&client, guard, DeviceType::Wifi, || {
+                create_sta_connection(&opts)
+            })? {
+                return Ok(());
+            }
+
             match action {
-                Action::Create => create_station(&client, opts).await,
-                Action::Delete => todo!(), //delete_access_point(&client, opts).await,
-                Action::Status => todo!(), //access_point_status(&client, opts),
+                Action::Create => {
+                    let res = create_station(&client, opts.clone()).await;
+                    show_status_after_create(res, show_status, || {
+                        station_status(&client, opts).map(|_| ())
+                    })
+                }
+                Action::Delete => delete_station(&client, opts).await,
+                Action::Status => exit_with_status(station_status(&client, opts)),
+                Action::Export => export_station(&client, opts, include_secrets).await,
+                Action::Activate => set_station_active(&client, opts, true).await,
+                Action::Deactivate => set_station_active(&client, opts, false).await,
+                Action::MoveSlave | Action::AddSlave | Action::RemoveSlave => {
+                    Err(anyhow!("Slave changes not supported for stations"))
+                }
+                Action::Modify => Err(anyhow!("Modifying not supported for stations")),
             }
         }
         Command::AccessPoint { action, mut c_args } => {
             c_args.config = args.config;
+            c_args.overrides = args.overrides;
+            c_args.timeout = args.timeout;
+            c_args.retry = retry;
+            c_args.output = args.output;
+            c_args.dry_run = args.dry_run;
+            c_args.no_wait = args.no_wait;
+            c_args.no_preempt = args.no_preempt;
+            c_args.yes = args.yes;
+            c_args.uuid = args.uuid;
+            c_args.force = args.force;
             let opts = AccessPointOpts::try_from(c_args)?;
 
+            if explain && matches!(action, Action::Create) {
+                return print_connection_settings(&create_access_point_connection(&opts)?);
+            }
+
+            if !active_guard_allows(&client, guard, DeviceType::Wifi, || {
+                create_access_point_connection(&opts)
+            })? {
+                return Ok(());
+            }
+
             match action {
-                Action::Create => create_access_point(&client, opts).await,
+                Action::Create => {
+                    let res = create_access_point(&client, opts.clone()).await;
+                    show_status_after_create(res, show_status, || {
+                        access_point_status(&client, opts).map(|_| ())
+                    })
+                }
                 Action::Delete => delete_access_point(&client, opts).await,
-                Action::Status => access_point_status(&client, opts),
+                Action::Status => exit_with_status(access_point_status(&client, opts)),
+                Action::Export => export_access_point(&client, opts, include_secrets).await,
+                Action::Activate => set_access_point_active(&client, opts, true).await,
+                Action::Deactivate => set_access_point_active(&client, opts, false).await,
+                Action::MoveSlave | Action::AddSlave | Action::RemoveSlave => {
+                    Err(anyhow!("Slave changes not supported for access points"))
+                }
+                Action::Modify => Err(anyhow!("Modifying not supported for access points")),
             }
         }
         Command::Bond { action, mut c_args } => {
             c_args.config = args.config;
+            c_args.overrides = args.overrides;
+            c_args.timeout = args.timeout;
+            c_args.retry = retry;
+            c_args.output = args.output;
+            c_args.dry_run = args.dry_run;
+            c_args.no_wait = args.no_wait;
+            c_args.no_preempt = args.no_preempt;
+            c_args.yes = args.yes;
+            c_args.uuid = args.uuid;
+            c_args.force = args.force;
             let opts = BondOpts::try_from(c_args)?;
 
+            if explain && matches!(action, Action::Create) {
+                return print_connection_settings(&create_bond_connection(&opts)?);
+            }
+
+            // Guard only applies to a single bond, not slave moves or listing all bonds
+            let guarded = !opts.all && !matches!(action, Action::MoveSlave);
+            if guarded
+                && !active_guard_allows(&client, guard, DeviceType::Bond, || {
+                    create_bond_connection(&opts)
+                })?
+            {
+                return Ok(());
+            }
+
             match action {
-                Action::Create => create_bond(&client, opts).await,
+                Action::Create => {
+                    let res = create_bond(&client, opts.clone()).await;
+                    show_status_after_create(res, show_status, || {
+                        bond_status(&client, opts).map(|_| ())
+                    })
+                }
                 Action::Delete => delete_bond(&client, opts).await,
-                Action::Status => bond_status(&client, opts),
+                Action::Status if opts.all => list_bonds(&client, opts),
+                Action::Status => exit_with_status(bond_status(&client, opts)),
+                Action::MoveSlave => move_bond_slave(&client, opts).await,
+                Action::AddSlave => add_bond_slaves(&client, opts).await,
+                Action::RemoveSlave => remove_bond_slaves(&client, opts).await,
+                Action::Modify => modify_bond(&client, opts).await,
+                Action::Export => export_bond(&client, opts).await,
+                Action::Activate => set_bond_active(&client, opts, true).await,
+                Action::Deactivate => set_bond_active(&client, opts, false).await,
+            }
+        }
+        Command::Wifi {
+            action: WifiAction::Delete,
+            ssid,
+            wireless_ifname,
+        } => {
+            delete_wifi_connection(
+                &client,
+                &ssid,
+                wireless_ifname.as_deref(),
+                args.timeout.map(Duration::from_secs),
+                args.yes,
+                args.dry_run,
+            )
+            .await
+        }
+        Command::List => list_connections(&client, args.output),
+        Command::Scan { wireless_ifname } => scan(&client, &wireless_ifname, args.output).await,
+        Command::ExportAll { out } => export_all(&client, out.as_deref()),
+        Command::Apply { .. } => {
+            unreachable!("Config directory applied before matching other commands")
+        }
+        Command::Completions { .. } => {
+            unreachable!("Completions generated before connecting to NetworkManager")
+        }
+    }
+}
+
+// Create connection for every config file in directory, reporting each file's result.
+// Continue past failures unless failing fast, but still error if any failed. When
+// atomic, stop at the first failure and roll back every change made before it
+async fn apply_config_dir(
+    client: &impl NmClient,
+    args: &App,
+    dir: &str,
+    fail_fast: bool,
+    atomic: bool,
+) -> Result<()> {
+    let unsupported = args.unsupported_apply_flags();
+    if !unsupported.is_empty() {
+        return Err(anyhow!(
+            "Options {:?} not supported when applying a config directory",
+            unsupported
+        ));
+    }
+
+    let configs = read_config_dir(dir)?;
+    let total = configs.len();
+    let mut failed = 0;
+
+    // Nothing changes during a dry run, so nothing to roll back
+    let atomic = atomic && !args.dry_run;
+    let fail_fast = fail_fast || atomic;
+    let mut journal = ApplyJournal::default();
+
+    for (path, conn) in configs {
+        let res = match conn {
+            Ok(mut conn) => {
+                apply_cli_opts(&mut conn, args);
+
+                // Record state before each step, as a failed step may still change connections
+                let before = match atomic {
+                    true => Some(get_step_state(client, &conn).await),
+                    false => None,
+                };
+                let res = create_exported_connection(client, conn).await;
+                if let Some(before) = before {
+                    journal.record_step(
+                        before,
+                        &get_connection_paths(client),
+                        &get_active_connection_paths(client),
+                    );
+                }
+                res
+            }
+            Err(e) => Err(e),
+        };
+
+        match res {
+            Ok(_) => println!("{path}: created"),
+            Err(e) => {
+                println!("{path}: failed: {e:#}");
+                failed += 1;
+
+                if fail_fast {
+                    break;
+                }
             }
         }
     }
+
+    if failed > 0 && atomic && !journal.is_empty() {
+        rollback_journal(client, &journal).await?;
+        return Err(anyhow!(
+            "Failed to create connections from \"{}\", rolled back all changes",
+            dir
+        ));
+    }
+
+    if failed > 0 {
+        return Err(anyhow!(
+            "Failed to create {} of {} connections from \"{}\"",
+            failed,
+            total,
+            dir
+        ));
+    }
+    Ok(())
+}
+
+// Command-line only options, applied to connections created from config files
+fn apply_cli_opts(conn: &mut ExportedConnection, args: &App) {
+    let timeout = args.timeout.map(Duration::from_secs);
+    let retry = args.retry_policy();
+
+    match conn {
+        ExportedConnection::Bond(opts) => {
+            opts.timeout = timeout;
+            opts.retry = retry;
+            opts.output = args.output;
+            opts.dry_run = args.dry_run;
+            opts.no_wait = args.no_wait;
+            opts.no_preempt = args.no_preempt;
+            opts.yes = args.yes;
+            opts.force = args.force;
+        }
+        ExportedConnection::AccessPoint(opts) => {
+            opts.timeout = timeout;
+            opts.retry = retry;
+            opts.output = args.output;
+            opts.dry_run = args.dry_run;
+            opts.no_wait = args.no_wait;
+            opts.no_preempt = args.no_preempt;
+            opts.yes = args.yes;
+            opts.force = args.force;
+        }
+        // Replacing existing connections not supported for stations
+        ExportedConnection::Station(opts) => {
+            opts.timeout = timeout;
+            opts.retry = retry;
+            opts.output = args.output;
+            opts.dry_run = args.dry_run;
+            opts.no_wait = args.no_wait;
+            opts.no_preempt = args.no_preempt;
+            opts.yes = args.yes;
+        }
+    }
+}
+
+async fn create_exported_connection(
+    client: &impl NmClient,
+    conn: ExportedConnection,
+) -> Result<()> {
+    match conn {
+        ExportedConnection::Bond(opts) => create_bond(client, opts).await,
+        ExportedConnection::AccessPoint(opts) => create_access_point(client, opts).await,
+        ExportedConnection::Station(opts) => create_station(client, opts).await,
+    }
+}
+
+// Exit with status command's code when the connection is not active. Errors,
+// e.g. a missing connection, are returned as usual for the generic error code
+fn exit_with_status(res: Result<StatusCode>) -> Result<()> {
+    let code = res?;
+
+    if code != StatusCode::Active {
+        std::process::exit(code.exit_code());
+    }
+    Ok(())
 }
@@ -1,22 +1,44 @@
-use std::fs::File;
-use std::io::Read;
+use std::path::Path;
 use std::str;
-use std::str::FromStr;
+use std::time::Duration;
 
 use anyhow::{anyhow, Result};
-use ipnet::Ipv4Net;
+use clap::ValueEnum;
+use ipnet::Ipv6Net;
 use nm::*;
-use serde::Deserialize;
-use tracing::{debug, info, instrument};
+use serde::{Deserialize, Serialize};
+use tracing::{info, instrument, warn};
 
 use crate::{
-    access_point::{create_access_point_connection, AccessPointOpts},
+    access_point::AccessPointOpts,
     cli::StationArgs,
-    connection::{get_active_connection, wait_for_connection_to_activate},
-    util::deserialize_password,
+    connection::*,
+    export::{export_connection, get_common_opts, get_ssid_str},
+    status::*,
+    util::{
+        apply_config_overrides, config_fields, confirm_action, deserialize_ip4_addrs,
+        deserialize_ip6_addr, deserialize_password, read_config, serialize_ip6_addr,
+        validate_bssid, validate_cloned_mac, validate_ifname, validate_min_signal, validate_mtu,
+        validate_ssid,
+    },
 };
 
-#[derive(Default, Deserialize, PartialEq, Clone, Debug)]
+/// Enterprise (WPA-EAP) authentication method. Both authenticate
+/// with an identity and password, using MSCHAPv2 inside a TLS tunnel
+#[derive(ValueEnum, Serialize, Deserialize, PartialEq, Copy, Clone, Debug)]
+pub enum EapMethod {
+    /// Protected EAP, e.g. eduroam
+    Peap,
+    /// Tunneled TLS
+    Ttls,
+}
+
+/// Time to wait for scan results after requesting a scan, as
+/// NetworkManager accepts the request before scanning finishes
+const SCAN_WAIT: Duration = Duration::from_secs(3);
+
+#[serde_with::skip_serializing_none]
+#[derive(Default, Serialize, Deserialize, PartialEq, Clone, Debug)]
 pub struct StationOpts {
     #[serde(rename = "wireless_interface")]
     #[serde(default)]
@@ -27,34 +49,255 @@ pub struct StationOpts {
     #[serde(with = "serde_with::rust::string_empty_as_none")]
     pub ssid: Option<String>,
 
+    /// Connection name shown by NetworkManager. If not specified, default to the SSID.
+    /// Connections are matched by interface and SSID, never by name
+    #[serde(default)]
+    #[serde(with = "serde_with::rust::string_empty_as_none")]
+    pub connection_id: Option<String>,
+
     /// Must be 8 characters or longer
     #[serde(default)]
     #[serde(deserialize_with = "deserialize_password")]
     pub password: Option<String>,
+    /// Static IPv4 addresses, each including prefix length.
+    /// Accepts a single address or a list of addresses
+    #[serde(default, alias = "ip4_addr", skip_serializing_if = "Vec::is_empty")]
+    #[serde(deserialize_with = "deserialize_ip4_addrs")]
+    pub ip4_addrs: Vec<String>,
+
+    /// IPv4 method, e.g. "shared" to provide NAT and DHCP to downstream clients.
+    /// If not specified, static with IPv4 addresses, otherwise DHCP
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ip4_method: Option<Ip4Method>,
+
+    /// IPv4 gateway. Requires a static IPv4 address
+    #[serde(default)]
+    #[serde(with = "serde_with::rust::string_empty_as_none")]
+    pub gateway: Option<String>,
+
+    /// IPv4 DNS servers
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub dns: Vec<String>,
+
+    /// Static IPv4 routes, each "dest/prefix via gateway [metric]",
+    /// e.g. "198.51.100.0/24 via 192.0.2.254 100"
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub routes: Vec<String>,
+
+    /// Seconds to wait for a DHCP lease before failing activation. Only
+    /// used without a static IPv4 address. Zero uses NetworkManager's default
+    #[serde(default)]
+    pub dhcp_timeout: Option<i32>,
+
+    /// DHCP client identifier sent when requesting a lease, e.g. "mac" or
+    /// "duid". Only used with automatic IPv4 configuration
+    #[serde(default)]
+    #[serde(with = "serde_with::rust::string_empty_as_none")]
+    pub dhcp_client_id: Option<String>,
+
+    /// Hostname sent when requesting a lease. Only used with automatic IPv4 configuration
+    #[serde(default)]
+    #[serde(with = "serde_with::rust::string_empty_as_none")]
+    pub dhcp_hostname: Option<String>,
+
+    /// Whether the connection may activate even if IPv4 configuration fails, e.g.
+    /// when no DHCP server responds. If not specified, use NetworkManager default (true)
+    #[serde(default)]
+    pub ip4_may_fail: Option<bool>,
+
+    /// Static IPv6 address, including prefix length. If not specified, default to auto
+    #[serde(default)]
+    #[serde(deserialize_with = "deserialize_ip6_addr")]
+    #[serde(serialize_with = "serialize_ip6_addr")]
+    pub ip6_addr: Option<Ipv6Net>,
+
+    /// Pin station to a single AP's BSSID. If not specified, station
+    /// may roam between any APs broadcasting the SSID
+    #[serde(default)]
+    #[serde(with = "serde_with::rust::string_empty_as_none")]
+    pub bssid: Option<String>,
+
+    /// Minimum signal strength (0-100) of the SSID, or pinned BSSID, required to
+    /// create the station. If not specified, join regardless of signal
+    #[serde(default)]
+    pub min_signal: Option<u8>,
+
+    /// If not specified, use NetworkManager default (single)
+    #[serde(default)]
+    pub multi_connect: Option<MultiConnect>,
+
+    /// If not specified, default to enabled
+    #[serde(default)]
+    pub autoconnect: Option<bool>,
+
+    /// If not specified, use NetworkManager default (global DNS configuration)
+    #[serde(default)]
+    pub dns_over_tls: Option<DnsOverTls>,
+
+    /// Seed for generated stable MAC and IPv6 privacy addresses. May contain
+    /// NetworkManager substitution tokens, e.g. "${CONNECTION}"
+    #[serde(default)]
+    pub stable_id: Option<String>,
+
+    /// Firewalld zone the connection is placed in, e.g. "public". If not
+    /// specified, use firewalld default zone
+    #[serde(default)]
+    pub zone: Option<String>,
+
+    /// MAC address used instead of device's permanent address. Either an
+    /// explicit MAC address or one of "random", "stable", or "permanent"
+    #[serde(default)]
+    #[serde(with = "serde_with::rust::string_empty_as_none")]
+    pub cloned_mac: Option<String>,
+
+    /// Maximum transmission unit in bytes, between 68 and 9000. When
+    /// unspecified, NetworkManager uses the device's default
+    #[serde(default)]
+    pub mtu: Option<u32>,
+
+    /// Wireless power saving. Disabling reduces latency at the cost of power. If not
+    /// specified, use NetworkManager default (global configuration)
+    #[serde(default)]
+    pub powersave: Option<bool>,
+
+    /// IPv4 route metric, lower is preferred, e.g. to prefer a bond over a
+    /// station when both are active. When unspecified or -1, NetworkManager chooses
+    #[serde(default)]
+    pub route_metric: Option<i64>,
+
+    /// Requires a password. If not specified, default to WPA-PSK
+    #[serde(default)]
+    pub key_mgmt: Option<KeyMgmt>,
+
+    /// Minimum password length, between 8 and 63. Only raises the WPA-PSK
+    /// minimum of 8 characters and does not apply to 64 character hex keys
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub psk_min_len: Option<usize>,
+
+    /// Enterprise (WPA-EAP) authentication method. Requires an identity
+    /// and EAP password, and may not be used with a password
+    #[serde(default)]
+    pub eap_method: Option<EapMethod>,
+
+    /// Enterprise network user name
     #[serde(default)]
     #[serde(with = "serde_with::rust::string_empty_as_none")]
-    pub ip4_addr: Option<String>,
+    pub identity: Option<String>,
+
+    /// Path to CA certificate used to verify the enterprise authentication server
+    #[serde(default)]
+    #[serde(with = "serde_with::rust::string_empty_as_none")]
+    pub ca_cert: Option<String>,
+
+    /// Enterprise network password
+    #[serde(default)]
+    #[serde(with = "serde_with::rust::string_empty_as_none")]
+    pub eap_password: Option<String>,
+
+    /// Time to wait for connection to (de)activate. When unspecified, wait
+    /// indefinitely to activate, or a short time to deactivate before deleting
+    #[serde(skip)]
+    pub timeout: Option<Duration>,
+
+    /// Retries for transient connection activation failures
+    #[serde(skip)]
+    pub retry: RetryPolicy,
+
+    /// Format used when printing connection status
+    #[serde(skip)]
+    pub output: OutputFormat,
+
+    /// Validate and build connection without modifying NetworkManager state
+    #[serde(skip)]
+    pub dry_run: bool,
+
+    /// Return once activation starts rather than waiting for it to finish
+    #[serde(skip)]
+    pub no_wait: bool,
+
+    /// Abort rather than deactivate conflicting active connections
+    #[serde(skip)]
+    pub no_preempt: bool,
+
+    /// Skip confirmation before destructive operations
+    #[serde(skip)]
+    pub yes: bool,
+
+    /// Target connection with this UUID rather than one matching other options
+    #[serde(skip)]
+    pub uuid: Option<String>,
+
+    /// Reject static IPv4 addresses overlapping subnets of active connections
+    #[serde(skip)]
+    pub strict: bool,
 }
 
 impl TryFrom<StationArgs> for StationOpts {
     type Error = anyhow::Error;
 
     fn try_from(args: StationArgs) -> Result<Self, Self::Error> {
-        if let Some(cfg) = args.config {
-            let mut buf = vec![];
-            let mut cfg_file = File::open(cfg)?;
-            cfg_file.read_to_end(&mut buf)?;
+        let mut opts = match args.config {
+            Some(cfg) => {
+                let config = read_config(&cfg)?;
+                let config = apply_config_overrides(
+                    &config,
+                    &args.overrides,
+                    config_fields::<StationOpts>(),
+                )?;
+                parse_station_opts(&config)?
+            }
+            None => StationOpts {
+                wireless_ifname: args.wireless_ifname,
+                ssid: args.ssid,
+                connection_id: args.connection_id,
+                ip4_addrs: args
+                    .ip4_addr
+                    .into_iter()
+                    .chain(args.extra_ip4_addrs)
+                    .collect(),
+                ip4_method: args.ip4_method,
+                gateway: args.gateway,
+                dns: args.dns,
+                routes: args.routes,
+                dhcp_timeout: args.dhcp_timeout,
+                dhcp_client_id: args.dhcp_client_id,
+                dhcp_hostname: args.dhcp_hostname,
+                ip4_may_fail: args.ip4_may_fail,
+                ip6_addr: args.ip6_addr,
+                bssid: args.lock_bssid,
+                min_signal: args.min_signal,
+                multi_connect: args.multi_connect,
+                autoconnect: args.autoconnect,
+                stable_id: args.stable_id,
+                zone: args.zone,
+                cloned_mac: args.cloned_mac,
+                mtu: args.mtu,
+                powersave: args.powersave,
+                route_metric: args.route_metric,
+                dns_over_tls: args.dns_over_tls,
+                password: args.password,
+                key_mgmt: args.key_mgmt,
+                psk_min_len: args.psk_min_len,
+                eap_method: args.eap_method,
+                identity: args.identity,
+                ca_cert: args.ca_cert,
+                eap_password: args.eap_password,
+                ..Default::default()
+            },
+        };
 
-            let config = str::from_utf8(buf.as_slice())?;
-            return parse_station_opts(config);
-        }
+        // Command-line only options, applied whether or not config file used
+        opts.timeout = args.timeout.map(Duration::from_secs);
+        opts.retry = args.retry;
+        opts.output = args.output;
+        opts.dry_run = args.dry_run;
+        opts.no_wait = args.no_wait;
+        opts.no_preempt = args.no_preempt;
+        opts.yes = args.yes;
+        opts.uuid = args.uuid;
+        opts.strict = args.strict;
 
-        Ok(StationOpts {
-            wireless_ifname: args.wireless_ifname,
-            ssid: args.ssid,
-            ip4_addr: args.ip4_addr,
-            password: args.password,
-        })
+        Ok(opts)
     }
 }
 
@@ -64,7 +307,15 @@ impl From<AccessPointOpts> for StationOpts {
             wireless_ifname: opts.wireless_ifname,
             ssid: opts.ssid,
             password: opts.password,
-            ip4_addr: opts.ip4_addr,
+            key_mgmt: opts.key_mgmt,
+            psk_min_len: opts.psk_min_len,
+            ip4_addrs: opts.ip4_addrs,
+            ip4_method: opts.ip4_method,
+            gateway: opts.gateway,
+            dns: opts.dns,
+            ip6_addr: opts.ip6_addr,
+            multi_connect: opts.multi_connect,
+            ..Default::default()
         }
     }
 }
@@ -75,12 +326,142 @@ fn parse_station_opts(config: &str) -> Result<StationOpts> {
 }
 
 #[instrument(skip(client), err)]
-pub async fn create_station(client: &Client, opts: StationOpts) -> Result<()> {
+pub async fn create_station(client: &impl NmClient, opts: StationOpts) -> Result<()> {
+    let ssid = match &opts.ssid {
+        Some(ssid) => ssid.clone(),
+        None => return Err(anyhow!("Required SSID not specified")),
+    };
+
+    validate_ca_cert(opts.ca_cert.as_deref())?;
+
+    // Station not bound to an interface, so deterministically choose
+    // the wireless device best suited to the SSID rather than letting
+    // NetworkManager pick any device. Profile itself is left unbound,
+    // only this activation uses the chosen device
     let wireless_ifname = match &opts.wireless_ifname {
-        Some(ifname) => ifname,
-        None => return Err(anyhow!("Required wireless interface not specified")),
+        Some(ifname) => ifname.clone(),
+        None => select_wireless_device(client, &ssid)?,
+    };
+
+    validate_ifname(&wireless_ifname)?;
+
+    // Create STA struct here so we can comprehensively search
+    // for any matching existing connection, should it exist
+    // Does not add connection to Network Manager, that happens later
+    let sta_conn = create_sta_connection(&opts)?;
+
+    // Deactivate any active station or access point connections on the interface
+    deactivate_connections_on_iface(
+        client,
+        &wireless_ifname,
+        DeviceType::Wifi,
+        opts.no_preempt,
+        opts.dry_run,
+        opts.yes,
+    )
+    .await?;
+
+    check_ip4_conflicts(client, &opts.ip4_addrs, &[&wireless_ifname], opts.strict)?;
+
+    let wireless_dev = match client.device_by_iface(wireless_ifname.as_str()) {
+        Some(device) => device,
+        None => {
+            return Err(anyhow!(
+                "Wireless device \"{}\" does not exist, quitting...",
+                wireless_ifname
+            ));
+        }
     };
 
+    if let Some(min_signal) = opts.min_signal {
+        validate_min_signal(min_signal)?;
+        let signal = get_visible_signal(&wireless_dev, &ssid, opts.bssid.as_deref());
+        check_min_signal(signal, min_signal, &ssid)?;
+    }
+
+    if opts.dry_run {
+        print_dry_run(&format!(
+            "create and activate station connection \"{}\" on interface \"{}\"",
+            ssid, wireless_ifname
+        ));
+        return Ok(());
+    }
+
+    info!("Creating station connection \"{}\"", ssid);
+    let sta_conn = client
+        .add_connection_future(&sta_conn, true)
+        .await
+        .map_err(map_nm_error)?;
+
+    info!("Activating station connection \"{}\"", ssid);
+    let sta_conn =
+        activate_connection_with_retry(client, &sta_conn, &wireless_dev, opts.retry).await?;
+
+    if opts.no_wait {
+        print_activation_state(&sta_conn, &ssid);
+        return Ok(());
+    }
+
+    // Waits until station is up and associated
+    let res = wait_for_connection_to_activate(&sta_conn, opts.timeout).await;
+
+    if res.is_ok() {
+        info!("Activated access point connection \"{}\"", ssid);
+    }
+    res
+}
+
+#[instrument(skip(client), err)]
+pub async fn delete_station(client: &impl NmClient, opts: StationOpts) -> Result<()> {
+    let ssid = match &opts.ssid {
+        Some(ssid) => ssid,
+        None => return Err(anyhow!("Required SSID not specified")),
+    };
+
+    let sta_conn = create_sta_connection(&opts)?;
+
+    // Use created SimpleConnection to find matching connections from NetworkManager
+    let uuid = opts.uuid.as_deref();
+    let sta_remote_conn = match get_target_connection(client, DeviceType::Wifi, &sta_conn, uuid)? {
+        Some(c) => c,
+        None => {
+            return Err(anyhow!(
+                "Required station connection \"{}\" does not exist, quitting...",
+                &ssid
+            ));
+        }
+    };
+
+    if opts.dry_run {
+        print_dry_run(&format!("delete station connection \"{}\"", ssid));
+        return Ok(());
+    }
+
+    confirm_action(&format!("delete station connection \"{}\"", ssid), opts.yes)?;
+
+    info!("Deactivating station connection \"{}\"", ssid);
+    match get_target_active_connection(client, DeviceType::Wifi, &sta_conn, uuid) {
+        Some(c) => {
+            client.deactivate_connection_future(&c).await?;
+
+            // Give connection a chance to fully deactivate before deleting
+            wait_for_deactivate_before_delete(&c, opts.timeout).await?;
+            info!("Station connection deactivated");
+        }
+        None => {
+            info!("Required station connection \"{}\" is not active", &ssid);
+        }
+    };
+
+    info!("Deleting station connection \"{}\"", ssid);
+    sta_remote_conn.delete_future().await?;
+    info!("Station connection deleted");
+
+    Ok(())
+}
+
+#[instrument(skip(client), err)]
+pub fn station_status(client: &impl NmClient, opts: StationOpts) -> Result<StatusCode> {
     let ssid = match &opts.ssid {
         Some(ssid) => ssid,
         None => return Err(anyhow!("Required SSID not specified")),
@@ -89,47 +470,169 @@ pub async fn create_station(client: &Client, opts: StationOpts) -> Result<()> {
     // Create STA struct here so we can comprehensively search
     // for any matching existing connection, should it exist
     // Does not add connection to Network Manager, that happens later
-    //
-    // AP connection added for searching purposes. Does not add
-    // connection to Network Manager, it is purely local
     let sta_conn = create_sta_connection(&opts)?;
-    let ap_conn = create_access_point_connection(&opts.clone().into())?;
-
-    // Check for and deactivate any existing active station connections
-    // which share the same wireless interface.
-    //
-    // Station connection added for searching purposes. Does not add
-    // connection to Network Manager, it is purely local
-    match get_active_connection(client, DeviceType::Wifi, &sta_conn) {
-        Some(c) => {
-            debug!(
-                "Found active station connection with ifname \"{}\", deactivating",
-                wireless_ifname
-            );
-            client.deactivate_connection_future(&c).await?;
+
+    // Only possibly active, so assume deactivated until proven otherwise
+    let mut conn_state: ActiveConnectionState = ActiveConnectionState::Deactivated;
+    let mut ip4_addrs: Vec<StatusAddress> = vec![];
+    let mut ip6_addrs: Vec<StatusAddress> = vec![];
+    let mut route_metric: Option<i64> = None;
+    let mut assoc_ssid: Option<String> = None;
+    let mut assoc_bssid: Option<String> = None;
+    let uuid = opts.uuid.as_deref();
+    if let Some(c) = get_target_active_connection(client, DeviceType::Wifi, &sta_conn, uuid) {
+        conn_state = c.state();
+
+        // Gather active IPv4 info
+        if let Some(cfg) = c.ip4_config() {
+            // Active IPv4 addresses (i.e. non-NetworkManager configured)
+            ip4_addrs.append(&mut get_active_addrs(&cfg));
+            route_metric = get_default_route_metric(&cfg);
+        } else {
+            // Expected when station is still associating or waiting
+            // to get IP information
+            warn!(
+                "Unable to get IPv4 config for active station connection \"{}\"",
+                ssid
+            )
+        }
+
+        // Gather active IPv6 info. May be missing when IPv6 is disabled
+        if let Some(cfg) = c.ip6_config() {
+            ip6_addrs.append(&mut get_active_addrs(&cfg));
+        }
+
+        // Gather currently-associated AP info from the backing wireless device
+        let wireless_dev = c
+            .devices()
+            .into_iter()
+            .find_map(|d| d.downcast::<DeviceWifi>().ok());
+
+        match wireless_dev.and_then(|d| d.active_access_point()) {
+            Some(ap) => {
+                assoc_ssid = ap.ssid().map(|s| get_ssid_display_str(&s));
+                assoc_bssid = ap.bssid().map(|s| s.to_string());
+            }
+            None => warn!(
+                "Unable to get associated access point for active station connection \"{}\"",
+                ssid
+            ),
         }
-        None => debug!(
-            "No matching active wireless station connections for interface \"{}\"",
-            wireless_ifname
-        ),
     };
 
-    // Check for and deactivate any matching AP conn
-    match get_active_connection(client, DeviceType::Wifi, &ap_conn) {
-        Some(c) => {
-            debug!(
-                "Found active wireless connection with ifname \"{}\", deactivating",
-                wireless_ifname
-            );
-            client.deactivate_connection_future(&c).await?;
+    // Try to get connection that matches what we want from NetworkManager
+    // If it doesn't exist, no sense continuing
+    let sta_remote_conn = match get_target_connection(client, DeviceType::Wifi, &sta_conn, uuid)? {
+        Some(c) => c,
+        None => {
+            return Err(anyhow!("Station connection \"{}\" does not exist", ssid));
         }
-        None => debug!(
-            "No matching active wireless connections for interface \"{}\"",
-            wireless_ifname
-        ),
     };
+    let sta_conn = sta_remote_conn.upcast::<Connection>();
 
-    let wireless_dev = match client.device_by_iface(wireless_ifname.as_str()) {
+    // Gather station static info
+    let sta_ip4_settings = match sta_conn.setting_ip4_config() {
+        Some(c) => c,
+        None => {
+            return Err(anyhow!("Unable to get connection ip4 settings"));
+        }
+    };
+
+    // Effective metric when active, otherwise any configured metric
+    let route_metric = route_metric.or(get_route_metric(sta_ip4_settings.route_metric()));
+
+    let ip4_method = match sta_ip4_settings.method() {
+        Some(m) => m,
+        None => return Err(anyhow!("Unable to get ip4 configuration method")),
+    };
+
+    // Static IPv4 addresses
+    ip4_addrs.append(&mut get_static_addrs(&sta_ip4_settings));
+
+    // Gather static IPv6 info, if any. Connections created
+    // before IPv6 support was added may not have IPv6 settings
+    let ip6_method = match sta_conn.setting_ip6_config() {
+        Some(s) => {
+            ip6_addrs.append(&mut get_static_addrs(&s));
+            s.method()
+        }
+        None => None,
+    };
+
+    let report = StatusReport {
+        name: ssid.to_string(),
+        kind: "station".to_string(),
+        active: get_connection_state_str(conn_state).to_string(),
+        wireless: Some(WirelessStatus {
+            ssid: assoc_ssid,
+            bssid: assoc_bssid,
+            pinned_bssid: sta_conn
+                .setting_wireless()
+                .and_then(|s| s.bssid())
+                .map(|b| b.to_string()),
+        }),
+        bond_mode: None,
+        miimon: None,
+        slaves: None,
+        primary: None,
+        xmit_hash_policy: None,
+        mtu: sta_conn.setting_wireless().and_then(|s| get_mtu(s.mtu())),
+        route_metric,
+        ipv4_method: ip4_method.to_string(),
+        ipv4_addresses: ip4_addrs,
+        ipv4_routes: get_static_routes(&sta_ip4_settings),
+        ipv6_method: ip6_method.map_or(String::new(), |m| m.to_string()),
+        ipv6_addresses: ip6_addrs,
+        dns_over_tls: get_dns_over_tls_status(&sta_conn),
+        zone: get_zone(&sta_conn),
+    };
+    report.print(opts.output)?;
+    Ok(StatusCode::from_state(conn_state))
+}
+
+// Strongest signal of any AP broadcasting the SSID visible to the wireless device,
+// limited to the pinned BSSID when specified. None when not visible
+fn get_visible_signal(wireless_dev: &Device, ssid: &str, bssid: Option<&str>) -> Option<u8> {
+    let wireless_dev = wireless_dev.clone().downcast::<DeviceWifi>().ok()?;
+
+    wireless_dev
+        .access_points()
+        .into_iter()
+        .filter(|ap| ap.ssid().is_some_and(|s| s.as_ref() == ssid.as_bytes()))
+        .filter(|ap| bssid.is_none_or(|b| ap.bssid().is_some_and(|a| a.eq_ignore_ascii_case(b))))
+        .map(|ap| ap.strength())
+        .max()
+}
+
+// Avoid joining a network too weak to be usable. Scan results may be slightly stale,
+// as NetworkManager rate limits scans
+pub fn check_min_signal(signal: Option<u8>, min_signal: u8, ssid: &str) -> Result<()> {
+    match signal {
+        Some(signal) if signal >= min_signal => Ok(()),
+        Some(signal) => Err(anyhow!(
+            "Signal strength {} for SSID \"{}\" below minimum {}",
+            signal,
+            ssid,
+            min_signal
+        )),
+        None => Err(anyhow!(
+            "SSID \"{}\" not visible, unable to check minimum signal strength",
+            ssid
+        )),
+    }
+}
+
+// Request a scan on the given wireless interface and list visible networks, strongest
+// signal first. NetworkManager rate limits scans, so fall back to previous results
+#[instrument(skip(client), err)]
+pub async fn scan(
+    client: &impl NmClient,
+    wireless_ifname: &str,
+    output: OutputFormat,
+) -> Result<()> {
+    validate_ifname(wireless_ifname)?;
+
+    let device = match client.device_by_iface(wireless_ifname) {
         Some(device) => device,
         None => {
             return Err(anyhow!(
@@ -139,21 +642,197 @@ pub async fn create_station(client: &Client, opts: StationOpts) -> Result<()> {
         }
     };
 
-    info!("Creating station connection \"{}\"", ssid);
-    let sta_conn = client.add_connection_future(&sta_conn, true).await?;
+    let wireless_dev = match device.downcast::<DeviceWifi>() {
+        Ok(d) => d,
+        Err(_) => {
+            return Err(anyhow!(
+                "Interface \"{}\" is not a wireless device",
+                wireless_ifname
+            ))
+        }
+    };
 
-    info!("Activating station connection \"{}\"", ssid);
-    let sta_conn = client
-        .activate_connection_future(Some(&sta_conn), Some(&wireless_dev), None)
-        .await?;
+    info!(
+        "Scanning for wireless networks on interface \"{}\"",
+        wireless_ifname
+    );
+    match wireless_dev.request_scan_future().await {
+        // Request only starts scan, so give it a chance to finish
+        Ok(_) => glib::timeout_future(SCAN_WAIT).await,
+        Err(e) => warn!(
+            "Unable to request scan, listing previously visible networks: {}",
+            map_nm_error(e)
+        ),
+    }
 
-    // Waits until station is up and associated, not sure we want that
-    let res = wait_for_connection_to_activate(&sta_conn).await;
+    let mut results: Vec<ScanResult> = wireless_dev
+        .access_points()
+        .iter()
+        .map(|ap| ScanResult {
+            ssid: ap
+                .ssid()
+                .map_or(String::new(), |s| get_ssid_display_str(&s)),
+            bssid: ap.bssid().map_or(String::new(), |b| b.to_string()),
+            signal: ap.strength(),
+            security: get_ap_security_str(ap.flags(), ap.wpa_flags(), ap.rsn_flags()).to_string(),
+        })
+        .collect();
+    results.sort_by_key(|r| std::cmp::Reverse(r.signal));
 
-    if res.is_ok() {
-        info!("Activated access point connection \"{}\"", ssid);
+    print_scan_results(&results, output)
+}
+
+// Strongest key management advertised by an AP, in either its WPA or RSN (WPA2+)
+// information elements. Privacy without either implies legacy WEP
+fn get_ap_security_str(
+    flags: _80211ApFlags,
+    wpa_flags: _80211ApSecurityFlags,
+    rsn_flags: _80211ApSecurityFlags,
+) -> &'static str {
+    let has = |f: _80211ApSecurityFlags| wpa_flags.contains(f) || rsn_flags.contains(f);
+
+    if has(_80211ApSecurityFlags::KEY_MGMT_802_1X) {
+        "wpa-eap"
+    } else if has(_80211ApSecurityFlags::KEY_MGMT_SAE) {
+        "sae"
+    } else if has(_80211ApSecurityFlags::KEY_MGMT_PSK) {
+        "wpa-psk"
+    } else if has(_80211ApSecurityFlags::KEY_MGMT_OWE) {
+        "owe"
+    } else if flags.contains(_80211ApFlags::PRIVACY) {
+        "wep"
+    } else {
+        "open"
     }
-    res
+}
+
+// Bring existing station connection up or down without recreating or deleting it
+#[instrument(skip(client), err)]
+pub async fn set_station_active(
+    client: &impl NmClient,
+    opts: StationOpts,
+    active: bool,
+) -> Result<()> {
+    if opts.ssid.is_none() {
+        return Err(anyhow!("Required SSID not specified"));
+    }
+
+    let sta_conn = create_sta_connection(&opts)?;
+    let uuid = opts.uuid.as_deref();
+    match active {
+        true => {
+            activate_existing_connection(
+                client,
+                DeviceType::Wifi,
+                &sta_conn,
+                uuid,
+                opts.timeout,
+                opts.no_wait,
+                opts.dry_run,
+            )
+            .await
+        }
+        false => {
+            deactivate_existing_connection(
+                client,
+                DeviceType::Wifi,
+                &sta_conn,
+                uuid,
+                opts.timeout,
+                opts.no_wait,
+                opts.dry_run,
+            )
+            .await
+        }
+    }
+}
+
+#[instrument(skip(client), err)]
+pub async fn export_station(
+    client: &impl NmClient,
+    opts: StationOpts,
+    include_secrets: bool,
+) -> Result<()> {
+    let ssid = match &opts.ssid {
+        Some(ssid) => ssid,
+        None => return Err(anyhow!("Required SSID not specified")),
+    };
+
+    let sta_conn = create_sta_connection(&opts)?;
+
+    let sta_remote_conn = match get_connection(client, DeviceType::Wifi, &sta_conn) {
+        Some(c) => c,
+        None => {
+            return Err(anyhow!(
+                "Required station connection \"{}\" does not exist, quitting...",
+                &ssid
+            ));
+        }
+    };
+
+    export_connection(client, &sta_remote_conn, include_secrets).await
+}
+
+// Recover station options from an existing station connection, e.g. for export.
+// Secrets are not recovered, so password-protected stations have no password
+pub fn export_station_opts(conn: &Connection) -> Result<StationOpts> {
+    let s_wireless = match conn.setting_wireless() {
+        Some(s) => s,
+        None => return Err(anyhow!("Unable to get wireless settings")),
+    };
+
+    let key_mgmt = conn
+        .setting_wireless_security()
+        .and_then(|s| s.key_mgmt())
+        .and_then(|k| get_key_mgmt(&k));
+
+    // Enterprise settings, except the EAP password and CA certificate
+    let s_8021x = conn.setting_802_1x();
+    let eap_method = s_8021x
+        .as_ref()
+        .and_then(|s| s.eap_method(0))
+        .and_then(|m| {
+            EapMethod::value_variants()
+                .iter()
+                .find(|e| get_eap_method_str(**e) == m)
+                .copied()
+        });
+    let identity = s_8021x
+        .as_ref()
+        .and_then(|s| s.identity())
+        .map(|i| i.to_string());
+
+    let common = get_common_opts(conn)?;
+    let ssid = get_ssid_str(&s_wireless)?;
+    Ok(StationOpts {
+        wireless_ifname: conn.interface_name().map(|i| i.to_string()),
+        connection_id: common.id.filter(|id| *id != ssid),
+        ssid: Some(ssid),
+        bssid: s_wireless.bssid().map(|b| b.to_string()),
+        cloned_mac: s_wireless.cloned_mac_address().map(|m| m.to_string()),
+        mtu: get_mtu(s_wireless.mtu()),
+        powersave: get_powersave(s_wireless.powersave()),
+        route_metric: common.route_metric,
+        ip4_may_fail: common.ip4_may_fail,
+        dhcp_timeout: common.dhcp_timeout,
+        dhcp_client_id: common.dhcp_client_id,
+        dhcp_hostname: common.dhcp_hostname,
+        key_mgmt,
+        ip4_addrs: common.ip4_addrs,
+        ip4_method: common.ip4_method,
+        gateway: common.gateway,
+        dns: common.dns,
+        routes: common.routes,
+        ip6_addr: common.ip6_addr,
+        multi_connect: common.multi_connect,
+        autoconnect: common.autoconnect,
+        dns_over_tls: common.dns_over_tls,
+        stable_id: common.stable_id,
+        zone: common.zone,
+        eap_method,
+        identity,
+        ..Default::default()
+    })
 }
 
 pub fn create_sta_connection(opts: &StationOpts) -> Result<SimpleConnection> {
@@ -165,69 +844,205 @@ pub fn create_sta_connection(opts: &StationOpts) -> Result<SimpleConnection> {
 
     // General connection settings
     s_connection.set_type(Some(SETTING_WIRELESS_SETTING_NAME));
+    s_connection.set_autoconnect(opts.autoconnect.unwrap_or(true));
+
+    if let Some(multi_connect) = opts.multi_connect {
+        s_connection.set_multi_connect(multi_connect as i32);
+    }
+
+    if let Some(stable_id) = &opts.stable_id {
+        validate_stable_id(stable_id)?;
+        s_connection.set_stable_id(Some(stable_id));
+    }
+
+    if let Some(zone) = &opts.zone {
+        validate_zone(zone)?;
+        s_connection.set_zone(Some(zone));
+    }
+
+    if let Some(dns_over_tls) = opts.dns_over_tls {
+        set_dns_over_tls(&s_connection, dns_over_tls);
+    }
 
     match &opts.ssid {
         Some(ssid) => {
-            s_connection.set_id(Some(ssid));
+            s_connection.set_id(Some(opts.connection_id.as_deref().unwrap_or(ssid)));
         }
         None => return Err(anyhow!("Required SSID not specified")),
     };
 
-    match &opts.wireless_ifname {
-        Some(ifname) => s_connection.set_interface_name(Some(ifname)),
-        None => return Err(anyhow!("Required wireless interface not specified")),
-    };
+    // Unbound when no interface specified, usable on any wireless device
+    if let Some(ifname) = &opts.wireless_ifname {
+        s_connection.set_interface_name(Some(ifname));
+    }
 
     // Wifi-specific settings
     s_wireless.set_mode(Some(SETTING_WIRELESS_MODE_INFRA));
 
     match &opts.ssid {
         Some(ssid) => {
+            validate_ssid(ssid)?;
             s_wireless.set_ssid(Some(&(ssid.as_bytes().into())));
         }
         None => return Err(anyhow!("Required SSID not specified")),
     };
 
-    // Wifi security settings
-    if let Some(password) = &opts.password {
-        let s_wireless_security = SettingWirelessSecurity::new();
-        s_wireless_security.set_key_mgmt(Some("wpa-psk")); // TODO
-        s_wireless_security.set_psk(Some(password));
+    if let Some(cloned_mac) = &opts.cloned_mac {
+        validate_cloned_mac(cloned_mac)?;
+        s_wireless.set_cloned_mac_address(Some(cloned_mac));
+    }
+
+    if let Some(mtu) = opts.mtu {
+        validate_mtu(mtu)?;
+        s_wireless.set_mtu(mtu);
+    }
+
+    if let Some(powersave) = opts.powersave {
+        s_wireless.set_powersave(get_powersave_value(powersave));
+    }
+
+    // Leave BSSID unset unless explicitly requested, allowing
+    // station to roam between APs which share the same SSID
+    if let Some(bssid) = &opts.bssid {
+        validate_bssid(bssid)?;
+        s_wireless.set_bssid(Some(bssid));
+    }
+
+    // Wifi security settings, either enterprise or personal
+    if let Some((s_wireless_security, s_8021x)) = create_eap_settings(opts)? {
+        connection.add_setting(s_wireless_security);
+        connection.add_setting(s_8021x);
+    } else if let Some(s_wireless_security) =
+        create_wireless_security_setting(opts.password.as_deref(), opts.key_mgmt, opts.psk_min_len)?
+    {
         connection.add_setting(s_wireless_security);
     }
 
     // IPv4 settings
-    match &opts.ip4_addr {
-        Some(addr) => {
-            let ip4_net = Ipv4Net::from_str(addr)?;
-
-            let ip4_addr = IPAddress::new(
-                libc::AF_INET,
-                ip4_net.addr().to_string().as_str(),
-                ip4_net.prefix_len() as u32,
-            )?;
-
-            s_ip4.add_address(&ip4_addr);
-            s_ip4.set_method(Some(SETTING_IP4_CONFIG_METHOD_MANUAL));
-        }
-        None => {
-            s_ip4.set_method(Some(SETTING_IP4_CONFIG_METHOD_AUTO));
-        }
-    }
+    apply_route_metric(&s_ip4, opts.route_metric)?;
+    apply_ip4_method(&s_ip4, opts.ip4_method, &opts.ip4_addrs, Ip4Method::Auto)?;
+
+    add_ip4_routes(&s_ip4, &opts.routes)?;
+    apply_ip4_gateway_dns(
+        &s_ip4,
+        opts.gateway.as_deref(),
+        &opts.dns,
+        !opts.ip4_addrs.is_empty(),
+    )?;
+    apply_ip4_dhcp_timeout(&s_ip4, opts.dhcp_timeout, !opts.ip4_addrs.is_empty())?;
+    apply_ip4_dhcp_identity(
+        &s_ip4,
+        opts.dhcp_client_id.as_deref(),
+        opts.dhcp_hostname.as_deref(),
+    );
+
+    apply_ip4_may_fail(&s_ip4, opts.ip4_may_fail, opts.dhcp_timeout)?;
 
     connection.add_setting(s_connection);
     connection.add_setting(s_wireless);
     connection.add_setting(s_ip4);
+    connection.add_setting(create_ip6_setting(opts.ip6_addr.as_ref())?);
 
     Ok(connection)
 }
 
+// Enterprise wireless security and 802.1X settings, if an EAP method is specified
+fn create_eap_settings(
+    opts: &StationOpts,
+) -> Result<Option<(SettingWirelessSecurity, Setting8021x)>> {
+    validate_eap(opts)?;
+
+    let eap_method = match opts.eap_method {
+        Some(m) => m,
+        None => return Ok(None),
+    };
+
+    let s_wireless_security = SettingWirelessSecurity::new();
+    s_wireless_security.set_key_mgmt(Some(KEY_MGMT_WPA_EAP));
+
+    let s_8021x = Setting8021x::new();
+    s_8021x.add_eap_method(get_eap_method_str(eap_method));
+    s_8021x.set_phase2_auth(Some(EAP_PHASE2_AUTH));
+    s_8021x.set_identity(opts.identity.as_deref());
+    s_8021x.set_password(opts.eap_password.as_deref());
+
+    // libnm reads the certificate when set, so leave unset if since moved. Creation
+    // already checked it exists, and lookups don't match on the certificate
+    if let Some(ca_cert) = opts.ca_cert.as_deref().filter(|c| Path::new(c).is_file()) {
+        s_8021x
+            .set_ca_cert(Some(ca_cert), Setting8021xCKScheme::Path)
+            .map_err(map_nm_error)?;
+    }
+
+    Ok(Some((s_wireless_security, s_8021x)))
+}
+
+/// Key management for all enterprise networks, regardless of EAP method
+const KEY_MGMT_WPA_EAP: &str = "wpa-eap";
+
+/// Inner authentication used by both supported (tunneled) EAP methods
+const EAP_PHASE2_AUTH: &str = "mschapv2";
+
+// Ensure enterprise options are complete, and not mixed with personal network
+// options, before building settings
+fn validate_eap(opts: &StationOpts) -> Result<()> {
+    let eap_method = match opts.eap_method {
+        Some(m) => m,
+        None if opts.identity.is_some()
+            || opts.ca_cert.is_some()
+            || opts.eap_password.is_some() =>
+        {
+            return Err(anyhow!(
+                "Identity, CA certificate, and EAP password require an EAP method"
+            ))
+        }
+        None => return Ok(()),
+    };
+    let eap_method = get_eap_method_str(eap_method);
+
+    if opts.password.is_some() || opts.key_mgmt.is_some() {
+        return Err(anyhow!(
+            "EAP method \"{}\" cannot be used with a password or key management",
+            eap_method
+        ));
+    } else if opts.identity.is_none() {
+        return Err(anyhow!(
+            "EAP method \"{}\" requires an identity",
+            eap_method
+        ));
+    } else if opts.eap_password.is_none() {
+        return Err(anyhow!(
+            "EAP method \"{}\" requires an EAP password",
+            eap_method
+        ));
+    }
+
+    Ok(())
+}
+
+// CA certificate must exist when creating a station. Only checked on creation, as
+// lookups for existing connections (e.g. status) don't depend on the certificate
+fn validate_ca_cert(ca_cert: Option<&str>) -> Result<()> {
+    match ca_cert {
+        Some(ca_cert) if !Path::new(ca_cert).is_file() => {
+            Err(anyhow!("CA certificate \"{}\" does not exist", ca_cert))
+        }
+        _ => Ok(()),
+    }
+}
+
+fn get_eap_method_str(eap_method: EapMethod) -> &'static str {
+    match eap_method {
+        EapMethod::Peap => "peap",
+        EapMethod::Ttls => "ttls",
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::connection::mock::MockClient;
 
-    // Expect empty interface which should be caught later on
-    // when attempting to create connection
     #[test]
     fn no_wireless_interface() {
         let cfg = "
@@ -240,6 +1055,18 @@ mod test {
         assert!(opts.wireless_ifname.is_none());
     }
 
+    // Portable profile, not bound to any interface
+    #[test]
+    fn unbound_station() {
+        let cfg = "
+            ssid: \"test_ssid\"
+        ";
+
+        let opts = parse_station_opts(cfg).unwrap();
+        let conn = create_sta_connection(&opts).unwrap();
+        assert!(conn.interface_name().is_none());
+    }
+
     #[test]
     fn empty_wireless_interface() {
         let cfg = "
@@ -328,7 +1155,7 @@ mod test {
         ";
 
         let opts = parse_station_opts(cfg).unwrap();
-        assert!(opts.ip4_addr.is_none());
+        assert!(opts.ip4_addrs.is_empty());
     }
 
     #[test]
@@ -343,4 +1170,265 @@ mod test {
 
         parse_station_opts(cfg).unwrap();
     }
+
+    #[test]
+    fn multi_connect() {
+        let cfg = "
+            wireless_interface: \"test_interface\"
+            ssid: \"test_ssid\"
+            multi_connect: !Multiple
+        ";
+
+        let opts = parse_station_opts(cfg).unwrap();
+        assert_eq!(opts.multi_connect, Some(MultiConnect::Multiple));
+
+        let conn = create_sta_connection(&opts).unwrap();
+        let s_conn = conn.setting_connection().unwrap();
+        assert_eq!(s_conn.multi_connect(), ConnectionMultiConnect::Multiple);
+    }
+
+    #[test]
+    #[should_panic]
+    fn unexpected_multi_connect() {
+        let cfg = "
+            wireless_interface: \"test_interface\"
+            ssid: \"test_ssid\"
+            multi_connect: !UnexpectedMultiConnect
+        ";
+
+        parse_station_opts(cfg).unwrap();
+    }
+
+    #[test]
+    fn export_dhcp_identity() {
+        let cfg = "
+            wireless_interface: \"test_interface\"
+            ssid: \"test_ssid\"
+            dhcp_client_id: \"mac\"
+            dhcp_hostname: \"test_host\"
+        ";
+
+        let opts = parse_station_opts(cfg).unwrap();
+        let conn = create_sta_connection(&opts).unwrap();
+
+        let exported = export_station_opts(conn.upcast_ref()).unwrap();
+        assert_eq!(exported.dhcp_client_id.as_deref(), Some("mac"));
+        assert_eq!(exported.dhcp_hostname.as_deref(), Some("test_host"));
+    }
+
+    // Station should roam by default, only locking to a BSSID when requested
+    #[test]
+    fn roaming_station() {
+        let cfg = "
+            wireless_interface: \"test_interface\"
+            ssid: \"test_ssid\"
+        ";
+
+        let opts = parse_station_opts(cfg).unwrap();
+        assert!(opts.bssid.is_none());
+
+        let conn = create_sta_connection(&opts).unwrap();
+        let s_wireless = conn.setting_wireless().unwrap();
+        assert!(s_wireless.bssid().is_none());
+    }
+
+    #[test]
+    fn locked_bssid_station() {
+        let cfg = "
+            wireless_interface: \"test_interface\"
+            ssid: \"test_ssid\"
+            bssid: \"00:11:22:33:44:55\"
+        ";
+
+        let opts = parse_station_opts(cfg).unwrap();
+        assert_eq!(opts.bssid.as_deref(), Some("00:11:22:33:44:55"));
+
+        let conn = create_sta_connection(&opts).unwrap();
+        let s_wireless = conn.setting_wireless().unwrap();
+        assert_eq!(s_wireless.bssid().unwrap(), "00:11:22:33:44:55");
+    }
+
+    #[test]
+    fn powersave() {
+        let cfg = "
+            wireless_interface: \"test_interface\"
+            ssid: \"test_ssid\"
+            powersave: false
+        ";
+
+        let mut opts = parse_station_opts(cfg).unwrap();
+        assert_eq!(opts.powersave, Some(false));
+
+        let conn = create_sta_connection(&opts).unwrap();
+        let s_wireless = conn.setting_wireless().unwrap();
+        assert_eq!(get_powersave(s_wireless.powersave()), Some(false));
+
+        // Defaults to NetworkManager global configuration
+        opts.powersave = None;
+        let conn = create_sta_connection(&opts).unwrap();
+        let s_wireless = conn.setting_wireless().unwrap();
+        assert_eq!(get_powersave(s_wireless.powersave()), None);
+    }
+
+    #[test]
+    fn invalid_bssid_station() {
+        let cfg = "
+            wireless_interface: \"test_interface\"
+            ssid: \"test_ssid\"
+            bssid: \"00:11:22:33:44\"
+        ";
+
+        let opts = parse_station_opts(cfg).unwrap();
+        assert!(create_sta_connection(&opts).is_err());
+    }
+
+    #[test]
+    fn min_signal() {
+        assert!(check_min_signal(Some(70), 50, "test_ssid").is_ok());
+        assert!(check_min_signal(Some(50), 50, "test_ssid").is_ok());
+
+        // Too weak or not visible at all
+        assert!(check_min_signal(Some(49), 50, "test_ssid").is_err());
+        assert!(check_min_signal(None, 0, "test_ssid").is_err());
+    }
+
+    #[test]
+    fn sae_key_mgmt() {
+        let cfg = "
+            wireless_interface: \"test_interface\"
+            ssid: \"test_ssid\"
+            password: \"test_password\"
+            key_mgmt: !Sae
+        ";
+
+        let opts = parse_station_opts(cfg).unwrap();
+        assert_eq!(opts.key_mgmt, Some(KeyMgmt::Sae));
+    }
+
+    #[test]
+    #[should_panic]
+    fn unexpected_key_mgmt() {
+        let cfg = "
+            wireless_interface: \"test_interface\"
+            ssid: \"test_ssid\"
+            password: \"test_password\"
+            key_mgmt: !UnexpectedKeyMgmt
+        ";
+
+        parse_station_opts(cfg).unwrap();
+    }
+
+    #[test]
+    fn autoconnect() {
+        let cfg = "
+            wireless_interface: \"test_interface\"
+            ssid: \"test_ssid\"
+            autoconnect: false
+        ";
+
+        let mut opts = parse_station_opts(cfg).unwrap();
+        assert_eq!(opts.autoconnect, Some(false));
+
+        let conn = create_sta_connection(&opts).unwrap();
+        assert!(!conn.setting_connection().unwrap().autoconnect());
+
+        // Stations default to autoconnect on
+        opts.autoconnect = None;
+        let conn = create_sta_connection(&opts).unwrap();
+        assert!(conn.setting_connection().unwrap().autoconnect());
+    }
+
+    #[test]
+    fn eap_station() {
+        let cfg = "
+            wireless_interface: \"test_interface\"
+            ssid: \"test_ssid\"
+            eap_method: !Peap
+            identity: \"test_user\"
+            eap_password: \"test_eap_password\"
+        ";
+
+        let mut opts = parse_station_opts(cfg).unwrap();
+        assert_eq!(opts.eap_method, Some(EapMethod::Peap));
+        assert!(validate_eap(&opts).is_ok());
+
+        opts.ca_cert = Some(concat!(env!("CARGO_MANIFEST_DIR"), "/Cargo.toml").to_string());
+        assert!(validate_eap(&opts).is_ok());
+        assert!(validate_ca_cert(opts.ca_cert.as_deref()).is_ok());
+
+        // 1. CA certificate must exist on creation, but missing one doesn't
+        // prevent validating options for lookups, e.g. status
+        opts.ca_cert = Some("/nonexistent/ca.pem".to_string());
+        assert!(validate_eap(&opts).is_ok());
+        assert!(validate_ca_cert(opts.ca_cert.as_deref()).is_err());
+        opts.ca_cert = None;
+
+        // 2. Not mixed with personal network password
+        opts.password = Some("test_password".to_string());
+        assert!(validate_eap(&opts).is_err());
+        opts.password = None;
+
+        // 3. Identity required
+        opts.identity = None;
+        assert!(validate_eap(&opts).is_err());
+
+        // 4. Enterprise options without EAP method
+        opts.eap_method = None;
+        opts.identity = Some("test_user".to_string());
+        assert!(validate_eap(&opts).is_err());
+    }
+
+    #[test]
+    fn ap_security() {
+        let no_flags = _80211ApSecurityFlags::NONE;
+
+        assert_eq!(
+            get_ap_security_str(_80211ApFlags::NONE, no_flags, no_flags),
+            "open"
+        );
+        assert_eq!(
+            get_ap_security_str(_80211ApFlags::PRIVACY, no_flags, no_flags),
+            "wep"
+        );
+
+        // Key management from either WPA or RSN information elements
+        assert_eq!(
+            get_ap_security_str(
+                _80211ApFlags::PRIVACY,
+                _80211ApSecurityFlags::KEY_MGMT_PSK,
+                no_flags
+            ),
+            "wpa-psk"
+        );
+        assert_eq!(
+            get_ap_security_str(
+                _80211ApFlags::PRIVACY,
+                no_flags,
+                _80211ApSecurityFlags::KEY_MGMT_SAE
+            ),
+            "sae"
+        );
+        assert_eq!(
+            get_ap_security_str(
+                _80211ApFlags::PRIVACY,
+                no_flags,
+                _80211ApSecurityFlags::KEY_MGMT_802_1X
+            ),
+            "wpa-eap"
+        );
+    }
+
+    #[test]
+    fn delete_missing_station() {
+        let client = MockClient::default();
+        let opts = StationOpts {
+            ssid: Some("test_ssid".to_string()),
+            yes: true,
+            ..Default::default()
+        };
+
+        let res = glib::MainContext::default().block_on(delete_station(&client, opts));
+        assert!(res.is_err());
+        assert!(client.calls.borrow().is_empty());
+    }
 }
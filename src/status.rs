@@ -0,0 +1,765 @@
+use std::collections::HashMap;
+
+use anyhow::Result;
+use clap::ValueEnum;
+use nm::*;
+use serde::Serialize;
+use tracing::{instrument, warn};
+
+use crate::connection::{get_connection_state_str, get_ip4_route_str, NmClient};
+
+#[derive(ValueEnum, Default, PartialEq, Copy, Clone, Debug)]
+pub enum OutputFormat {
+    #[default]
+    Text,
+    Json,
+}
+
+/// Outcome of a status command, reported as the process exit code so scripts can tell
+/// a connection which is down from one which is missing. Missing connections are errors
+#[derive(PartialEq, Copy, Clone, Debug)]
+pub enum StatusCode {
+    Active,
+    /// Connection exists, but is not activated
+    Inactive,
+}
+
+impl StatusCode {
+    pub fn from_state(state: ActiveConnectionState) -> Self {
+        match state {
+            ActiveConnectionState::Activated => StatusCode::Active,
+            _ => StatusCode::Inactive,
+        }
+    }
+
+    pub fn exit_code(self) -> i32 {
+        match self {
+            StatusCode::Active => 0,
+            StatusCode::Inactive => 3,
+        }
+    }
+}
+
+#[derive(Serialize, PartialEq, Copy, Clone, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum AddressOrigin {
+    /// Currently assigned to the active connection
+    Active,
+    /// Configured in the connection's settings
+    Static,
+}
+
+#[derive(Serialize, PartialEq, Clone, Debug)]
+pub struct StatusAddress {
+    pub address: String,
+    pub origin: AddressOrigin,
+}
+
+#[derive(Serialize, Default, PartialEq, Clone, Debug)]
+pub struct WirelessStatus {
+    /// SSID of currently-associated AP, if any. For access points, their own SSID
+    pub ssid: Option<String>,
+
+    /// BSSID of currently-associated AP, if any
+    pub bssid: Option<String>,
+
+    /// BSSID station is pinned to, only reported for pinned stations
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pinned_bssid: Option<String>,
+}
+
+/// Bond slave interface and the state of its backing device
+#[derive(Serialize, Default, PartialEq, Clone, Debug)]
+pub struct SlaveStatus {
+    pub interface: String,
+
+    /// Device state, e.g. "activated" or "disconnected"
+    pub state: String,
+
+    /// Whether slave is currently carrying traffic, only reported in active-backup mode
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub active: Option<bool>,
+}
+
+#[derive(Serialize, Default, PartialEq, Clone, Debug)]
+pub struct StatusReport {
+    pub name: String,
+
+    /// Connection type, e.g. "bond"
+    pub kind: String,
+
+    /// Active connection state, e.g. "activated"
+    pub active: String,
+
+    /// SSID and association info, only reported for wireless connections.
+    /// BSSID only reported for stations
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wireless: Option<WirelessStatus>,
+
+    /// Bonding mode, e.g. "802.3ad", only reported for bond connections
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bond_mode: Option<String>,
+
+    /// Link monitoring interval in milliseconds, only reported for bond connections
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub miimon: Option<u32>,
+
+    /// Backing slave interfaces and their device states, only reported for bond connections
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub slaves: Option<Vec<SlaveStatus>>,
+
+    /// Preferred slave interface, only reported for bonds with one configured
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub primary: Option<String>,
+
+    /// Transmit hash policy, only reported for bonds with one configured
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub xmit_hash_policy: Option<String>,
+
+    /// Configured MTU, only reported when not the device default
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mtu: Option<u32>,
+
+    /// IPv4 default route metric when active, otherwise configured metric.
+    /// Only reported when known
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub route_metric: Option<i64>,
+
+    pub ipv4_method: String,
+    pub ipv4_addresses: Vec<StatusAddress>,
+
+    /// Configured static IPv4 routes, each "dest/prefix via gateway [metric]"
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub ipv4_routes: Vec<String>,
+
+    pub ipv6_method: String,
+    pub ipv6_addresses: Vec<StatusAddress>,
+
+    /// DNS-over-TLS mode, only reported when explicitly configured
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dns_over_tls: Option<String>,
+
+    /// Firewalld zone, only reported when explicitly configured
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub zone: Option<String>,
+}
+
+impl StatusReport {
+    pub fn print(&self, output: OutputFormat) -> Result<()> {
+        match output {
+            OutputFormat::Text => self.print_text(),
+            OutputFormat::Json => println!("{}", serde_json::to_string_pretty(self)?),
+        }
+
+        Ok(())
+    }
+
+    fn print_text(&self) {
+        println!("Name:\t\t{}", self.name);
+        println!("Type:\t\t{}", self.kind);
+        println!("Active:\t\t{}", self.active);
+
+        // Association status info, only present when active
+        if let Some(wireless) = &self.wireless {
+            println!("SSID:\t\t{}", wireless.ssid.as_deref().unwrap_or(""));
+            println!("BSSID:\t\t{}", wireless.bssid.as_deref().unwrap_or(""));
+
+            if let Some(pinned_bssid) = &wireless.pinned_bssid {
+                println!("Pinned BSSID:\t{pinned_bssid}");
+            }
+        }
+
+        if let Some(bond_mode) = &self.bond_mode {
+            println!("Mode:\t\t{bond_mode}");
+        }
+
+        if let Some(miimon) = self.miimon {
+            println!("Monitoring interval:\t{miimon}ms");
+        }
+
+        // Backing connections/devices
+        if let Some(slaves) = &self.slaves {
+            let slaves: Vec<String> = slaves.iter().map(get_slave_status_str).collect();
+            print_aligned("Slave devices:", &slaves);
+        }
+
+        if let Some(primary) = &self.primary {
+            println!("Primary:\t{primary}");
+        }
+
+        if let Some(policy) = &self.xmit_hash_policy {
+            println!("Hash policy:\t{policy}");
+        }
+
+        if let Some(mtu) = self.mtu {
+            println!("MTU:\t\t{mtu}");
+        }
+
+        if let Some(route_metric) = self.route_metric {
+            println!("Route metric:\t{route_metric}");
+        }
+
+        // IP status info
+        print_ip_status("IPv4", &self.ipv4_method, &self.ipv4_addresses);
+        if !self.ipv4_routes.is_empty() {
+            print_aligned("IPv4 routes:", &self.ipv4_routes);
+        }
+        print_ip_status("IPv6", &self.ipv6_method, &self.ipv6_addresses);
+
+        if let Some(dns_over_tls) = &self.dns_over_tls {
+            println!("DNS over TLS:\t{dns_over_tls}");
+        }
+
+        if let Some(zone) = &self.zone {
+            println!("Zone:\t\t{zone}");
+        }
+    }
+}
+
+/// One-line summary of any connection type nutil manages, used when listing connections
+#[derive(Serialize, PartialEq, Clone, Debug)]
+pub struct ConnectionSummary {
+    pub id: String,
+
+    /// Connection type, e.g. "wifi"
+    pub kind: String,
+
+    pub interface: String,
+
+    /// Active connection state, e.g. "activated"
+    pub active: String,
+}
+
+/// Wireless network visible to a station, found by scanning
+#[derive(Serialize, PartialEq, Clone, Debug)]
+pub struct ScanResult {
+    /// Empty for hidden networks
+    pub ssid: String,
+
+    pub bssid: String,
+
+    /// Signal strength, 0-100
+    pub signal: u8,
+
+    /// Strongest key management advertised, e.g. "sae", or "open"
+    pub security: String,
+}
+
+/// Connection missing settings every connection requires, e.g. one edited by hand
+#[derive(Serialize, PartialEq, Clone, Debug)]
+pub struct ConnectionAnomaly {
+    /// D-Bus object path, as malformed connections may not have an ID
+    pub path: String,
+
+    pub reason: String,
+}
+
+/// Failed command's error, reported on stderr when output is JSON
+#[derive(Serialize, PartialEq, Clone, Debug)]
+pub struct ErrorReport {
+    pub error: String,
+
+    /// Underlying causes, outermost first
+    pub context: Vec<String>,
+}
+
+impl From<&anyhow::Error> for ErrorReport {
+    fn from(err: &anyhow::Error) -> Self {
+        ErrorReport {
+            error: err.to_string(),
+            context: err.chain().skip(1).map(|e| e.to_string()).collect(),
+        }
+    }
+}
+
+// Print failed command's error as JSON so automation need not parse text
+pub fn print_error(err: &anyhow::Error) -> Result<()> {
+    eprintln!("{}", serde_json::to_string(&ErrorReport::from(err))?);
+    Ok(())
+}
+
+// Print one-line summary of every bond, wired, and wireless connection. Malformed
+// connections are reported separately so they can be cleaned up, rather than skipped
+#[instrument(skip(client), err)]
+pub fn list_connections(client: &impl NmClient, output: OutputFormat) -> Result<()> {
+    // Active connections keyed by connection UUID
+    let active_states: HashMap<String, ActiveConnectionState> = client
+        .active_connections()
+        .into_iter()
+        .filter_map(|c| Some((c.uuid()?.to_string(), c.state())))
+        .collect();
+
+    let mut summaries: Vec<ConnectionSummary> = vec![];
+    let mut anomalies: Vec<ConnectionAnomaly> = vec![];
+    for remote_conn in client.connections().into_iter() {
+        let conn = remote_conn.upcast::<Connection>();
+
+        if let Some(reason) = get_connection_anomaly(&conn) {
+            anomalies.push(ConnectionAnomaly {
+                path: conn.path().map_or(String::new(), |p| p.to_string()),
+                reason: reason.to_string(),
+            });
+            continue;
+        }
+
+        let kind = match conn
+            .connection_type()
+            .as_deref()
+            .and_then(get_conn_kind_str)
+        {
+            Some(kind) => kind,
+            None => continue,
+        };
+
+        // Only possibly active, so assume deactivated until proven otherwise
+        let conn_state = conn
+            .uuid()
+            .and_then(|uuid| active_states.get(uuid.as_str()).copied())
+            .unwrap_or(ActiveConnectionState::Deactivated);
+
+        summaries.push(ConnectionSummary {
+            id: conn.id().map_or(String::new(), |id| id.to_string()),
+            kind: kind.to_string(),
+            interface: conn
+                .interface_name()
+                .map_or(String::new(), |i| i.to_string()),
+            active: get_connection_state_str(conn_state).to_string(),
+        });
+    }
+    summaries.sort_by_key(|s| (s.kind.clone(), s.id.clone()));
+
+    match output {
+        OutputFormat::Text => {
+            for summary in summaries.iter() {
+                println!("{}", get_connection_summary_str(summary));
+            }
+
+            if !anomalies.is_empty() {
+                println!("\nMalformed connections (consider deleting):");
+                for anomaly in anomalies.iter() {
+                    println!("{}\t{}", anomaly.path, anomaly.reason);
+                }
+            }
+        }
+        OutputFormat::Json => {
+            println!("{}", serde_json::to_string_pretty(&summaries)?);
+
+            // Keep JSON output a list of summaries, so report anomalies out of band
+            for anomaly in anomalies.iter() {
+                warn!(
+                    "Malformed connection \"{}\": {}",
+                    anomaly.path, anomaly.reason
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
+
+// Reason connection is malformed, if it is. Only checks settings NetworkManager
+// requires of every connection, which nutil relies on to match connections
+fn get_connection_anomaly(conn: &impl ConnectionExt) -> Option<&'static str> {
+    let s_connection = match conn.setting_connection() {
+        Some(s) => s,
+        None => return Some("missing connection settings"),
+    };
+
+    if conn.connection_type().is_none() {
+        Some("missing connection type")
+    } else if s_connection.uuid().is_none() {
+        Some("missing UUID")
+    } else {
+        None
+    }
+}
+
+// Connection kind for setting type names nutil manages. None for all others, e.g. VPNs
+fn get_conn_kind_str(conn_type: &str) -> Option<&'static str> {
+    match conn_type {
+        SETTING_BOND_SETTING_NAME => Some("bond"),
+        SETTING_WIRED_SETTING_NAME => Some("wired"),
+        SETTING_WIRELESS_SETTING_NAME => Some("wifi"),
+        _ => None,
+    }
+}
+
+fn get_connection_summary_str(summary: &ConnectionSummary) -> String {
+    format!(
+        "{}\t{}\t{}\t{}",
+        summary.id, summary.kind, summary.interface, summary.active
+    )
+}
+
+pub fn print_scan_results(results: &[ScanResult], output: OutputFormat) -> Result<()> {
+    match output {
+        OutputFormat::Text => {
+            for result in results.iter() {
+                println!("{}", get_scan_result_str(result));
+            }
+        }
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(results)?),
+    }
+
+    Ok(())
+}
+
+fn get_scan_result_str(result: &ScanResult) -> String {
+    format!(
+        "{}\t{}\t{}\t{}",
+        result.ssid, result.bssid, result.signal, result.security
+    )
+}
+
+// Print connection status following a successful create, if requested
+pub fn show_status_after_create<F>(
+    create_res: Result<()>,
+    show_status: bool,
+    status: F,
+) -> Result<()>
+where
+    F: FnOnce() -> Result<()>,
+{
+    create_res?;
+
+    if show_status {
+        status()
+    } else {
+        Ok(())
+    }
+}
+
+// Print IP status info for use in status commands
+fn print_ip_status(family: &str, method: &str, addrs: &[StatusAddress]) {
+    println!("{family}:");
+    println!("  Method:\t{method}");
+
+    let addr_strs: Vec<String> = addrs.iter().map(get_status_addr_str).collect();
+    print_aligned("  Addresses:", &addr_strs);
+}
+
+// Print list of values after a label. First value printed
+// on same line as the label, remaining values aligned below it
+fn print_aligned(label: &str, values: &[String]) {
+    print!("{label}");
+    if values.is_empty() {
+        // Print first value on same line, but if no values, need newline
+        println!();
+    }
+    for (ix, value) in values.iter().enumerate() {
+        if ix == 0 {
+            println!("\t{value}");
+            continue;
+        }
+        println!("\t\t{value}");
+    }
+}
+
+// Slave interface followed by its state, e.g. "eth0 (activated, active)"
+fn get_slave_status_str(slave: &SlaveStatus) -> String {
+    match slave.active {
+        Some(true) => format!("{} ({}, active)", slave.interface, slave.state),
+        Some(false) => format!("{} ({}, backup)", slave.interface, slave.state),
+        None => format!("{} ({})", slave.interface, slave.state),
+    }
+}
+
+fn get_status_addr_str(addr: &StatusAddress) -> String {
+    let origin = match addr.origin {
+        AddressOrigin::Active => "active",
+        AddressOrigin::Static => "static",
+    };
+    format!("{}\t({})", addr.address, origin)
+}
+
+// Metric of active connection's default route, i.e. the one used to prefer
+// one connection over another. Lowest metric wins should there be several
+pub fn get_default_route_metric(cfg: &IPConfig) -> Option<i64> {
+    cfg.routes()
+        .iter()
+        .filter(|r| r.prefix() == 0)
+        .map(|r| r.metric())
+        .min()
+}
+
+// Gather static routes configured in connection's settings
+pub fn get_static_routes(settings: &impl SettingIPConfigExt) -> Vec<String> {
+    (0..settings.num_routes())
+        .filter_map(|ix| settings.route(ix as i32))
+        .map(|r| get_ip4_route_str(&r))
+        .collect()
+}
+
+// Gather addresses currently assigned to an active connection (IPv4 or IPv6)
+pub fn get_active_addrs(cfg: &IPConfig) -> Vec<StatusAddress> {
+    let mut addrs: Vec<StatusAddress> = vec![];
+
+    for (ix, ip_addr) in cfg.addresses().iter().enumerate() {
+        match ip_addr.address() {
+            Some(addr) => addrs.push(StatusAddress {
+                address: addr.to_string(),
+                origin: AddressOrigin::Active,
+            }),
+            None => warn!("Unable to get active address string with index \"{}\"", ix),
+        }
+    }
+
+    addrs
+}
+
+// Gather statically-configured addresses from a connection's IP settings (IPv4 or IPv6)
+pub fn get_static_addrs(settings: &impl SettingIPConfigExt) -> Vec<StatusAddress> {
+    let mut addrs: Vec<StatusAddress> = vec![];
+
+    for ix in 0..settings.num_addresses() {
+        match settings.address(ix as i32) {
+            // Why does this take a signed int lmao
+            Some(c) => match c.address() {
+                Some(addr) => addrs.push(StatusAddress {
+                    address: addr.to_string(),
+                    origin: AddressOrigin::Static,
+                }),
+                None => warn!("Unable to get address string with index \"{}\"", ix),
+            },
+            None => warn!("Unable to get address with index \"{}\"", ix),
+        }
+    }
+
+    addrs
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn test_report() -> StatusReport {
+        StatusReport {
+            name: "bond0".to_string(),
+            kind: "bond".to_string(),
+            active: "activated".to_string(),
+            wireless: None,
+            bond_mode: Some("active-backup".to_string()),
+            miimon: Some(100),
+            slaves: Some(vec![
+                SlaveStatus {
+                    interface: "eth0".to_string(),
+                    state: "activated".to_string(),
+                    active: Some(true),
+                },
+                SlaveStatus {
+                    interface: "eth1".to_string(),
+                    state: "disconnected".to_string(),
+                    active: Some(false),
+                },
+            ]),
+            primary: None,
+            xmit_hash_policy: None,
+            mtu: None,
+            route_metric: None,
+            ipv4_method: "manual".to_string(),
+            ipv4_addresses: vec![StatusAddress {
+                address: "192.0.2.1".to_string(),
+                origin: AddressOrigin::Static,
+            }],
+            ipv4_routes: vec![],
+            ipv6_method: "auto".to_string(),
+            ipv6_addresses: vec![],
+            dns_over_tls: None,
+            zone: None,
+        }
+    }
+
+    #[test]
+    fn json_report() {
+        let report = test_report();
+        let json: serde_json::Value = serde_json::to_value(&report).unwrap();
+
+        assert_eq!(json["name"], "bond0");
+        assert_eq!(json["kind"], "bond");
+        assert_eq!(json["active"], "activated");
+        assert_eq!(json["bond_mode"], "active-backup");
+        assert_eq!(json["miimon"], 100);
+        assert_eq!(json["slaves"][0]["interface"], "eth0");
+        assert_eq!(json["slaves"][0]["state"], "activated");
+        assert_eq!(json["slaves"][0]["active"], true);
+        assert_eq!(json["slaves"][1]["interface"], "eth1");
+        assert_eq!(json["slaves"][1]["active"], false);
+        assert_eq!(json["ipv4_method"], "manual");
+        assert_eq!(json["ipv4_addresses"][0]["address"], "192.0.2.1");
+        assert_eq!(json["ipv4_addresses"][0]["origin"], "static");
+        assert_eq!(json["ipv6_addresses"], serde_json::json!([]));
+
+        // Not applicable to bonds, so omitted entirely
+        assert!(json.get("wireless").is_none());
+    }
+
+    #[test]
+    fn scan_result_str() {
+        let result = ScanResult {
+            ssid: "test-ssid".to_string(),
+            bssid: "00:11:22:33:44:55".to_string(),
+            signal: 80,
+            security: "sae".to_string(),
+        };
+        assert_eq!(
+            get_scan_result_str(&result),
+            "test-ssid\t00:11:22:33:44:55\t80\tsae"
+        );
+
+        let json: serde_json::Value = serde_json::to_value(&result).unwrap();
+        assert_eq!(json["signal"], 80);
+    }
+
+    #[test]
+    fn slave_status_str() {
+        let mut slave = SlaveStatus {
+            interface: "eth0".to_string(),
+            state: "activated".to_string(),
+            active: None,
+        };
+        assert_eq!(get_slave_status_str(&slave), "eth0 (activated)");
+
+        slave.active = Some(true);
+        assert_eq!(get_slave_status_str(&slave), "eth0 (activated, active)");
+
+        slave.active = Some(false);
+        assert_eq!(get_slave_status_str(&slave), "eth0 (activated, backup)");
+    }
+
+    #[test]
+    fn json_report_wireless() {
+        let report = StatusReport {
+            kind: "station".to_string(),
+            wireless: Some(WirelessStatus {
+                ssid: Some("test-ssid".to_string()),
+                bssid: None,
+                pinned_bssid: Some("00:11:22:33:44:55".to_string()),
+            }),
+            bond_mode: None,
+            miimon: None,
+            slaves: None,
+            ..test_report()
+        };
+        let json: serde_json::Value = serde_json::to_value(&report).unwrap();
+
+        assert_eq!(json["wireless"]["ssid"], "test-ssid");
+        assert!(json["wireless"]["bssid"].is_null());
+        assert_eq!(json["wireless"]["pinned_bssid"], "00:11:22:33:44:55");
+        assert!(json.get("bond_mode").is_none());
+        assert!(json.get("slaves").is_none());
+    }
+
+    #[test]
+    fn status_after_create() {
+        use anyhow::anyhow;
+        use std::cell::Cell;
+
+        let shown = Cell::new(false);
+        let status = || {
+            shown.set(true);
+            Ok(())
+        };
+
+        // 1. Flag set and create succeeded, expect status shown
+        show_status_after_create(Ok(()), true, status).unwrap();
+        assert!(shown.replace(false));
+
+        // 2. Flag not set, expect status not shown
+        show_status_after_create(Ok(()), false, status).unwrap();
+        assert!(!shown.get());
+
+        // 3. Create failed, expect error and status not shown
+        let res = show_status_after_create(Err(anyhow!("create failed")), true, status);
+        assert!(res.is_err());
+        assert!(!shown.get());
+    }
+
+    #[test]
+    fn connection_kind() {
+        assert_eq!(get_conn_kind_str(SETTING_BOND_SETTING_NAME), Some("bond"));
+        assert_eq!(get_conn_kind_str(SETTING_WIRED_SETTING_NAME), Some("wired"));
+        assert_eq!(
+            get_conn_kind_str(SETTING_WIRELESS_SETTING_NAME),
+            Some("wifi")
+        );
+        assert_eq!(get_conn_kind_str("vpn"), None);
+    }
+
+    #[test]
+    fn connection_summary() {
+        let summary = ConnectionSummary {
+            id: "bond0".to_string(),
+            kind: "bond".to_string(),
+            interface: "bond0".to_string(),
+            active: "activated".to_string(),
+        };
+
+        assert_eq!(
+            get_connection_summary_str(&summary),
+            "bond0\tbond\tbond0\tactivated"
+        );
+    }
+
+    #[test]
+    fn connection_anomaly() {
+        // No settings at all, so reported rather than silently skipped
+        let conn = SimpleConnection::new();
+        assert_eq!(
+            get_connection_anomaly(&conn),
+            Some("missing connection settings")
+        );
+
+        let s_connection = SettingConnection::new();
+        conn.add_setting(s_connection.clone());
+        assert_eq!(
+            get_connection_anomaly(&conn),
+            Some("missing connection type")
+        );
+
+        s_connection.set_type(Some(SETTING_WIRED_SETTING_NAME));
+        s_connection.set_uuid(Some(&utils_uuid_generate()));
+        assert_eq!(get_connection_anomaly(&conn), None);
+    }
+
+    #[test]
+    fn status_code() {
+        let active = StatusCode::from_state(ActiveConnectionState::Activated);
+        assert_eq!(active, StatusCode::Active);
+        assert_eq!(active.exit_code(), 0);
+
+        for state in [
+            ActiveConnectionState::Activating,
+            ActiveConnectionState::Deactivating,
+            ActiveConnectionState::Deactivated,
+        ] {
+            let code = StatusCode::from_state(state);
+            assert_eq!(code, StatusCode::Inactive);
+            assert_eq!(code.exit_code(), 3);
+        }
+    }
+
+    #[test]
+    fn error_report() {
+        let err = anyhow::anyhow!("Device \"wlan0\" not found")
+            .context("Failed to find wireless device")
+            .context("Failed to create station");
+        let report = ErrorReport::from(&err);
+
+        assert_eq!(report.error, "Failed to create station");
+        assert_eq!(
+            report.context,
+            vec![
+                "Failed to find wireless device",
+                "Device \"wlan0\" not found"
+            ]
+        );
+
+        let json: serde_json::Value = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["error"], "Failed to create station");
+        assert_eq!(json["context"].as_array().unwrap().len(), 2);
+
+        // No context for a single error
+        let report = ErrorReport::from(&anyhow::anyhow!("Required SSID not specified"));
+        assert!(report.context.is_empty());
+    }
+}
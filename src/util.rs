@@ -1,9 +1,36 @@
+use std::fs::File;
+use std::io::{IsTerminal, Read, Write};
+use std::str::FromStr;
+
 use anyhow::{anyhow, Result};
+use ipnet::Ipv6Net;
 use nm::utils_wpa_psk_valid;
-use serde::{de::Error, Deserialize, Deserializer};
+use serde::{
+    de::{DeserializeOwned, Error, Visitor},
+    Deserialize, Deserializer, Serializer,
+};
+use serde_yaml::{Mapping, Value};
 
 pub const DEFAULT_IP4_ADDR: &str = "192.0.2.1/24";
 
+/// Maximum interface name length, excluding trailing NUL (IFNAMSIZ - 1)
+pub const MAX_IFNAME_LEN: usize = 15;
+
+/// Maximum SSID length in bytes (IEEE 802.11)
+pub const MAX_SSID_LEN: usize = 32;
+
+/// Minimum WPA-PSK passphrase length in bytes (IEEE 802.11i)
+pub const MIN_PSK_LEN: usize = 8;
+
+/// Maximum WPA-PSK passphrase length in bytes. 64 byte passwords are hex keys
+pub const MAX_PSK_LEN: usize = 63;
+
+/// Length of a raw 256-bit WPA-PSK key in hex digits
+pub const HEX_PSK_LEN: usize = 64;
+
+/// Config path which reads config from stdin, e.g. when piping config in
+pub const STDIN_CONFIG_PATH: &str = "-";
+
 pub fn deserialize_password<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
 where
     D: Deserializer<'de>,
@@ -12,15 +39,372 @@ where
 
     if s.is_empty() {
         Ok(None)
-    } else if s.len() < 8 {
-        Err(anyhow!("Password must be 8 chars or longer")).map_err(D::Error::custom)
-    } else if !utils_wpa_psk_valid(s.as_str()) {
-        Err(anyhow!("libnm says your PSK is invalid ¯\\_(ツ)_/¯")).map_err(D::Error::custom)
     } else {
+        validate_psk(&s, MIN_PSK_LEN).map_err(D::Error::custom)?;
         Ok(Some(s))
     }
 }
 
+// Check a WPA-PSK password against the given minimum length, then against
+// libnm, which has the final say on whether NetworkManager accepts it
+pub fn validate_psk(psk: &str, min_len: usize) -> Result<()> {
+    let len = psk.len();
+
+    // Raw keys are used as is rather than hashed like passphrases, so
+    // passphrase length rules, including any stricter minimum, don't apply
+    if is_hex_psk(psk) {
+        return Ok(());
+    }
+
+    if (MIN_PSK_LEN..min_len).contains(&len) {
+        return Err(anyhow!(
+            "Invalid password: too short, must be at least {min_len} characters long \
+             (got {len} bytes)"
+        ));
+    }
+
+    if !(MIN_PSK_LEN..=MAX_PSK_LEN).contains(&len) || !utils_wpa_psk_valid(psk) {
+        return Err(anyhow!("Invalid password: {}", get_invalid_psk_reason(psk)));
+    }
+    Ok(())
+}
+
+// Whether the password is a raw 256-bit key (64 hex digits) rather than a passphrase
+pub fn is_hex_psk(psk: &str) -> bool {
+    psk.len() == HEX_PSK_LEN && psk.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+// Minimum password length may only raise the WPA-PSK minimum
+pub fn validate_psk_min_len(min_len: usize) -> Result<()> {
+    if !(MIN_PSK_LEN..=MAX_PSK_LEN).contains(&min_len) {
+        return Err(anyhow!(
+            "Minimum password length {min_len} must be between {MIN_PSK_LEN} and {MAX_PSK_LEN}"
+        ));
+    }
+    Ok(())
+}
+
+// Explain which WPA-PSK rule an invalid password breaks. Passphrases are 8 to 63
+// bytes long, while 64 byte passwords are treated as a raw key and must be hex
+fn get_invalid_psk_reason(psk: &str) -> String {
+    let len = psk.len();
+    let non_ascii_hint = if psk.is_ascii() {
+        ""
+    } else {
+        " Non-ASCII characters count as multiple bytes."
+    };
+
+    if !(MIN_PSK_LEN..=HEX_PSK_LEN).contains(&len) {
+        format!(
+            "must be between {MIN_PSK_LEN} and {MAX_PSK_LEN} characters long, or a \
+             {HEX_PSK_LEN} character hex key (got {len} bytes).{non_ascii_hint}"
+        )
+    } else if len == HEX_PSK_LEN && !psk.is_ascii() {
+        format!(
+            "contains non-ASCII characters. {HEX_PSK_LEN} byte passwords are treated as a \
+             hex key, so use only hex characters (0-9, a-f) or shorten to {MAX_PSK_LEN} \
+             bytes or fewer"
+        )
+    } else if len == HEX_PSK_LEN {
+        format!(
+            "is {HEX_PSK_LEN} characters so is treated as a hex key, but contains non-hex \
+             characters. Use only hex characters (0-9, a-f) or shorten to {MAX_PSK_LEN} \
+             characters or fewer"
+        )
+    } else if !psk.is_ascii() {
+        "contains non-ASCII characters, which NetworkManager rejects".to_string()
+    } else {
+        "rejected by NetworkManager".to_string()
+    }
+}
+
+// Ask user to confirm a destructive action, e.g. deleting a connection, unless
+// already confirmed with "--yes". Never blocks waiting on non-interactive stdin
+pub fn confirm_action(action: &str, yes: bool) -> Result<()> {
+    if yes {
+        return Ok(());
+    }
+
+    if !std::io::stdin().is_terminal() {
+        return Err(anyhow!(
+            "Not confirmed to {}, as stdin is not a terminal. Rerun with \"--yes\" to confirm",
+            action
+        ));
+    }
+
+    eprint!("About to {}. Continue? [y/N] ", action);
+    std::io::stderr().flush()?;
+
+    let mut answer = String::new();
+    std::io::stdin().read_line(&mut answer)?;
+
+    if !is_confirmed(&answer) {
+        return Err(anyhow!("Not confirmed to {}, quitting...", action));
+    }
+    Ok(())
+}
+
+fn is_confirmed(answer: &str) -> bool {
+    matches!(answer.trim().to_lowercase().as_str(), "y" | "yes")
+}
+
+// Catch invalid interface names before they surface as
+// libnm errors deep in connection activation
+pub fn validate_ifname(ifname: &str) -> Result<()> {
+    if ifname.is_empty() {
+        return Err(anyhow!("Empty string is not a valid interface name"));
+    } else if ifname.len() > MAX_IFNAME_LEN {
+        return Err(anyhow!(
+            "Interface name \"{}\" is longer than {} characters",
+            ifname,
+            MAX_IFNAME_LEN
+        ));
+    } else if ifname.contains('/') || ifname.chars().any(char::is_whitespace) {
+        return Err(anyhow!(
+            "Interface name \"{}\" must not contain \"/\" or whitespace",
+            ifname
+        ));
+    }
+
+    Ok(())
+}
+
+// SSIDs are limited in bytes rather than characters, so
+// multi-byte UTF-8 SSIDs may have fewer than 32 characters
+pub fn validate_ssid(ssid: &str) -> Result<()> {
+    if ssid.is_empty() {
+        return Err(anyhow!("Empty string is not a valid SSID"));
+    } else if ssid.len() > MAX_SSID_LEN {
+        return Err(anyhow!(
+            "SSID \"{}\" is {} bytes long, longer than {} bytes",
+            ssid,
+            ssid.len(),
+            MAX_SSID_LEN
+        ));
+    }
+
+    Ok(())
+}
+
+// Check for colon-separated MAC address, e.g. "00:11:22:aa:bb:cc"
+pub fn is_valid_mac_addr(addr: &str) -> bool {
+    let octets: Vec<&str> = addr.split(':').collect();
+
+    octets.len() == 6
+        && octets
+            .iter()
+            .all(|o| o.len() == 2 && o.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+pub fn validate_bssid(bssid: &str) -> Result<()> {
+    if !is_valid_mac_addr(bssid) {
+        return Err(anyhow!(
+            "Invalid BSSID \"{}\", expected a MAC address, e.g. \"00:11:22:aa:bb:cc\"",
+            bssid
+        ));
+    }
+
+    Ok(())
+}
+
+/// Strongest signal strength reported by NetworkManager, as a percentage
+pub const MAX_SIGNAL: u8 = 100;
+
+pub fn validate_min_signal(min_signal: u8) -> Result<()> {
+    if min_signal > MAX_SIGNAL {
+        return Err(anyhow!(
+            "Invalid minimum signal \"{}\", expected 0 to {}",
+            min_signal,
+            MAX_SIGNAL
+        ));
+    }
+
+    Ok(())
+}
+
+/// Special cloned MAC address values understood by NetworkManager
+pub const CLONED_MAC_MODES: [&str; 3] = ["random", "stable", "permanent"];
+
+// Cloned MAC address may be either an explicit MAC address or one of
+// NetworkManager's special values, e.g. "random" for a new MAC every activation
+pub fn validate_cloned_mac(mac: &str) -> Result<()> {
+    if CLONED_MAC_MODES.contains(&mac) || is_valid_mac_addr(mac) {
+        Ok(())
+    } else {
+        Err(anyhow!(
+            "Invalid cloned MAC address \"{}\", expected a MAC address or one of {:?}",
+            mac,
+            CLONED_MAC_MODES
+        ))
+    }
+}
+
+/// Smallest MTU permitted for IPv4 (RFC 791)
+pub const MIN_MTU: u32 = 68;
+
+/// Largest MTU permitted, i.e. a typical jumbo frame
+pub const MAX_MTU: u32 = 9000;
+
+pub fn validate_mtu(mtu: u32) -> Result<()> {
+    if !(MIN_MTU..=MAX_MTU).contains(&mtu) {
+        return Err(anyhow!(
+            "Invalid MTU \"{}\", expected between {} and {}",
+            mtu,
+            MIN_MTU,
+            MAX_MTU
+        ));
+    }
+
+    Ok(())
+}
+
+// Accept either a single IPv4 address string or a list of them. Empty strings ignored
+pub fn deserialize_ip4_addrs<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany {
+        One(String),
+        Many(Vec<String>),
+    }
+
+    let addrs = match OneOrMany::deserialize(deserializer)? {
+        OneOrMany::One(addr) => vec![addr],
+        OneOrMany::Many(addrs) => addrs,
+    };
+
+    Ok(addrs.into_iter().filter(|a| !a.is_empty()).collect())
+}
+
+pub fn deserialize_ip6_addr<'de, D>(deserializer: D) -> Result<Option<Ipv6Net>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let s: String = Deserialize::deserialize(deserializer)?;
+
+    if s.is_empty() {
+        Ok(None)
+    } else {
+        Ipv6Net::from_str(&s).map(Some).map_err(D::Error::custom)
+    }
+}
+
+// Read config file at given path, or from stdin when path is "-"
+pub fn read_config(path: &str) -> Result<String> {
+    let reader: Box<dyn Read> = match path {
+        STDIN_CONFIG_PATH => Box::new(std::io::stdin()),
+        _ => Box::new(File::open(path)?),
+    };
+
+    read_config_from(reader)
+}
+
+pub fn read_config_from(mut reader: Box<dyn Read>) -> Result<String> {
+    let mut buf = vec![];
+    reader.read_to_end(&mut buf)?;
+
+    Ok(String::from_utf8(buf)?)
+}
+
+// Patch top-level fields of a YAML config with "field=value" overrides, returning
+// the merged config. Values are parsed as YAML, so lists and tagged enums work, e.g.
+// "slave_interfaces=[eth0, eth1]". Overrides of fields not in the given known fields
+// are rejected, as a typo would otherwise be silently ignored
+pub fn apply_config_overrides(
+    config: &str,
+    overrides: &[String],
+    known_fields: &[&str],
+) -> Result<String> {
+    if overrides.is_empty() {
+        return Ok(config.to_string());
+    }
+
+    let mut fields = match serde_yaml::from_str(config)? {
+        Value::Mapping(m) => m,
+        Value::Null => Mapping::new(),
+        _ => return Err(anyhow!("Config file must be a mapping of fields to values")),
+    };
+
+    for o in overrides {
+        let (field, value) = match o.split_once('=') {
+            Some((f, v)) if !f.trim().is_empty() => (f.trim(), v.trim()),
+            _ => {
+                return Err(anyhow!(
+                    "Invalid override \"{}\", expected \"field=value\"",
+                    o
+                ))
+            }
+        };
+
+        if !known_fields.contains(&field) {
+            return Err(anyhow!(
+                "Unknown override field \"{}\", expected one of {:?}",
+                field,
+                known_fields
+            ));
+        }
+
+        // Empty value clears field rather than setting it to null
+        let value = if value.is_empty() {
+            Value::String(String::new())
+        } else {
+            serde_yaml::from_str(value)
+                .map_err(|e| anyhow!("Invalid value for override \"{}\": {}", field, e))?
+        };
+
+        fields.insert(Value::String(field.to_string()), value);
+    }
+
+    Ok(serde_yaml::to_string(&fields)?)
+}
+
+// Top-level fields a config struct accepts, as named in config files (e.g.
+// "bond_interface"), including aliases. Captured from the field names serde
+// passes when deserializing the struct, so always matches the struct itself
+pub fn config_fields<T: DeserializeOwned>() -> &'static [&'static str] {
+    struct FieldsDeserializer<'a>(&'a mut &'static [&'static str]);
+
+    impl<'de> Deserializer<'de> for FieldsDeserializer<'_> {
+        type Error = serde::de::value::Error;
+
+        fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Self::Error> {
+            Err(Self::Error::custom("expected struct"))
+        }
+
+        fn deserialize_struct<V: Visitor<'de>>(
+            self,
+            _name: &'static str,
+            fields: &'static [&'static str],
+            _visitor: V,
+        ) -> Result<V::Value, Self::Error> {
+            *self.0 = fields;
+            Err(Self::Error::custom("fields captured"))
+        }
+
+        serde::forward_to_deserialize_any! {
+            bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+            bytes byte_buf option unit unit_struct newtype_struct seq tuple
+            tuple_struct map enum identifier ignored_any
+        }
+    }
+
+    let mut fields: &'static [&'static str] = &[];
+    let _ = T::deserialize(FieldsDeserializer(&mut fields));
+    fields
+}
+
+pub fn serialize_ip6_addr<S>(addr: &Option<Ipv6Net>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    match addr {
+        Some(addr) => serializer.serialize_str(&addr.to_string()),
+        None => serializer.serialize_str(""),
+    }
+}
+
 //pub fn deserialize_ip4_addr<'de, D>(deserializer: D) -> Result<Ipv4Net, D::Error>
 //where
 //    D: Deserializer<'de>,
@@ -29,3 +413,252 @@ where
 //
 //    Ipv4Net::from_str(&s).map_err(D::Error::custom)
 //}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn get_password_err(password: &str) -> String {
+        let yaml = format!("\"{password}\"");
+        let deserializer = serde_yaml::Deserializer::from_str(&yaml);
+
+        match deserialize_password(deserializer) {
+            Ok(_) => panic!("Expected password \"{password}\" to be invalid"),
+            Err(e) => e.to_string(),
+        }
+    }
+
+    #[test]
+    fn too_short_password_err() {
+        let err = get_password_err("123");
+        assert!(err.contains("between 8 and 63 characters"));
+        assert!(err.contains("got 3 bytes"));
+    }
+
+    #[test]
+    fn non_ascii_password_err() {
+        // 64 bytes, so treated as a hex key
+        let password = "ü".repeat(32);
+        let err = get_password_err(&password);
+        assert!(err.contains("non-ASCII"));
+        assert!(err.contains("hex"));
+
+        // Too long once multi-byte characters are counted
+        let password = "ü".repeat(40);
+        let err = get_password_err(&password);
+        assert!(err.contains("got 80 bytes"));
+        assert!(err.contains("Non-ASCII characters count as multiple bytes"));
+    }
+
+    #[test]
+    fn seven_char_password_err() {
+        let err = get_password_err("1234567");
+        assert!(err.contains("between 8 and 63 characters"));
+        assert!(err.contains("got 7 bytes"));
+    }
+
+    #[test]
+    fn hex_key_password() {
+        let password = "0123456789abcdef".repeat(4);
+        assert!(validate_psk(&password, MIN_PSK_LEN).is_ok());
+
+        // Stricter minimum only applies to passphrases
+        assert!(validate_psk(&password, MAX_PSK_LEN).is_ok());
+
+        // Mixed case hex digits are valid too
+        let password = "0123456789ABCDEF".repeat(4);
+        assert!(is_hex_psk(&password));
+
+        let yaml = format!("\"{password}\"");
+        let deserializer = serde_yaml::Deserializer::from_str(&yaml);
+        assert_eq!(deserialize_password(deserializer).unwrap(), Some(password));
+    }
+
+    #[test]
+    fn invalid_63_char_password_err() {
+        // 63 bytes, but not a valid passphrase
+        let password = format!("{}ü", "a".repeat(61));
+        assert_eq!(password.len(), 63);
+        assert!(!is_hex_psk(&password));
+
+        let err = get_password_err(&password);
+        assert!(err.contains("non-ASCII"));
+    }
+
+    #[test]
+    fn psk_min_len() {
+        let err = validate_psk("test_password", 16).unwrap_err().to_string();
+        assert!(err.contains("too short"));
+        assert!(err.contains("at least 16 characters"));
+        assert!(err.contains("got 13 bytes"));
+
+        assert!(validate_psk_min_len(MIN_PSK_LEN).is_ok());
+        assert!(validate_psk_min_len(MAX_PSK_LEN).is_ok());
+        assert!(validate_psk_min_len(7).is_err());
+        assert!(validate_psk_min_len(64).is_err());
+    }
+
+    #[test]
+    fn non_hex_key_password_err() {
+        let password = "g".repeat(64);
+        let err = get_password_err(&password);
+        assert!(err.contains("non-hex characters"));
+    }
+
+    #[test]
+    fn confirm() {
+        assert!(confirm_action("delete connection", true).is_ok());
+
+        assert!(is_confirmed("y\n"));
+        assert!(is_confirmed(" YES "));
+        assert!(!is_confirmed("\n"));
+        assert!(!is_confirmed("n"));
+        assert!(!is_confirmed("yess"));
+    }
+
+    #[test]
+    fn valid_ifname() {
+        assert!(validate_ifname("eth0").is_ok());
+        assert!(validate_ifname("wlp2s0").is_ok());
+        assert!(validate_ifname("bond0.100").is_ok());
+        assert!(validate_ifname("a23456789012345").is_ok());
+    }
+
+    #[test]
+    fn invalid_ifname() {
+        assert!(validate_ifname("").is_err());
+        assert!(validate_ifname("a234567890123456").is_err());
+        assert!(validate_ifname("eth/0").is_err());
+        assert!(validate_ifname("eth 0").is_err());
+        assert!(validate_ifname("eth0\t").is_err());
+
+        let err = validate_ifname("eth/0").unwrap_err().to_string();
+        assert!(err.contains("\"eth/0\""));
+    }
+
+    #[test]
+    fn ssid() {
+        assert!(validate_ssid("test_ssid").is_ok());
+        assert!(validate_ssid(&"a".repeat(32)).is_ok());
+
+        assert!(validate_ssid("").is_err());
+        assert!(validate_ssid(&"a".repeat(33)).is_err());
+
+        // 16 two-byte characters is 32 bytes, one more is too long
+        assert!(validate_ssid(&"ü".repeat(16)).is_ok());
+        let err = validate_ssid(&"ü".repeat(17)).unwrap_err().to_string();
+        assert!(err.contains("34 bytes"));
+    }
+
+    #[test]
+    fn cloned_mac() {
+        assert!(validate_cloned_mac("00:11:22:aa:bb:cc").is_ok());
+        assert!(validate_cloned_mac("random").is_ok());
+        assert!(validate_cloned_mac("stable").is_ok());
+        assert!(validate_cloned_mac("permanent").is_ok());
+
+        assert!(validate_cloned_mac("").is_err());
+        assert!(validate_cloned_mac("Random").is_err());
+        assert!(validate_cloned_mac("00:11:22:aa:bb").is_err());
+    }
+
+    #[test]
+    fn bssid() {
+        assert!(validate_bssid("00:11:22:aa:bb:cc").is_ok());
+        assert!(validate_bssid("00:11:22:AA:BB:CC").is_ok());
+
+        assert!(validate_bssid("").is_err());
+        assert!(validate_bssid("00:11:22:aa:bb").is_err());
+        assert!(validate_bssid("00-11-22-aa-bb-cc").is_err());
+        assert!(validate_bssid("00:11:22:aa:bb:cg").is_err());
+    }
+
+    #[test]
+    fn min_signal() {
+        assert!(validate_min_signal(0).is_ok());
+        assert!(validate_min_signal(MAX_SIGNAL).is_ok());
+        assert!(validate_min_signal(MAX_SIGNAL + 1).is_err());
+    }
+
+    #[test]
+    fn mtu() {
+        assert!(validate_mtu(68).is_ok());
+        assert!(validate_mtu(1500).is_ok());
+        assert!(validate_mtu(9000).is_ok());
+
+        assert!(validate_mtu(0).is_err());
+        assert!(validate_mtu(67).is_err());
+        assert!(validate_mtu(9001).is_err());
+    }
+
+    #[test]
+    fn mac_addr() {
+        assert!(is_valid_mac_addr("00:11:22:aa:bb:cc"));
+        assert!(is_valid_mac_addr("00:11:22:AA:BB:CC"));
+
+        assert!(!is_valid_mac_addr(""));
+        assert!(!is_valid_mac_addr("00:11:22:aa:bb"));
+        assert!(!is_valid_mac_addr("00:11:22:aa:bb:cc:dd"));
+        assert!(!is_valid_mac_addr("00-11-22-aa-bb-cc"));
+        assert!(!is_valid_mac_addr("0:11:22:aa:bb:cc"));
+        assert!(!is_valid_mac_addr("00:11:22:aa:bb:zz"));
+    }
+
+    const TEST_FIELDS: &[&str] = &["bond_interface", "bond_mode", "slave_interfaces"];
+
+    #[test]
+    fn config_overrides() {
+        let cfg = "
+            bond_interface: \"bond0\"
+            bond_mode: !ActiveBackup
+        ";
+
+        let overrides = vec![
+            "bond_mode=!RoundRobin".to_string(),
+            "slave_interfaces=[eth0, eth1]".to_string(),
+        ];
+        let merged = apply_config_overrides(cfg, &overrides, TEST_FIELDS).unwrap();
+        let fields: Mapping = serde_yaml::from_str(&merged).unwrap();
+
+        assert_eq!(fields["bond_interface"], Value::from("bond0"));
+        assert_eq!(
+            fields["slave_interfaces"],
+            serde_yaml::from_str::<Value>("[eth0, eth1]").unwrap()
+        );
+        assert!(merged.contains("!RoundRobin"));
+
+        // No overrides, config used as-is
+        assert_eq!(apply_config_overrides(cfg, &[], TEST_FIELDS).unwrap(), cfg);
+    }
+
+    #[test]
+    fn invalid_config_overrides() {
+        let cfg = "bond_interface: \"bond0\"";
+
+        let invalid = |o: &str| apply_config_overrides(cfg, &[o.to_string()], TEST_FIELDS);
+        assert!(invalid("bond_mode").is_err());
+        assert!(invalid("=bond1").is_err());
+        assert!(invalid("bond_interface=[bond1").is_err());
+
+        // Unknown fields rejected, e.g. typos
+        assert!(invalid("bond_mdoe=!RoundRobin").is_err());
+    }
+
+    #[test]
+    fn stdin_config() {
+        // Piped config, as read from stdin
+        let cfg = "
+            bond_interface: bond0
+            bond_mode: !ActiveBackup
+        ";
+
+        let config = read_config_from(Box::new(cfg.as_bytes())).unwrap();
+        assert_eq!(config, cfg);
+
+        let fields: Mapping = serde_yaml::from_str(&config).unwrap();
+        assert_eq!(fields["bond_interface"], Value::from("bond0"));
+
+        // Config must be UTF-8
+        assert!(read_config_from(Box::new(&[0xff, 0xfe][..])).is_err());
+    }
+}